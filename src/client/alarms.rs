@@ -0,0 +1,391 @@
+use std::collections::VecDeque;
+
+use bytes::{Buf, BytesMut};
+
+use super::create::S7Client;
+use crate::errors::{DataItemErrorMapper, Error};
+use crate::s7_protocol::user_data::{
+    build_user_data_request, check_return_code, exchange_user_data, is_pushed_user_data,
+    parse_pushed_user_data, parse_user_data_response, GROUP_CPU_FUNCTIONS, RETURN_CODE_SUCCESS,
+    SUBFUNCTION_ALARM_ACK, SUBFUNCTION_ALARM_SQ_INDICATION, SUBFUNCTION_ALARM_S_INDICATION,
+    SUBFUNCTION_MESSAGE_SERVICE,
+};
+use crate::time::{decode_dt, S7DateTime};
+
+// Events of the message service, only alarms are subscribed
+const EVENTS_ALARMS: u8 = 0x80;
+// Alarm types of the message service
+const ALARM_S_ABORT: u8 = 0x08;
+const ALARM_S_INITIATE: u8 = 0x09;
+// Name of the subscriber shown by the CPU (8 characters)
+const SUBSCRIBER_NAME: &[u8; 8] = b"s7client";
+
+// Function identifier and syntax id of an alarm acknowledgement
+const FUNCTION_ALARM_ACK: u8 = 0x09;
+const SYNTAX_ID_ALARM_ACKSET: u8 = 0x19;
+// Variable specification of a message object
+const VARIABLE_SPECIFICATION: u8 = 0x12;
+
+/// Block type that generated an alarm message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmKind {
+    /// Message of an `ALARM_S` block, acknowledgement is optional
+    AlarmS,
+    /// Message of an `ALARM_SQ` block that has to be acknowledged
+    AlarmSq,
+}
+
+/// Alarm message pushed by the CPU, see [`S7Client::subscribe_alarms`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlarmEvent {
+    /// Block type that generated the message
+    pub kind: AlarmKind,
+    /// Time the CPU generated the message
+    pub timestamp: S7DateTime,
+    /// Number of the message (event id)
+    pub message_number: u32,
+    /// Whether the message is coming (signal changed to 1) or going (signal changed to 0)
+    pub coming: bool,
+    /// State of the event as sent by the CPU
+    pub event_state: u8,
+    /// Acknowledgement state of the going message
+    pub ack_state_going: u8,
+    /// Acknowledgement state of the coming message
+    pub ack_state_coming: u8,
+    /// Raw bytes of the values associated with the message
+    pub associated_values: Vec<Vec<u8>>,
+}
+
+impl AlarmEvent {
+    /// Parse the message objects of an alarm indication
+    ///
+    /// The indication starts with the timestamp (8 bytes), the function identifier and the number
+    /// of message objects. Every object consists of its specification, message number and states
+    /// followed by the associated values.
+    fn parse_indication(
+        kind: AlarmKind,
+        mut data: BytesMut,
+        mapper: Option<&DataItemErrorMapper>,
+    ) -> Result<Vec<Self>, Error> {
+        let invalid = |data: &BytesMut| {
+            Error::TryFrom(
+                data.to_vec(),
+                "Invalid length for alarm indication".to_string(),
+            )
+        };
+        if data.len() < 10 {
+            return Err(invalid(&data));
+        }
+        let timestamp = decode_dt(&data.split_to(8))?;
+        let _function_identifier = data.get_u8();
+        let object_count = data.get_u8();
+
+        let mut events = Vec::with_capacity(usize::from(object_count));
+        for _ in 0..object_count {
+            if data.len() < 12 {
+                return Err(invalid(&data));
+            }
+            let _variable_specification = data.get_u8();
+            let specification_len = usize::from(data.get_u8());
+            if specification_len < 10 || data.len() < specification_len {
+                return Err(invalid(&data));
+            }
+            let mut specification = data.split_to(specification_len);
+            let _syntax_id = specification.get_u8();
+            let value_count = specification.get_u8();
+            let message_number = specification.get_u32();
+            let event_state = specification.get_u8();
+            let state = specification.get_u8();
+            let ack_state_going = specification.get_u8();
+            let ack_state_coming = specification.get_u8();
+
+            let associated_values = (0..value_count)
+                .map(|_| parse_associated_value(&mut data, mapper))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            events.push(Self {
+                kind,
+                timestamp,
+                message_number,
+                coming: state & 0x01 != 0,
+                event_state,
+                ack_state_going,
+                ack_state_coming,
+                associated_values,
+            });
+        }
+        Ok(events)
+    }
+}
+
+/// Split off an associated value (return code, transport size, length and data) of a message object
+fn parse_associated_value(
+    data: &mut BytesMut,
+    mapper: Option<&DataItemErrorMapper>,
+) -> Result<Vec<u8>, Error> {
+    if data.len() < 4 {
+        return Err(Error::TryFrom(
+            data.to_vec(),
+            "Invalid length for associated value".to_string(),
+        ));
+    }
+    let return_code = data.get_u8();
+    let transport_size = data.get_u8();
+    let length = usize::from(data.get_u16());
+    // the length of bits, bytes and integers is given in bits
+    let length = match transport_size {
+        0x03..=0x05 => length.div_ceil(8),
+        _ => length,
+    }
+    .min(data.len());
+    let value = data.split_to(length).to_vec();
+    check_return_code(return_code, mapper)?;
+    Ok(value)
+}
+
+/// Subscription to the alarm messages of the CPU, created by [`S7Client::subscribe_alarms`]
+///
+/// The subscription borrows the client exclusively, as the CPU pushes the messages over its
+/// connection at any time.
+#[derive(Debug)]
+pub struct AlarmStream<'a> {
+    client: &'a mut S7Client,
+    pending: VecDeque<AlarmEvent>,
+}
+
+impl AlarmStream<'_> {
+    /// Wait for the next alarm message of the CPU
+    ///
+    /// There is no timeout, wrap the call into `tokio::time::timeout` to limit the waiting time.
+    /// Data pushed by the CPU that is no alarm message is skipped.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error` if the connection failed or a message could not be parsed.
+    pub async fn next(&mut self) -> Result<AlarmEvent, Error> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+            match self.client.receive_pushed().await {
+                Ok(pdu) if is_pushed_user_data(&pdu) => self.queue_indication(pdu)?,
+                // a late response to a request is of no interest anymore
+                Ok(_) => {}
+                Err(error) => return Err(self.closed_on_connection_error(error)),
+            }
+        }
+    }
+
+    /// Acknowledge an alarm message, e.g. of an `ALARM_SQ` block
+    ///
+    /// Alarm messages the CPU pushes while waiting for the acknowledgement are kept for [`AlarmStream::next`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error` if the CPU rejected the acknowledgement or any errors occurred during the exchange.
+    pub async fn acknowledge(&mut self, event: &AlarmEvent) -> Result<(), Error> {
+        let mut payload = vec![
+            FUNCTION_ALARM_ACK,
+            0x01, // number of message objects
+            VARIABLE_SPECIFICATION,
+            0x08, // length of the following specification
+            SYNTAX_ID_ALARM_ACKSET,
+            0x01, // number of message numbers
+        ];
+        payload.extend(event.message_number.to_be_bytes());
+        payload.extend([u8::from(!event.coming), u8::from(event.coming)]);
+
+        let request = build_user_data_request(
+            self.client,
+            GROUP_CPU_FUNCTIONS,
+            SUBFUNCTION_ALARM_ACK,
+            0,
+            RETURN_CODE_SUCCESS,
+            &payload,
+        )?;
+        let pdu_number = self.client.pdu_number;
+        if let Err(error) = self.client.send(request).await {
+            return Err(self.closed_on_connection_error(error));
+        }
+
+        loop {
+            let pdu = match self.client.receive().await {
+                Ok(pdu) => pdu,
+                Err(error) => return Err(self.closed_on_connection_error(error)),
+            };
+            if is_pushed_user_data(&pdu) {
+                self.queue_indication(pdu)?;
+                continue;
+            }
+            let response =
+                parse_user_data_response(pdu, pdu_number, self.client.config.strict_pdu_reference)?;
+            return check_return_code(response.return_code, self.client.config.error_mapper());
+        }
+    }
+
+    /// End the subscription to the alarm messages
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error` if the CPU rejected the request or any errors occurred during the exchange.
+    pub async fn unsubscribe(self) -> Result<(), Error> {
+        subscribe(self.client, ALARM_S_ABORT).await
+    }
+
+    fn queue_indication(&mut self, pdu: BytesMut) -> Result<(), Error> {
+        let mapper = self.client.config.error_mapper();
+        let pushed = parse_pushed_user_data(pdu, mapper)?;
+        if pushed.function_group != GROUP_CPU_FUNCTIONS {
+            return Ok(());
+        }
+        let kind = match pushed.subfunction {
+            SUBFUNCTION_ALARM_S_INDICATION => AlarmKind::AlarmS,
+            SUBFUNCTION_ALARM_SQ_INDICATION => AlarmKind::AlarmSq,
+            _ => return Ok(()),
+        };
+        self.pending
+            .extend(AlarmEvent::parse_indication(kind, pushed.data, mapper)?);
+        Ok(())
+    }
+
+    fn closed_on_connection_error(&mut self, error: Error) -> Error {
+        if error.is_connection_error() {
+            self.client.set_closed();
+        }
+        error
+    }
+}
+
+/// Start or end the subscription to the alarm messages with the message service of the CPU
+async fn subscribe(client: &mut S7Client, alarm_type: u8) -> Result<(), Error> {
+    let mut payload = vec![EVENTS_ALARMS, 0x00];
+    payload.extend(SUBSCRIBER_NAME);
+    payload.extend([alarm_type, 0x00]);
+
+    match exchange_user_data(
+        client,
+        GROUP_CPU_FUNCTIONS,
+        SUBFUNCTION_MESSAGE_SERVICE,
+        0,
+        RETURN_CODE_SUCCESS,
+        &payload,
+    )
+    .await
+    {
+        Ok(response) => check_return_code(response.return_code, client.config.error_mapper()),
+        Err(error) => {
+            if error.is_connection_error() {
+                client.set_closed();
+            }
+            Err(error)
+        }
+    }
+}
+
+impl S7Client {
+    /// Subscribe to the alarm messages (`ALARM_S` and `ALARM_SQ`) the CPU pushes
+    ///
+    /// The returned [`AlarmStream`] delivers the parsed messages and allows to acknowledge them.
+    /// While subscribed the client can not be used for other requests.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300).await?;
+    /// let mut alarms = client.subscribe_alarms().await?;
+    /// let alarm = alarms.next().await?;
+    /// println!("message {} coming: {}", alarm.message_number, alarm.coming);
+    /// alarms.acknowledge(&alarm).await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if the CPU rejected the subscription or any errors occurred during the exchange.
+    pub async fn subscribe_alarms(&mut self) -> Result<AlarmStream<'_>, Error> {
+        self.validate_connection_info().await?;
+        subscribe(self, ALARM_S_INITIATE).await?;
+
+        Ok(AlarmStream {
+            client: self,
+            pending: VecDeque::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    fn alarm_s_indication(message_number: u32, state: u8, value: &[u8]) -> Vec<u8> {
+        // 2024-03-05 14:30:15.250, function identifier, one message object
+        let mut data = vec![0x24, 0x03, 0x05, 0x14, 0x30, 0x15, 0x25, 0x03, 0x00, 0x01];
+        data.extend([0x12, 0x0A, 0x09, 0x01]);
+        data.extend(message_number.to_be_bytes());
+        data.extend([0x00, state, 0x00, 0x00]);
+        data.extend([0xFF, 0x04]);
+        data.extend((u16::try_from(value.len()).unwrap() * 8).to_be_bytes());
+        data.extend(value);
+        data
+    }
+
+    #[tokio::test]
+    async fn pushed_alarm_is_parsed_and_acknowledged() {
+        let plc = MockPlc::start().await;
+        plc.push_user_data(
+            SUBFUNCTION_ALARM_S_INDICATION,
+            &alarm_s_indication(0x0851_0001, 0x01, &[0x12, 0x34]),
+        );
+        plc.push_user_data(
+            SUBFUNCTION_ALARM_SQ_INDICATION,
+            &alarm_s_indication(0x0851_0002, 0x00, &[0x56]),
+        );
+        let mut client = plc.client().await;
+
+        let mut alarms = client.subscribe_alarms().await.unwrap();
+        let alarm = alarms.next().await.unwrap();
+        assert_eq!(alarm.kind, AlarmKind::AlarmS);
+        assert_eq!(alarm.message_number, 0x0851_0001);
+        assert!(alarm.coming);
+        assert_eq!(alarm.associated_values, vec![vec![0x12, 0x34]]);
+        assert_eq!(
+            alarm.timestamp,
+            S7DateTime::new(2024, 3, 5, 14, 30, 15, 250_000_000).unwrap()
+        );
+
+        // the second alarm arrives before the response to the acknowledgement
+        alarms.acknowledge(&alarm).await.unwrap();
+        let alarm = alarms.next().await.unwrap();
+        assert_eq!(alarm.kind, AlarmKind::AlarmSq);
+        assert_eq!(alarm.message_number, 0x0851_0002);
+        assert!(!alarm.coming);
+        alarms.unsubscribe().await.unwrap();
+
+        let acknowledgements = plc
+            .state()
+            .requests
+            .iter()
+            .filter(|pdu| pdu[1] == 0x07 && pdu[16] == SUBFUNCTION_ALARM_ACK)
+            .map(|pdu| pdu[pdu.len() - 6..].to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            acknowledgements,
+            vec![vec![0x08, 0x51, 0x00, 0x01, 0x00, 0x01]]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_subscription() {
+        let plc = MockPlc::start().await;
+        plc.state().message_service_supported = false;
+        let mut client = plc.client().await;
+
+        assert!(matches!(
+            client.subscribe_alarms().await,
+            Err(Error::FunctionNotSupported)
+        ));
+    }
+}
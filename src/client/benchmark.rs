@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use super::create::S7Client;
+use super::pooled::S7Pool;
+use crate::errors::Error;
+
+/// Result of [`S7Client::benchmark_reads`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchmarkResult {
+    /// Number of completed reads
+    pub reads: u64,
+    /// Number of bytes read in total
+    pub bytes: u64,
+    /// Time spent reading
+    pub elapsed: Duration,
+    /// Median duration of a read
+    pub latency_p50: Duration,
+    /// Duration 90 % of the reads completed within
+    pub latency_p90: Duration,
+    /// Duration 99 % of the reads completed within
+    pub latency_p99: Duration,
+    /// Duration of the slowest read
+    pub latency_max: Duration,
+}
+
+impl BenchmarkResult {
+    fn new(latencies: &mut [Duration], length: u16, elapsed: Duration) -> Self {
+        latencies.sort_unstable();
+        let percentile = |percent: usize| {
+            latencies
+                .get(latencies.len().saturating_sub(1) * percent / 100)
+                .copied()
+                .unwrap_or_default()
+        };
+        let reads = latencies.len() as u64;
+
+        Self {
+            reads,
+            bytes: reads * u64::from(length),
+            elapsed,
+            latency_p50: percentile(50),
+            latency_p90: percentile(90),
+            latency_p99: percentile(99),
+            latency_max: percentile(100),
+        }
+    }
+
+    /// Average number of completed reads per second
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn reads_per_second(&self) -> f64 {
+        self.reads as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Average number of bytes read per second
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bytes_per_second(&self) -> f64 {
+        self.bytes as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+impl S7Client {
+    /// Measure the read performance of the connection by reading the same bytes of a data block repeatedly
+    ///
+    /// Reads are issued one after another until `duration` has passed, at least one read is issued.
+    /// The duration of each read is measured including the splitting of reads that exceed the PDU length.
+    /// Meant as a commissioning aid, the benchmark occupies the connection for the whole duration.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let result = client.benchmark_reads(100, 200, Duration::from_secs(10))
+    ///     .await?;
+    /// println!("{:.1} reads/s, p99 latency {:?}", result.reads_per_second(), result.latency_p99);
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any of the reads failed, the benchmark is aborted.
+    pub async fn benchmark_reads(
+        &mut self,
+        db_number: u16,
+        length: u16,
+        duration: Duration,
+    ) -> Result<BenchmarkResult, Error> {
+        let mut latencies = Vec::new();
+        let started = Instant::now();
+        loop {
+            let read_started = Instant::now();
+            self.db_read(db_number, 0, length).await?;
+            latencies.push(read_started.elapsed());
+
+            if started.elapsed() >= duration {
+                break;
+            }
+        }
+
+        Ok(BenchmarkResult::new(
+            &mut latencies,
+            length,
+            started.elapsed(),
+        ))
+    }
+}
+
+impl S7Pool {
+    /// Measure the read performance of one connection of the pool by reading the same bytes of a data block repeatedly
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let result = pool.benchmark_reads(100, 200, Duration::from_secs(10))
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any of the reads failed, the benchmark is aborted.
+    pub async fn benchmark_reads(
+        &self,
+        db_number: u16,
+        length: u16,
+        duration: Duration,
+    ) -> Result<BenchmarkResult, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection
+            .benchmark_reads(db_number, length, duration)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    #[test]
+    fn percentiles_of_latencies() {
+        let mut latencies: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+        let result = BenchmarkResult::new(&mut latencies, 4, Duration::from_secs(2));
+
+        assert_eq!(result.reads, 100);
+        assert_eq!(result.bytes, 400);
+        assert_eq!(result.latency_p50, Duration::from_millis(50));
+        assert_eq!(result.latency_p99, Duration::from_millis(99));
+        assert_eq!(result.latency_max, Duration::from_millis(100));
+        assert!((result.reads_per_second() - 50.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn benchmark_against_mock() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00; 100]);
+        let mut client = plc.client().await;
+
+        let result = client
+            .benchmark_reads(1, 100, Duration::from_millis(50))
+            .await
+            .unwrap();
+
+        assert!(result.reads > 0);
+        assert_eq!(result.bytes, result.reads * 100);
+        assert_eq!(client.requests_issued(), result.reads);
+        assert!(result.latency_p50 <= result.latency_max);
+    }
+}
@@ -0,0 +1,168 @@
+//! Packing and unpacking of consecutive bits in the LSB-first order of S7 PLCs.
+
+use crate::errors::Error;
+
+/// Number of bytes covered by `count` bits starting at bit `start_bit` of the first byte
+pub(crate) fn byte_span(start_bit: u8, count: usize) -> usize {
+    (usize::from(start_bit) + count).div_ceil(8)
+}
+
+/// Check that `data` holds all bytes covered by `count` bits starting at bit `start_bit`
+fn expect_span(data: &[u8], start_bit: u8, count: usize) -> Result<(), Error> {
+    let span = byte_span(start_bit, count);
+    if data.len() < span {
+        return Err(Error::Conversion(format!(
+            "Expected {span} bytes but got {}",
+            data.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Unpack `count` bits starting at bit `start_bit` of the first byte
+pub(crate) fn unpack_bits(data: &[u8], start_bit: u8, count: usize) -> Result<Vec<bool>, Error> {
+    expect_span(data, start_bit, count)?;
+    Ok((usize::from(start_bit)..usize::from(start_bit) + count)
+        .map(|bit| data[bit / 8] & (1 << (bit % 8)) != 0)
+        .collect())
+}
+
+/// Pack `bits` into `data` starting at bit `start_bit` of the first byte, leaving all other bits untouched
+pub(crate) fn pack_bits(data: &mut [u8], start_bit: u8, bits: &[bool]) -> Result<(), Error> {
+    expect_span(data, start_bit, bits.len())?;
+    for (offset, value) in bits.iter().enumerate() {
+        let bit = usize::from(start_bit) + offset;
+        let mask = 1 << (bit % 8);
+        if *value {
+            data[bit / 8] |= mask;
+        } else {
+            data[bit / 8] &= !mask;
+        }
+    }
+    Ok(())
+}
+
+/// Number of bytes covered by a bitfield, which may cross at most one byte boundary
+pub(crate) fn bitfield_span(start_bit: u8, bit_width: u8) -> Result<u16, Error> {
+    let end = u16::from(start_bit) + u16::from(bit_width);
+    if start_bit > 7 || bit_width == 0 || end > 16 {
+        return Err(Error::RequestedBitOutOfRange);
+    }
+    Ok(end.div_ceil(8))
+}
+
+/// Extract the unsigned value of `bit_width` bits starting at bit `start_bit` of the first byte
+pub(crate) fn extract_bitfield(data: &[u8], start_bit: u8, bit_width: u8) -> Result<u32, Error> {
+    Ok(unpack_bits(data, start_bit, bit_width.into())?
+        .iter()
+        .rev()
+        .fold(0, |value, bit| value << 1 | u32::from(*bit)))
+}
+
+/// Insert the unsigned `value` of `bit_width` bits at bit `start_bit` of the first byte, leaving all other bits untouched
+pub(crate) fn insert_bitfield(
+    data: &mut [u8],
+    start_bit: u8,
+    bit_width: u8,
+    value: u32,
+) -> Result<(), Error> {
+    if u64::from(value) >> bit_width != 0 {
+        return Err(Error::Conversion(format!(
+            "Value {value} does not fit into {bit_width} bits"
+        )));
+    }
+
+    let bits = (0..bit_width)
+        .map(|bit| value & (1 << bit) != 0)
+        .collect::<Vec<bool>>();
+    pack_bits(data, start_bit, &bits)
+}
+
+/// Expand a WORD into its 16 bits, index `n` holding bit `n` of the value
+pub(crate) fn word_to_flags(word: u16) -> [bool; 16] {
+    std::array::from_fn(|bit| word & (1 << bit) != 0)
+}
+
+/// Pack 16 bits into a WORD, index `n` providing bit `n` of the value
+pub(crate) fn flags_to_word(flags: &[bool; 16]) -> u16 {
+    flags
+        .iter()
+        .rev()
+        .fold(0, |word, flag| word << 1 | u16::from(*flag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_covers_partial_bytes() {
+        assert_eq!(byte_span(0, 0), 0);
+        assert_eq!(byte_span(0, 8), 1);
+        assert_eq!(byte_span(7, 1), 1);
+        assert_eq!(byte_span(7, 2), 2);
+        assert_eq!(byte_span(3, 16), 3);
+    }
+
+    #[test]
+    fn unpack_across_byte_boundary() {
+        // bits 6 and 7 of the first byte, bits 0 to 2 of the second byte
+        let data = [0b1000_0000, 0b0000_0101];
+        assert_eq!(
+            unpack_bits(&data, 6, 5),
+            Ok(vec![false, true, true, false, true])
+        );
+        assert!(matches!(
+            unpack_bits(&data[..1], 6, 5),
+            Err(Error::Conversion(_))
+        ));
+    }
+
+    #[test]
+    fn pack_keeps_surrounding_bits() {
+        let mut data = [0b0011_1111, 0b1111_0000];
+        let bits = [true, false, true, true, false, true];
+        pack_bits(&mut data, 6, &bits).unwrap();
+        assert_eq!(data, [0b0111_1111, 0b1111_1011]);
+        assert_eq!(unpack_bits(&data, 6, 6), Ok(bits.to_vec()));
+        assert!(matches!(
+            pack_bits(&mut data[..1], 6, &bits),
+            Err(Error::Conversion(_))
+        ));
+    }
+
+    #[test]
+    fn bitfield_crosses_one_byte_boundary() {
+        assert_eq!(bitfield_span(2, 3), Ok(1));
+        assert_eq!(bitfield_span(6, 4), Ok(2));
+        assert_eq!(bitfield_span(4, 13), Err(Error::RequestedBitOutOfRange));
+        assert_eq!(bitfield_span(0, 0), Err(Error::RequestedBitOutOfRange));
+        assert_eq!(bitfield_span(8, 1), Err(Error::RequestedBitOutOfRange));
+
+        let mut data = [0b1100_0000, 0b1111_1110];
+        assert_eq!(extract_bitfield(&data, 6, 4), Ok(0b1011));
+        insert_bitfield(&mut data, 6, 4, 0b1001).unwrap();
+        assert_eq!(data, [0b0100_0000, 0b1111_1110]);
+        assert!(matches!(
+            insert_bitfield(&mut data, 6, 4, 16),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            extract_bitfield(&data[..1], 6, 4),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            insert_bitfield(&mut data[..1], 6, 4, 0b1001),
+            Err(Error::Conversion(_))
+        ));
+    }
+
+    #[test]
+    fn word_flags_follow_bit_numbers() {
+        let flags = word_to_flags(0x0001);
+        assert!(flags[0]);
+        assert!(flags[1..].iter().all(|flag| !flag));
+        assert!(word_to_flags(0x8000)[15]);
+        assert_eq!(flags_to_word(&word_to_flags(0x1234)), 0x1234);
+    }
+}
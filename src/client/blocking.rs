@@ -0,0 +1,160 @@
+use std::net::IpAddr;
+
+use tokio::runtime::{Builder, Runtime};
+
+use super::builder::S7ClientBuilder;
+use super::create::S7Client;
+use super::szl::{CpuInfo, CpuStatus};
+use super::IoMode;
+use crate::connection::iso::S7Types;
+use crate::errors::Error;
+use crate::s7_protocol::types::Area;
+use crate::time::S7DateTime;
+use crate::{S7ReadAccess, S7WriteAccess};
+
+/// Synchronous wrapper around an [`S7Client`] for code without an async runtime
+///
+/// Each method runs the corresponding async method of [`S7Client`] to completion on a
+/// current-thread runtime that is created together with the client and reused for every call.
+/// Do not use it from within an async context, blocking inside a runtime panics.
+///
+/// # Example
+/// ```rust,no_run
+/// # use std::net::Ipv4Addr;
+/// use s7client::{BlockingS7Client, S7Types};
+///
+/// let mut client = BlockingS7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+/// let data = client.db_read(100, 0, 4)?;
+/// # Ok::<(), s7client::errors::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct BlockingS7Client {
+    // declared before the runtime so the connection is dropped while the runtime still exists
+    client: S7Client,
+    runtime: Runtime,
+}
+
+/// Generate blocking variants of `S7Client` methods taking `&mut self`
+macro_rules! blocking {
+    ($(fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+        $(
+            #[doc = concat!("Blocking variant of [`S7Client::", stringify!($name), "`]")]
+            ///
+            /// # Errors
+            ///
+            #[doc = concat!("Will return `Error` in the same cases as [`S7Client::", stringify!($name), "`].")]
+            pub fn $name(&mut self $(, $arg: $ty)*) -> Result<$ret, Error> {
+                self.runtime.block_on(self.client.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+impl BlockingS7Client {
+    /// Connect to a PLC like [`S7Client::new`]
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error::IO` if the runtime could not be created or `Error` if the connection could not be established.
+    pub fn new(ip: impl Into<IpAddr>, s7_type: S7Types) -> Result<Self, Error> {
+        Self::connect_with(S7Client::builder(ip, s7_type))
+    }
+
+    /// Connect to a PLC described by a connection string like [`S7Client::connect_str`]
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the connection string is invalid, `Error::IO` if the runtime
+    /// could not be created or `Error` if the connection could not be established.
+    pub fn connect_str(connection_str: &str) -> Result<Self, Error> {
+        Self::connect_with(S7ClientBuilder::from_connection_str(connection_str)?)
+    }
+
+    /// Connect to a PLC with the configuration of `builder`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error::IO` if the runtime could not be created or `Error` if the connection could not be established.
+    pub fn connect_with(builder: S7ClientBuilder) -> Result<Self, Error> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        let client = runtime.block_on(builder.connect())?;
+        Ok(Self { client, runtime })
+    }
+
+    /// The wrapped client, e.g. for its counters and negotiated parameters
+    #[must_use]
+    pub fn get_ref(&self) -> &S7Client {
+        &self.client
+    }
+
+    /// The wrapped client, e.g. for changing its configuration
+    ///
+    /// Its async methods can not be awaited without a runtime, use the blocking methods instead.
+    pub fn get_mut(&mut self) -> &mut S7Client {
+        &mut self.client
+    }
+
+    blocking! {
+        fn connect(&mut self) -> ();
+        fn disconnect(&mut self) -> ();
+        fn db_read(&mut self, db_number: u16, start: u32, length: u16) -> Vec<u8>;
+        fn db_read_bit(&mut self, db_number: u16, byte: u32, bit: u8) -> bool;
+        fn db_read_multi(&mut self, info: &[S7ReadAccess]) -> Vec<Result<Vec<u8>, Error>>;
+        fn db_write(&mut self, db_number: u16, start: u32, data: &[u8]) -> ();
+        fn db_write_bit(&mut self, db_number: u16, byte: u32, bit: u8, value: bool) -> ();
+        fn db_write_multi(&mut self, info: &[S7WriteAccess<'_>]) -> Vec<Result<(), Error>>;
+        fn mb_read(&mut self, start: u32, length: u16) -> Vec<u8>;
+        fn mb_write(&mut self, start: u32, data: &[u8]) -> ();
+        fn i_read(&mut self, start: u32, length: u16) -> Vec<u8>;
+        fn o_read(&mut self, start: u32, length: u16) -> Vec<u8>;
+        fn o_write(&mut self, start: u32, data: &[u8]) -> ();
+        fn io_read(&mut self, mode: IoMode, start: u32, length: u16) -> Vec<u8>;
+        fn io_write(&mut self, mode: IoMode, start: u32, data: &[u8]) -> ();
+        fn read(&mut self, area: Area, access: S7ReadAccess) -> Vec<u8>;
+        fn write(&mut self, area: Area, access: S7WriteAccess<'_>) -> ();
+        fn get_plc_clock(&mut self) -> S7DateTime;
+        fn set_plc_clock(&mut self, date_time: S7DateTime) -> ();
+        fn cpu_info(&mut self) -> CpuInfo;
+        fn cpu_status(&mut self) -> CpuStatus;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::BlockingS7Client;
+    use crate::mock::MockPlc;
+
+    /// Serve a mock PLC from a runtime on its own thread, the test itself has none
+    fn start_plc() -> MockPlc {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                sender.send(MockPlc::start().await).unwrap();
+                std::future::pending::<()>().await;
+            });
+        });
+        receiver.recv().unwrap()
+    }
+
+    #[test]
+    fn reads_and_writes_without_runtime() {
+        let plc = start_plc();
+        plc.set_db(1, &[0x00; 4]);
+        let mut client = BlockingS7Client::connect_with(plc.builder()).unwrap();
+
+        client.db_write(1, 1, &[0x12, 0x34]).unwrap();
+        client.db_write_bit(1, 3, 7, true).unwrap();
+
+        assert_eq!(client.db_read(1, 0, 4), Ok(vec![0x00, 0x12, 0x34, 0x80]));
+        assert_eq!(client.db_read_bit(1, 3, 7), Ok(true));
+        assert_eq!(plc.db(1), [0x00, 0x12, 0x34, 0x80]);
+        assert_eq!(client.get_ref().requests_issued(), 4);
+        client.disconnect().unwrap();
+    }
+}
@@ -0,0 +1,143 @@
+use super::create::S7Client;
+use super::pooled::S7Pool;
+use crate::errors::Error;
+use crate::s7_protocol::user_data::{
+    check_return_code, exchange_user_data, GROUP_BLOCK_FUNCTIONS, RETURN_CODE_SUCCESS,
+    SUBFUNCTION_BLOCK_INFO,
+};
+
+// block type of data blocks in block requests, prefixed by ASCII '0'
+const BLOCK_TYPE_DB: [u8; 2] = [b'0', b'A'];
+// file system of the blocks loaded into the CPU
+const FILE_SYSTEM_ACTIVE: u8 = b'A';
+// attribute of the block flags set for blocks with optimized access
+const BLOCK_FLAG_OPTIMIZED: u8 = 0x80;
+
+/// Build the payload of a block info request for a data block
+///
+/// The block is addressed by its type, the number as 5 ASCII digits and the file system.
+fn block_info_request(db_number: u16) -> Vec<u8> {
+    let mut payload = BLOCK_TYPE_DB.to_vec();
+    payload.extend(format!("{db_number:05}").into_bytes());
+    payload.push(FILE_SYSTEM_ACTIVE);
+    payload
+}
+
+/// Whether the block info of a data block has the attribute for optimized access set
+///
+/// The block info starts with the block type, the length of the info, two unknown words and an
+/// unknown byte, followed by the block flags (1 byte).
+fn is_optimized(block_info: &[u8]) -> Result<bool, Error> {
+    let flags = block_info.get(9).ok_or_else(|| {
+        Error::TryFrom(
+            block_info.to_vec(),
+            "Invalid length for block info".to_string(),
+        )
+    })?;
+    Ok(flags & BLOCK_FLAG_OPTIMIZED != 0)
+}
+
+impl S7Client {
+    /// Check whether a data block has optimized block access
+    ///
+    /// Optimized data blocks of S7-1200 and S7-1500 CPUs can not be addressed by offset, reading
+    /// them fails with an access error. The attribute is read from the block info of the data block.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71500).await?;
+    /// if client.db_is_optimized(100).await? {
+    ///     println!("Disable the optimized block access of DB100 to read it");
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the data block does not exist or the PLC does not provide block infos
+    /// or `Error` if any errors occurred during reading.
+    pub async fn db_is_optimized(&mut self, db_number: u16) -> Result<bool, Error> {
+        self.validate_connection_info().await?;
+        let response = match exchange_user_data(
+            self,
+            GROUP_BLOCK_FUNCTIONS,
+            SUBFUNCTION_BLOCK_INFO,
+            0,
+            RETURN_CODE_SUCCESS,
+            &block_info_request(db_number),
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                return Err(error);
+            }
+        };
+        check_return_code(response.return_code, self.config.error_mapper())?;
+        is_optimized(&response.data)
+    }
+}
+
+impl S7Pool {
+    /// Check whether a data block has optimized block access
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71500)?;
+    /// let optimized = pool.db_is_optimized(100)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the data block does not exist or the PLC does not provide block infos
+    /// or `Error` if any errors occurred during reading.
+    pub async fn db_is_optimized(&self, db_number: u16) -> Result<bool, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_is_optimized(db_number).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    fn block_info(flags: u8) -> Vec<u8> {
+        let mut info = vec![0x00; 78];
+        info[..2].copy_from_slice(&BLOCK_TYPE_DB);
+        info[9] = flags;
+        info
+    }
+
+    #[test]
+    fn request_addresses_data_block() {
+        assert_eq!(block_info_request(100), b"0A00100A");
+    }
+
+    #[tokio::test]
+    async fn optimized_attribute_is_read_from_block_info() {
+        let plc = MockPlc::start().await;
+        plc.set_block_info(100, &block_info(0x81));
+        plc.set_block_info(101, &block_info(0x01));
+        let mut client = plc.client().await;
+
+        assert_eq!(client.db_is_optimized(100).await, Ok(true));
+        assert_eq!(client.db_is_optimized(101).await, Ok(false));
+        assert_eq!(
+            client.db_is_optimized(102).await,
+            Err(Error::FunctionNotSupported)
+        );
+    }
+}
@@ -0,0 +1,720 @@
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::net::lookup_host;
+use tokio_util::sync::CancellationToken;
+
+use super::create::{S7Client, CONNECTION_TIMEOUT, TCP_PORT};
+use super::events::{ConnectionEvent, EventHandler};
+use crate::connection::tcp::DATA_SEND_AND_RECEIVE_TIMEOUT;
+use crate::errors::{DataItemErrorMapper, Error};
+use crate::S7Types;
+
+// PDU size proposed to the PLC during negotiation
+pub(crate) const DEFAULT_REQUESTED_PDU_SIZE: u16 = 480;
+// scheme of connection strings
+const CONNECTION_STRING_SCHEME: &str = "s7://";
+
+/// Address of the PLC, host names are resolved on every connect
+#[derive(Debug, Clone)]
+pub(crate) enum Host {
+    Ip(IpAddr),
+    Name(String),
+}
+
+/// Configuration of a standalone S7 connection
+///
+/// Allows to configure everything that can not be passed to [`S7Client::new`](crate::S7Client::new).
+/// Every option falls back to the same default `S7Client::new` uses.
+///```rust
+/// # tokio_test::block_on(async {
+/// use std::net::Ipv4Addr;
+/// use std::time::Duration;
+/// use s7client::{S7ClientBuilder, S7Types};
+///
+/// let mut client = S7ClientBuilder::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7400)
+///     .port(1102)
+///     .rack_slot(0, 3)
+///     .connect_timeout(Duration::from_secs(10))
+///     .exchange_timeout(Duration::from_secs(15))
+///     .requested_pdu_size(960)
+///     .connect()
+///     .await?;
+/// # Ok::<(), s7client::errors::Error>(())
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+#[must_use]
+pub struct S7ClientBuilder {
+    pub(crate) host: Host,
+    pub(crate) s7_type: S7Types,
+    pub(crate) port: u16,
+    pub(crate) local_addr: Option<SocketAddr>,
+    pub(crate) rack_slot: Option<(u8, u8)>,
+    pub(crate) connect_timeout: Duration,
+    pub(crate) connect_retries: u32,
+    pub(crate) connect_backoff: Duration,
+    pub(crate) exchange_timeout: Duration,
+    pub(crate) requested_pdu_size: u16,
+    pub(crate) tcp_nodelay: bool,
+    pub(crate) allow_direct_output_writes: bool,
+    pub(crate) allow_plc_control: bool,
+    pub(crate) auto_reconnect: bool,
+    pub(crate) strict_pdu_reference: bool,
+    pub(crate) event_handler: Option<EventHandler>,
+    pub(crate) data_item_error_mapper: Option<DataItemErrorMapper>,
+    pub(crate) cancellation_token: Option<CancellationToken>,
+}
+
+impl S7ClientBuilder {
+    /// Start configuring a connection to an S7 PLC with an IPv4 or IPv6 address
+    pub fn new(ip: impl Into<IpAddr>, s7_type: S7Types) -> Self {
+        Self::with_host(Host::Ip(ip.into()), s7_type)
+    }
+
+    /// Start configuring a connection to an S7 PLC listening on the given socket address
+    ///
+    /// Both IPv4 and IPv6 addresses are supported.
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use std::net::{Ipv6Addr, SocketAddr};
+    /// use s7client::{S7ClientBuilder, S7Types};
+    ///
+    /// let addr = SocketAddr::from((Ipv6Addr::new(0xfd00, 0, 0, 0, 0, 0, 0, 0x72), 102));
+    /// let mut client = S7ClientBuilder::from_socket_addr(addr, S7Types::S71500)
+    ///     .connect()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    pub fn from_socket_addr(addr: SocketAddr, s7_type: S7Types) -> Self {
+        Self::new(addr.ip(), s7_type).port(addr.port())
+    }
+
+    /// Start configuring a connection to an S7 PLC that is addressed by a host name or IP literal
+    ///
+    /// The name is resolved every time a connection is established and every resolved address
+    /// is tried until a connection succeeds.
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use s7client::{S7ClientBuilder, S7Types};
+    ///
+    /// let mut client = S7ClientBuilder::from_host("plc-line-1.plant.local", S7Types::S71500)
+    ///     .connect()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    pub fn from_host(host: impl Into<String>, s7_type: S7Types) -> Self {
+        Self::with_host(Host::Name(host.into()), s7_type)
+    }
+
+    /// Start configuring a connection from a URL-style connection string, e.g. of a configuration file
+    ///
+    /// The string has the form `s7://<host>[:<port>]?type=<type>[&rack=<rack>][&slot=<slot>]`. The
+    /// host is an IP address (IPv6 in brackets) or a host name, `type` is one of `200`, `300`, `400`,
+    /// `1200` or `1500`. The port and the rack and slot fall back to the same defaults as
+    /// [`S7ClientBuilder::new`].
+    ///```rust
+    /// use s7client::S7ClientBuilder;
+    ///
+    /// let builder = S7ClientBuilder::from_connection_str("s7://192.168.10.72:102?type=400&rack=0&slot=3")?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the connection string is malformed.
+    pub fn from_connection_str(connection_str: &str) -> Result<Self, Error> {
+        let invalid = |reason: &str| {
+            Error::Conversion(format!(
+                "Invalid connection string '{connection_str}': {reason}"
+            ))
+        };
+
+        let address = connection_str
+            .strip_prefix(CONNECTION_STRING_SCHEME)
+            .ok_or_else(|| invalid("must start with 's7://'"))?;
+        let (address, query) = address.split_once('?').unwrap_or((address, ""));
+        if address.is_empty() {
+            return Err(invalid("missing host"));
+        }
+
+        let (mut s7_type, mut rack, mut slot) = (None, None, None);
+        for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+            let (key, value) = parameter
+                .split_once('=')
+                .ok_or_else(|| invalid(&format!("parameter '{parameter}' has no value")))?;
+            let number = || {
+                value
+                    .parse::<u8>()
+                    .map_err(|_| invalid(&format!("{key} '{value}' is not a number up to 255")))
+            };
+            match key {
+                "type" => {
+                    s7_type = Some(
+                        parse_s7_type(value)
+                            .ok_or_else(|| invalid(&format!("unknown type '{value}'")))?,
+                    );
+                }
+                "rack" => rack = Some(number()?),
+                "slot" => slot = Some(number()?),
+                _ => return Err(invalid(&format!("unknown parameter '{key}'"))),
+            }
+        }
+        let s7_type = s7_type.ok_or_else(|| invalid("missing parameter 'type'"))?;
+
+        let mut builder = if let Ok(addr) = address.parse::<SocketAddr>() {
+            Self::from_socket_addr(addr, s7_type)
+        } else if let Ok(ip) = address
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+        {
+            Self::new(ip, s7_type)
+        } else {
+            match address.rsplit_once(':') {
+                Some((host, port)) => Self::from_host(host, s7_type).port(
+                    port.parse()
+                        .map_err(|_| invalid(&format!("invalid port '{port}'")))?,
+                ),
+                None => Self::from_host(address, s7_type),
+            }
+        };
+
+        if rack.is_some() || slot.is_some() {
+            let (default_rack, default_slot) = s7_type.default_rack_slot();
+            builder = builder.rack_slot(rack.unwrap_or(default_rack), slot.unwrap_or(default_slot));
+        }
+        Ok(builder)
+    }
+
+    fn with_host(host: Host, s7_type: S7Types) -> Self {
+        Self {
+            host,
+            s7_type,
+            port: TCP_PORT,
+            local_addr: None,
+            rack_slot: None,
+            connect_timeout: CONNECTION_TIMEOUT,
+            connect_retries: 0,
+            connect_backoff: Duration::ZERO,
+            exchange_timeout: DATA_SEND_AND_RECEIVE_TIMEOUT,
+            requested_pdu_size: DEFAULT_REQUESTED_PDU_SIZE,
+            tcp_nodelay: true,
+            allow_direct_output_writes: false,
+            allow_plc_control: false,
+            auto_reconnect: false,
+            strict_pdu_reference: true,
+            event_handler: None,
+            data_item_error_mapper: None,
+            cancellation_token: None,
+        }
+    }
+
+    /// TCP port of the PLC (default 102)
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Local address to bind the connection to before connecting, e.g. to use a specific network interface
+    ///
+    /// On hosts with several network interfaces this forces the traffic to the PLC through the
+    /// interface with the given address. Use port 0 to bind to any free port. Addresses of the PLC
+    /// that do not match the IP version of the local address are not tried.
+    ///```rust
+    /// use std::net::{Ipv4Addr, SocketAddr};
+    /// use s7client::{S7ClientBuilder, S7Types};
+    ///
+    /// let builder = S7ClientBuilder::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)
+    ///     .local_addr(SocketAddr::new(Ipv4Addr::new(192, 168, 10, 2).into(), 0));
+    /// ```
+    pub fn local_addr(mut self, local_addr: SocketAddr) -> Self {
+        self.local_addr = Some(local_addr);
+        self
+    }
+
+    /// Rack and slot of the CPU
+    ///
+    /// Defaults to rack 0 / slot 2 for S7-300/400 and rack 0 / slot 0 for S7-1200/1500, which does
+    /// not fit e.g. S7-400 CPUs in multi-rack configurations.
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use std::net::Ipv4Addr;
+    /// use s7client::{S7Client, S7Pool, S7Types};
+    ///
+    /// // connect to a CPU in rack 1 / slot 3
+    /// let builder = S7Client::builder(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7400).rack_slot(1, 3);
+    /// let mut pool = S7Pool::from_builder(builder.clone(), 2)?;
+    /// let mut client = builder.connect().await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    pub fn rack_slot(mut self, rack: u8, slot: u8) -> Self {
+        self.rack_slot = Some((rack, slot));
+        self
+    }
+
+    /// Maximum time for establishing the TCP connection (default 3 seconds)
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Retry a failed connect up to `retries` times, waiting `initial_backoff` before the first retry (default no retries)
+    ///
+    /// The delay doubles with every further retry, the error of the last attempt is returned if all
+    /// attempts fail. Useful at startup, when the PLC may still be booting. Applies to every
+    /// connection created from this configuration, including the connections of a pool, but not
+    /// to automatic reconnects.
+    ///```rust
+    /// use std::net::Ipv4Addr;
+    /// use std::time::Duration;
+    /// use s7client::{S7ClientBuilder, S7Types};
+    ///
+    /// // attempts after 0.5, 1, 2, 4 and 8 seconds
+    /// let builder = S7ClientBuilder::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)
+    ///     .connect_retries(5, Duration::from_millis(500));
+    /// ```
+    pub fn connect_retries(mut self, retries: u32, initial_backoff: Duration) -> Self {
+        self.connect_retries = retries;
+        self.connect_backoff = initial_backoff;
+        self
+    }
+
+    /// Maximum time for sending a request and receiving its response (default 4 seconds)
+    pub fn exchange_timeout(mut self, exchange_timeout: Duration) -> Self {
+        self.exchange_timeout = exchange_timeout;
+        self
+    }
+
+    /// PDU size proposed to the PLC during negotiation (default 480 bytes)
+    ///
+    /// The PLC may answer with a smaller PDU size which is then used for all requests. The ISO
+    /// TPDU size of the connection request is chosen to fit the requested PDU size.
+    pub fn requested_pdu_size(mut self, requested_pdu_size: u16) -> Self {
+        self.requested_pdu_size = requested_pdu_size;
+        self
+    }
+
+    /// Send requests immediately instead of collecting small segments with Nagle's algorithm (default on)
+    ///
+    /// S7 telegrams are small and every request waits for its response, so delaying them only
+    /// adds latency to each round trip.
+    pub fn tcp_nodelay(mut self, tcp_nodelay: bool) -> Self {
+        self.tcp_nodelay = tcp_nodelay;
+        self
+    }
+
+    /// Allow writing directly to the peripheral outputs with [`S7Client::pa_write`](crate::S7Client::pa_write) (default off)
+    ///
+    /// Direct writes take effect at the output module immediately and bypass the PLC program,
+    /// so they have to be enabled deliberately for every connection that needs them.
+    pub fn allow_direct_output_writes(mut self) -> Self {
+        self.allow_direct_output_writes = true;
+        self
+    }
+
+    /// Allow starting and stopping the CPU with [`S7Client::plc_hot_start`](crate::S7Client::plc_hot_start),
+    /// [`S7Client::plc_cold_start`](crate::S7Client::plc_cold_start) and [`S7Client::plc_stop`](crate::S7Client::plc_stop) (default off)
+    ///
+    /// Changing the operating mode halts or restarts the machine controlled by the PLC, so it has
+    /// to be enabled deliberately for every connection that needs it.
+    pub fn allow_plc_control(mut self) -> Self {
+        self.allow_plc_control = true;
+        self
+    }
+
+    /// Accept responses whose PDU reference does not match the request (default off)
+    ///
+    /// Some simulators and gateways do not echo the PDU reference of the request. With the check
+    /// relaxed a mismatch is only logged as warning (with the `tracing` feature) instead of failing
+    /// with [`Error::ResponseDoesNotBelongToCurrentPDU`](crate::errors::Error::ResponseDoesNotBelongToCurrentPDU).
+    /// Beware that a stale response, e.g. a late answer to a timed out request, is then taken as
+    /// answer to the current request. Requests are no longer pipelined, as responses cannot be
+    /// matched to their requests.
+    pub fn relax_pdu_reference_check(mut self) -> Self {
+        self.strict_pdu_reference = false;
+        self
+    }
+
+    /// Abort running requests once `cancellation_token` is cancelled, e.g. on shutdown of the application
+    ///
+    /// Instead of waiting for the response or the exchange timeout, a request fails with
+    /// [`Error::Cancelled`](crate::errors::Error::Cancelled) and the connection is closed, as a
+    /// partly exchanged telegram leaves it unusable. Pools discard these connections. Every following
+    /// request fails the same way, as long as the token stays cancelled. The token is shared by all
+    /// connections created from this configuration.
+    ///```rust
+    /// use std::net::Ipv4Addr;
+    /// use s7client::{S7ClientBuilder, S7Types};
+    /// use tokio_util::sync::CancellationToken;
+    ///
+    /// let shutdown = CancellationToken::new();
+    /// let builder = S7ClientBuilder::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)
+    ///     .cancellation_token(shutdown.clone());
+    /// ```
+    pub fn cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
+    /// Call `handler` for notable events of the connection, see [`ConnectionEvent`]
+    ///
+    /// The handler is shared by all connections created from this configuration, e.g. all connections of a pool.
+    ///```rust
+    /// use std::net::Ipv4Addr;
+    /// use s7client::{S7ClientBuilder, S7Types};
+    ///
+    /// let builder = S7ClientBuilder::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)
+    ///     .on_event(|event| eprintln!("PLC connection: {event:?}"));
+    /// ```
+    pub fn on_event(mut self, handler: impl Fn(&ConnectionEvent) + Send + Sync + 'static) -> Self {
+        self.event_handler = Some(EventHandler::new(handler));
+        self
+    }
+
+    /// Describe error codes of data items that are not known to this crate with `mapper`
+    ///
+    /// Some third-party servers answer with vendor-specific codes. The `mapper` is consulted for
+    /// every unknown code; if it returns a description the error is reported as
+    /// [`S7DataItemResponseError::Custom`](crate::errors::S7DataItemResponseError::Custom) instead of
+    /// [`S7DataItemResponseError::Unknown`](crate::errors::S7DataItemResponseError::Unknown). The
+    /// mapper is shared by all connections created from this configuration, e.g. all connections of a pool.
+    ///```rust
+    /// use std::net::Ipv4Addr;
+    /// use s7client::{S7ClientBuilder, S7Types};
+    ///
+    /// let builder = S7ClientBuilder::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)
+    ///     .data_item_error_mapper(|code| match code {
+    ///         0x42 => Some("Gateway buffer full".to_string()),
+    ///         _ => None,
+    ///     });
+    /// ```
+    pub fn data_item_error_mapper(
+        mut self,
+        mapper: impl Fn(u8) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.data_item_error_mapper = Some(DataItemErrorMapper::new(mapper));
+        self
+    }
+
+    pub(crate) fn error_mapper(&self) -> Option<&DataItemErrorMapper> {
+        self.data_item_error_mapper.as_ref()
+    }
+
+    pub(crate) fn emit(&self, event: &ConnectionEvent) {
+        if let Some(handler) = &self.event_handler {
+            handler.emit(event);
+        }
+    }
+
+    /// Connect to the PLC with the configured options
+    /// # Errors
+    ///
+    /// Will return `Error` if no connection could be established to the PLC.
+    pub async fn connect(self) -> Result<S7Client, Error> {
+        S7Client::connect_with(self).await
+    }
+
+    /// Resolve the socket addresses of the PLC
+    pub(crate) async fn socket_addrs(&self) -> Result<Vec<SocketAddr>, Error> {
+        match &self.host {
+            Host::Ip(ip) => Ok(vec![SocketAddr::new(*ip, self.port)]),
+            Host::Name(name) => {
+                let addrs = lookup_host((name.as_str(), self.port))
+                    .await
+                    .map_err(|e| Error::Connection(format!("Error on resolving '{name}': {e}")))?
+                    .collect::<Vec<_>>();
+                if addrs.is_empty() {
+                    Err(Error::Connection(format!(
+                        "Error on resolving '{name}': No address found"
+                    )))
+                } else {
+                    Ok(addrs)
+                }
+            }
+        }
+    }
+
+    pub(crate) fn rack_slot_or_default(&self) -> (u8, u8) {
+        self.rack_slot
+            .unwrap_or_else(|| self.s7_type.default_rack_slot())
+    }
+}
+
+fn parse_s7_type(value: &str) -> Option<S7Types> {
+    let value = value
+        .strip_prefix("S7")
+        .or_else(|| value.strip_prefix("s7"))
+        .unwrap_or(value);
+    match value {
+        "200" => Some(S7Types::S7200),
+        "300" => Some(S7Types::S7300),
+        "400" => Some(S7Types::S7400),
+        "1200" => Some(S7Types::S71200),
+        "1500" => Some(S7Types::S71500),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use super::{Host, S7ClientBuilder};
+    use crate::errors::{Error, S7DataItemResponseError};
+    use crate::mock::MockPlc;
+    use crate::{ConnectionEvent, S7Client, S7Types};
+
+    #[tokio::test]
+    async fn builder_configures_connection() {
+        let plc = MockPlc::start().await;
+        plc.state().pdu_length = 240;
+
+        let client = plc
+            .builder()
+            .exchange_timeout(Duration::from_secs(1))
+            .requested_pdu_size(960)
+            .connect()
+            .await
+            .unwrap();
+
+        // the requested PDU size is proposed, the negotiated one is used
+        let negotiation = plc.state().requests[0].clone();
+        assert_eq!(negotiation[10], 0xf0);
+        assert_eq!(u16::from_be_bytes([negotiation[16], negotiation[17]]), 960);
+        assert_eq!(client.pdu_length, 240);
+        assert_eq!(client.config.exchange_timeout, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn full_connection_string_is_parsed() {
+        let builder =
+            S7ClientBuilder::from_connection_str("s7://192.168.0.1:1102?type=400&rack=1&slot=3")
+                .unwrap();
+
+        assert!(matches!(builder.host, Host::Ip(ip) if ip == Ipv4Addr::new(192, 168, 0, 1)));
+        assert_eq!(builder.port, 1102);
+        assert_eq!(builder.s7_type, S7Types::S7400);
+        assert_eq!(builder.rack_slot_or_default(), (1, 3));
+
+        let builder =
+            S7ClientBuilder::from_connection_str("s7://[fd00::72]:102?type=S71500&slot=1").unwrap();
+        assert!(
+            matches!(builder.host, Host::Ip(ip) if ip == Ipv6Addr::new(0xfd00, 0, 0, 0, 0, 0, 0, 0x72))
+        );
+        assert_eq!(builder.rack_slot_or_default(), (0, 1));
+    }
+
+    #[test]
+    fn minimal_connection_string_uses_defaults() {
+        let builder = S7ClientBuilder::from_connection_str("s7://plc-line-1?type=300").unwrap();
+
+        assert!(matches!(&builder.host, Host::Name(name) if name == "plc-line-1"));
+        assert_eq!(builder.port, 102);
+        assert_eq!(builder.s7_type, S7Types::S7300);
+        assert_eq!(builder.rack_slot, None);
+        assert_eq!(builder.rack_slot_or_default(), (0, 2));
+    }
+
+    #[test]
+    fn malformed_connection_strings_are_rejected() {
+        for connection_str in [
+            "192.168.0.1?type=1200",
+            "s7://?type=1200",
+            "s7://192.168.0.1",
+            "s7://192.168.0.1?type=1600",
+            "s7://192.168.0.1?type=1200&slot=300",
+            "s7://192.168.0.1?type=1200&timeout=5",
+            "s7://192.168.0.1?type",
+            "s7://plc:port?type=1200",
+        ] {
+            assert!(
+                matches!(
+                    S7ClientBuilder::from_connection_str(connection_str),
+                    Err(Error::Conversion(_))
+                ),
+                "{connection_str}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_with_connection_string() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        let port = plc.builder().port;
+
+        let mut client = S7Client::connect_str(&format!("s7://127.0.0.1:{port}?type=1200"))
+            .await
+            .unwrap();
+
+        assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+    }
+
+    #[tokio::test]
+    async fn connect_is_retried_with_backoff() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let builder = S7ClientBuilder::new(Ipv4Addr::LOCALHOST, S7Types::S71200)
+            .port(port)
+            .connect_retries(2, Duration::from_millis(10))
+            .on_event(move |event| recorded.lock().unwrap().push(event.clone()));
+
+        assert!(builder.clone().connect().await.is_err());
+        let delays = events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| match event {
+                ConnectionEvent::ConnectRetry { attempt, delay, .. } => (*attempt, *delay),
+                event => panic!("unexpected event {event:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            delays,
+            [
+                (1, Duration::from_millis(10)),
+                (2, Duration::from_millis(20))
+            ]
+        );
+
+        // the PLC becomes available while retrying
+        let plc = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            MockPlc::start_on(port).await
+        });
+        let mut client = builder
+            .connect_retries(6, Duration::from_millis(10))
+            .connect()
+            .await
+            .unwrap();
+        let plc = plc.await.unwrap();
+        plc.set_db(1, &[0x2A]);
+        assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+    }
+
+    #[tokio::test]
+    async fn negotiated_max_amq_calle_is_exposed() {
+        let plc = MockPlc::start().await;
+        plc.state().max_amq_calle = 3;
+
+        let client = plc.client().await;
+
+        assert_eq!(client.max_amq_calle(), 3);
+    }
+
+    #[tokio::test]
+    async fn reduced_max_amq_caller_fires_event() {
+        let plc = MockPlc::start().await;
+        plc.state().max_amq_caller = 2;
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+
+        let client = plc
+            .builder()
+            .on_event(move |event| recorded.lock().unwrap().push(event.clone()))
+            .connect()
+            .await
+            .unwrap();
+
+        assert_eq!(client.max_amq_caller(), 2);
+        assert_eq!(
+            *events.lock().unwrap(),
+            [ConnectionEvent::MaxAmqCallerReduced {
+                requested: 0x0100,
+                negotiated: 2,
+            }]
+        );
+
+        // no event if the PLC accepts the requested value
+        plc.state().max_amq_caller = 0x0100;
+        plc.builder()
+            .on_event(|event| panic!("unexpected event {event:?}"))
+            .connect()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn rack_slot_overrides_default_tsap() {
+        let plc = MockPlc::start().await;
+
+        // S7-1200 defaults to rack 0 / slot 0
+        plc.client().await;
+        assert_eq!(plc.destination_tsap(), Some([0x03, 0x00]));
+
+        plc.builder().rack_slot(1, 3).connect().await.unwrap();
+        assert_eq!(plc.destination_tsap(), Some([0x03, 0x23]));
+
+        // S7-400 defaults to rack 0 / slot 2
+        S7ClientBuilder::new(Ipv4Addr::LOCALHOST, S7Types::S7400)
+            .port(plc.addr.port())
+            .connect()
+            .await
+            .unwrap();
+        assert_eq!(plc.destination_tsap(), Some([0x03, 0x02]));
+    }
+
+    #[tokio::test]
+    async fn data_item_error_mapper_is_per_configuration() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        plc.state().read_error_code = Some(0x42);
+
+        let mut client = plc
+            .builder()
+            .data_item_error_mapper(|code| (code == 0x42).then(|| "Gateway busy".to_string()))
+            .connect()
+            .await
+            .unwrap();
+        assert_eq!(
+            client.db_read(1, 0, 1).await,
+            Err(Error::DataItemError(S7DataItemResponseError::Custom {
+                code: 0x42,
+                description: "Gateway busy".to_string(),
+            }))
+        );
+
+        // other connections are not affected by the mapper
+        let mut other = plc.client().await;
+        assert_eq!(
+            other.db_read(1, 0, 1).await,
+            Err(Error::DataItemError(S7DataItemResponseError::Unknown))
+        );
+    }
+
+    #[tokio::test]
+    async fn host_name_is_resolved() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+
+        let mut client = S7ClientBuilder::from_host("localhost", S7Types::S71200)
+            .port(plc.addr.port())
+            .connect()
+            .await
+            .unwrap();
+
+        assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+    }
+
+    #[tokio::test]
+    async fn ipv6_address_is_formatted_with_brackets() {
+        let builder = S7ClientBuilder::new(Ipv6Addr::LOCALHOST, S7Types::S71500);
+        let addrs = builder.socket_addrs().await.unwrap();
+        assert_eq!(addrs.len(), 1);
+        assert_eq!(addrs[0].to_string(), "[::1]:102");
+
+        let addr = "[::1]:1102".parse().unwrap();
+        let builder = S7ClientBuilder::from_socket_addr(addr, S7Types::S71500);
+        assert_eq!(builder.socket_addrs().await, Ok(vec![addr]));
+    }
+}
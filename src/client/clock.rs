@@ -0,0 +1,345 @@
+use bytes::BytesMut;
+
+use super::create::S7Client;
+use super::pooled::S7Pool;
+use crate::errors::Error;
+use crate::s7_protocol::user_data::{
+    check_return_code, exchange_user_data, GROUP_TIME_FUNCTIONS, RETURN_CODE_FOLLOW_UP,
+    RETURN_CODE_SUCCESS, SUBFUNCTION_READ_CLOCK, SUBFUNCTION_SET_CLOCK,
+};
+use crate::time::{decode_dt, decode_dtl, encode_dt, to_bcd, S7DateTime};
+
+/// Decode the clock returned by the time functions
+///
+/// The data starts with a reserved byte and the BCD encoded century, followed by the date and time
+/// as `DATE_AND_TIME`. A clock that was never set reports all zeros.
+fn decode_clock(data: &[u8]) -> Result<S7DateTime, Error> {
+    if data.len() < 10 {
+        return Err(Error::TryFrom(
+            data.to_vec(),
+            "Invalid length for PLC clock".to_string(),
+        ));
+    }
+    let date_time = &data[2..10];
+    if date_time.iter().all(|byte| *byte == 0) {
+        return Err(Error::ClockNotSet);
+    }
+    decode_dt(date_time)
+}
+
+/// Decode the clock returned by the time functions, keeping the nanoseconds of an extended clock
+///
+/// Extended clocks follow the reserved byte and the century with the date and time as `DTL`
+/// instead of `DATE_AND_TIME`, all other clocks are decoded like [`decode_clock`].
+fn decode_clock_precise(data: &[u8]) -> Result<S7DateTime, Error> {
+    if data.len() < 14 {
+        return decode_clock(data);
+    }
+    let date_time = &data[2..14];
+    if date_time.iter().all(|byte| *byte == 0) {
+        return Err(Error::ClockNotSet);
+    }
+    decode_dtl(date_time)
+}
+
+/// Encode a date and time in the format of the clock of the time functions
+fn encode_clock(date_time: &S7DateTime) -> Result<[u8; 10], Error> {
+    let encoded = encode_dt(date_time)?;
+    let mut clock = [0; 10];
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        clock[1] = to_bcd((date_time.year / 100) as u8);
+    }
+    clock[2..].copy_from_slice(&encoded);
+    Ok(clock)
+}
+
+impl S7Client {
+    /// Read the raw clock data of the time functions, which is decoded by the caller
+    async fn read_clock_data(&mut self) -> Result<BytesMut, Error> {
+        self.validate_connection_info().await?;
+        let response = match exchange_user_data(
+            self,
+            GROUP_TIME_FUNCTIONS,
+            SUBFUNCTION_READ_CLOCK,
+            0,
+            RETURN_CODE_FOLLOW_UP,
+            &[],
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                return Err(error);
+            }
+        };
+        check_return_code(response.return_code, self.config.error_mapper())?;
+        Ok(response.data)
+    }
+
+    /// Read the date and time of the clock of the PLC
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let now = client.get_plc_clock()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::ClockNotSet` if the clock of the PLC was never set, `Error::FunctionNotSupported`
+    /// if the PLC does not expose its clock or `Error` if any errors occurred during reading.
+    pub async fn get_plc_clock(&mut self) -> Result<S7DateTime, Error> {
+        decode_clock(&self.read_clock_data().await?)
+    }
+
+    /// Read the date and time of the clock of the PLC with the full precision the CPU provides
+    ///
+    /// The clock is usually reported with milliseconds, like [`S7Client::get_plc_clock`]. CPUs
+    /// answering with an extended clock in the `DTL` format report the nanoseconds as well.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71500).await?;
+    /// let now = client.read_clock_precise()
+    ///     .await?;
+    /// println!("{}.{:09}", now.second, now.nanosecond);
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::ClockNotSet` if the clock of the PLC was never set, `Error::FunctionNotSupported`
+    /// if the PLC does not expose its clock or `Error` if any errors occurred during reading.
+    pub async fn read_clock_precise(&mut self) -> Result<S7DateTime, Error> {
+        decode_clock_precise(&self.read_clock_data().await?)
+    }
+
+    /// Set the clock of the PLC to the given date and time, e.g. to synchronize it with NTP
+    ///
+    /// The fraction of the second is truncated to milliseconds.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types, time::S7DateTime};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let now = S7DateTime::new(2024, 3, 5, 14, 30, 15, 0)?;
+    /// client.set_plc_clock(now)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the date and time is invalid or outside of 1990 to 2089,
+    /// `Error::ClockNotAccepted` if the PLC rejected the date and time or `Error` if any errors occurred during writing.
+    pub async fn set_plc_clock(&mut self, date_time: S7DateTime) -> Result<(), Error> {
+        let clock = encode_clock(&date_time)?;
+
+        self.validate_connection_info().await?;
+        match exchange_user_data(
+            self,
+            GROUP_TIME_FUNCTIONS,
+            SUBFUNCTION_SET_CLOCK,
+            0,
+            RETURN_CODE_SUCCESS,
+            &clock,
+        )
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(Error::S7ProtocolError(error)) => Err(Error::ClockNotAccepted(error)),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+impl S7Pool {
+    /// Read the date and time of the clock of the PLC
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let now = pool.get_plc_clock()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::ClockNotSet` if the clock of the PLC was never set, `Error::FunctionNotSupported`
+    /// if the PLC does not expose its clock or `Error` if any errors occurred during reading.
+    pub async fn get_plc_clock(&self) -> Result<S7DateTime, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.get_plc_clock().await
+    }
+
+    /// Read the date and time of the clock of the PLC with the full precision the CPU provides
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71500)?;
+    /// let now = pool.read_clock_precise()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::ClockNotSet` if the clock of the PLC was never set, `Error::FunctionNotSupported`
+    /// if the PLC does not expose its clock or `Error` if any errors occurred during reading.
+    pub async fn read_clock_precise(&self) -> Result<S7DateTime, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.read_clock_precise().await
+    }
+
+    /// Set the clock of the PLC to the given date and time
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types, time::S7DateTime};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let now = S7DateTime::new(2024, 3, 5, 14, 30, 15, 0)?;
+    /// pool.set_plc_clock(now)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the date and time is invalid or outside of 1990 to 2089,
+    /// `Error::ClockNotAccepted` if the PLC rejected the date and time or `Error` if any errors occurred during writing.
+    pub async fn set_plc_clock(&self, date_time: S7DateTime) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.set_plc_clock(date_time).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    #[tokio::test]
+    async fn read_plc_clock() {
+        let plc = MockPlc::start().await;
+        plc.state().clock = Some(vec![
+            0x00, 0x20, 0x24, 0x03, 0x05, 0x14, 0x30, 0x15, 0x25, 0x03,
+        ]);
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.get_plc_clock().await,
+            Ok(S7DateTime::new(2024, 3, 5, 14, 30, 15, 250_000_000).unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn precise_clock_keeps_nanoseconds() {
+        let plc = MockPlc::start().await;
+        let mut clock = vec![0x00, 0x20];
+        clock.extend([0x07, 0xE8, 0x03, 0x05, 0x03, 0x0E, 0x1E, 0x0F]);
+        clock.extend(123_456_789_u32.to_be_bytes());
+        plc.state().clock = Some(clock);
+        let mut client = plc.client().await;
+
+        let date_time = client.read_clock_precise().await.unwrap();
+        assert_eq!(
+            date_time,
+            S7DateTime::new(2024, 3, 5, 14, 30, 15, 123_456_789).unwrap()
+        );
+        assert_eq!(date_time.nanosecond, 123_456_789);
+
+        // CPUs without extended clock are read with milliseconds
+        plc.state().clock = Some(vec![
+            0x00, 0x20, 0x24, 0x03, 0x05, 0x14, 0x30, 0x15, 0x25, 0x03,
+        ]);
+        assert_eq!(
+            client.read_clock_precise().await,
+            Ok(S7DateTime::new(2024, 3, 5, 14, 30, 15, 250_000_000).unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn unset_plc_clock_is_reported() {
+        let plc = MockPlc::start().await;
+        plc.state().clock = Some(vec![0x00; 10]);
+        let mut client = plc.client().await;
+
+        assert_eq!(client.get_plc_clock().await, Err(Error::ClockNotSet));
+
+        plc.state().clock = None;
+        assert_eq!(
+            client.get_plc_clock().await,
+            Err(Error::FunctionNotSupported)
+        );
+    }
+
+    #[tokio::test]
+    async fn set_plc_clock_round_trips() {
+        let plc = MockPlc::start().await;
+        let mut client = plc.client().await;
+        let date_time = S7DateTime::new(2024, 3, 5, 14, 30, 15, 250_000_000).unwrap();
+
+        client.set_plc_clock(date_time).await.unwrap();
+
+        assert_eq!(
+            plc.state().clock,
+            Some(vec![
+                0x00, 0x20, 0x24, 0x03, 0x05, 0x14, 0x30, 0x15, 0x25, 0x03
+            ])
+        );
+        assert_eq!(client.get_plc_clock().await, Ok(date_time));
+    }
+
+    #[tokio::test]
+    async fn set_plc_clock_is_validated_and_rejections_reported() {
+        let plc = MockPlc::start().await;
+        let mut client = plc.client().await;
+
+        let mut invalid = S7DateTime::new(2024, 3, 5, 14, 30, 15, 0).unwrap();
+        invalid.month = 13;
+        assert!(matches!(
+            client.set_plc_clock(invalid).await,
+            Err(Error::Conversion(_))
+        ));
+        // nothing but the negotiation was sent
+        assert_eq!(plc.state().requests.len(), 1);
+
+        plc.state().set_clock_error_code = 0xD202;
+        let date_time = S7DateTime::new(2024, 3, 5, 14, 30, 15, 0).unwrap();
+        assert!(matches!(
+            client.set_plc_clock(date_time).await,
+            Err(Error::ClockNotAccepted(_))
+        ));
+    }
+}
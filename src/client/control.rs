@@ -0,0 +1,153 @@
+use super::create::S7Client;
+use crate::errors::Error;
+use crate::s7_protocol::control::{plc_control, PlcCommand};
+
+/// *Methods for changing the operating mode of the PLC device*
+///
+/// **Danger:** these methods start or stop the program execution of the CPU and therefore the
+/// machine it controls. They are only available for connections that were created with
+/// [`S7ClientBuilder::allow_plc_control`](crate::S7ClientBuilder::allow_plc_control).
+impl S7Client {
+    /// Put the CPU into RUN with a warm restart (hot start), keeping the retentive data
+    ///
+    /// **Danger:** the PLC program starts controlling the machine immediately.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// let mut client = S7Client::builder(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)
+    ///     .allow_plc_control()
+    ///     .connect()
+    ///     .await?;
+    /// client.plc_hot_start()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::PlcControlNotAllowed` if controlling the CPU was not enabled or `Error` if the CPU
+    /// rejected the command or any errors occurred during the exchange.
+    pub async fn plc_hot_start(&mut self) -> Result<(), Error> {
+        self.plc_control(PlcCommand::HotStart).await
+    }
+
+    /// Put the CPU into RUN with a cold restart, resetting all data to their initial values
+    ///
+    /// **Danger:** the PLC program starts controlling the machine immediately and retentive data is lost.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// let mut client = S7Client::builder(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)
+    ///     .allow_plc_control()
+    ///     .connect()
+    ///     .await?;
+    /// client.plc_cold_start()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::PlcControlNotAllowed` if controlling the CPU was not enabled or `Error` if the CPU
+    /// rejected the command or any errors occurred during the exchange.
+    pub async fn plc_cold_start(&mut self) -> Result<(), Error> {
+        self.plc_control(PlcCommand::ColdStart).await
+    }
+
+    /// Put the CPU into STOP
+    ///
+    /// **Danger:** the PLC program stops controlling the machine immediately.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// let mut client = S7Client::builder(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)
+    ///     .allow_plc_control()
+    ///     .connect()
+    ///     .await?;
+    /// client.plc_stop()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::PlcControlNotAllowed` if controlling the CPU was not enabled or `Error` if the CPU
+    /// rejected the command or any errors occurred during the exchange.
+    pub async fn plc_stop(&mut self) -> Result<(), Error> {
+        self.plc_control(PlcCommand::Stop).await
+    }
+
+    async fn plc_control(&mut self, command: PlcCommand) -> Result<(), Error> {
+        if !self.config.allow_plc_control {
+            return Err(Error::PlcControlNotAllowed);
+        }
+        self.validate_connection_info().await?;
+        match plc_control(self, command).await {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::Error;
+    use crate::mock::MockPlc;
+
+    #[tokio::test]
+    async fn plc_control_requires_guard() {
+        let plc = MockPlc::start().await;
+        let mut client = plc.client().await;
+
+        assert_eq!(client.plc_stop().await, Err(Error::PlcControlNotAllowed));
+        // nothing but the negotiation was sent
+        assert_eq!(plc.state().requests.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn plc_control_sends_pi_service() {
+        let plc = MockPlc::start().await;
+        let mut client = plc.builder().allow_plc_control().connect().await.unwrap();
+
+        client.plc_stop().await.unwrap();
+        client.plc_hot_start().await.unwrap();
+        client.plc_cold_start().await.unwrap();
+
+        let requests = plc.state().requests.clone();
+        assert_eq!(requests[1][10], 0x29);
+        assert_eq!(requests[2][10], 0x28);
+        assert!(requests[3].ends_with(b"\x00\x02C \x09P_PROGRAM"));
+    }
+
+    #[tokio::test]
+    async fn rejected_plc_control_is_an_error() {
+        let plc = MockPlc::start().await;
+        let mut client = plc.builder().allow_plc_control().connect().await.unwrap();
+        // service can not be executed in the current state of the CPU
+        plc.state().plc_control_error = 0x8404;
+
+        assert!(matches!(
+            client.plc_hot_start().await,
+            Err(Error::S7ProtocolError(_))
+        ));
+        assert!(matches!(
+            client.plc_stop().await,
+            Err(Error::S7ProtocolError(_))
+        ));
+        assert!(!client.is_closed());
+    }
+}
@@ -1,14 +1,32 @@
-use std::{net::Ipv4Addr, time::Duration};
-use tokio::{net::TcpStream, time::timeout};
+use bytes::BytesMut;
+use std::io::{Error as IOError, ErrorKind};
+use std::{collections::VecDeque, future::Future, net::IpAddr, time::Duration};
+use tokio::{
+    net::{TcpSocket, TcpStream},
+    time::{sleep, timeout},
+};
+use tokio_util::sync::CancellationToken;
 
+use super::builder::S7ClientBuilder;
+use super::events::ConnectionEvent;
+use super::operation::OpInfo;
+use super::protection::ProtectionLevel;
 use crate::connection::{
     iso::S7Types,
-    tcp::{connect, disconnect},
+    tcp::{
+        build_telegram, connect, disconnect, disconnect_now, exchange_buffer, receive_response,
+        recv_buffer, send_request,
+    },
 };
 use crate::errors::Error;
+use crate::s7_protocol::{
+    dry_run::synthetic_response,
+    negotiate::REQUESTED_MAX_AMQ,
+    segments::header::{S7ProtocolHeader, NEGOTIATION_PDU_REFERENCE},
+};
 
 // Default TCP Port
-pub(crate) const TCP_PORT: u32 = 102;
+pub(crate) const TCP_PORT: u16 = 102;
 // Default TCP timeout
 pub(crate) const CONNECTION_TIMEOUT: Duration = Duration::from_secs(3);
 
@@ -16,17 +34,29 @@ pub(crate) const CONNECTION_TIMEOUT: Duration = Duration::from_secs(3);
 #[derive(Debug)]
 pub struct S7Client {
     pub(crate) connection: TcpStream,
-    s7_type: S7Types,
+    pub(crate) config: S7ClientBuilder,
     pub(crate) pdu_length: u16,
     pub(crate) pdu_number: u16,
     // The Max AMQ parameters define how many unacknowledged requests a PLC (Callee) is able to accept from a client (Caller).
     pub(crate) max_amq_caller: u16,
     pub(crate) max_amq_calle: u16,
     closed: bool,
+    // Number of requests successfully exchanged with the PLC
+    requests_issued: u64,
+    // Request currently exchanged with the PLC
+    current_operation: Option<OpInfo>,
+    // Protection level of the CPU, cached for the current connection
+    pub(crate) protection_level: Option<ProtectionLevel>,
+    // Requests recorded instead of being sent, `None` if dry-run mode is disabled
+    dry_run_requests: Option<Vec<Vec<u8>>>,
+    // Exchange timeout replacing the configured one while a `TimeoutGuard` is alive
+    pub(crate) timeout_override: Option<Duration>,
 }
 
 impl S7Client {
     /// Create new standalone connection to an S7 PLC
+    ///
+    /// `ip` can be an IPv4 or IPv6 address.
     ///```rust
     /// # tokio_test::block_on(async {
     /// use std::net::Ipv4Addr;
@@ -41,32 +71,131 @@ impl S7Client {
     /// # Errors
     ///
     /// Will return `Error` if no connection could be established to the PLC.
-    pub async fn new(ip: Ipv4Addr, s7_type: S7Types) -> Result<Self, Error> {
-        let tcp_client = match timeout(
-            CONNECTION_TIMEOUT,
-            TcpStream::connect(format!("{ip}:{TCP_PORT}")),
-        )
-        .await
-        {
-            Ok(connection) => connection,
-            Err(_err) => {
-                return Err(Error::Connection(format!(
-                    "Error on connecting to '{}:{}': Timed out after {} seconds",
-                    ip,
-                    TCP_PORT,
-                    CONNECTION_TIMEOUT.as_secs()
-                )))
+    pub async fn new(ip: impl Into<IpAddr>, s7_type: S7Types) -> Result<Self, Error> {
+        S7ClientBuilder::new(ip, s7_type).connect().await
+    }
+
+    /// Create new standalone connection to an S7 PLC addressed by a host name or IP literal
+    ///
+    /// The name is resolved via DNS and every resolved address is tried until a connection succeeds.
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use s7client::{S7Client, S7Types};
+    ///
+    /// // create single s7 client for a PLC with a DNS name
+    /// let mut client = S7Client::from_host("plc-line-1.plant.local", S7Types::S71200)
+    ///          .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if the name could not be resolved or no connection could be established to the PLC.
+    pub async fn from_host(host: &str, s7_type: S7Types) -> Result<Self, Error> {
+        S7ClientBuilder::from_host(host, s7_type).connect().await
+    }
+
+    /// Create new standalone connection to an S7 PLC with custom timeouts
+    ///
+    /// `connect_timeout` limits the time for establishing the TCP connection (default 3 seconds),
+    /// `exchange_timeout` limits sending a request and receiving its response (default 4 seconds).
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use std::net::Ipv4Addr;
+    /// use std::time::Duration;
+    /// use s7client::{S7Client, S7Types};
+    ///
+    /// // create single s7 client for a slow PLC
+    /// let mut client = S7Client::with_timeouts(
+    ///     Ipv4Addr::new(192, 168, 10, 72),
+    ///     S7Types::S71200,
+    ///     Duration::from_secs(10),
+    ///     Duration::from_secs(15),
+    /// )
+    /// .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if no connection could be established to the PLC.
+    pub async fn with_timeouts(
+        ip: impl Into<IpAddr>,
+        s7_type: S7Types,
+        connect_timeout: Duration,
+        exchange_timeout: Duration,
+    ) -> Result<Self, Error> {
+        S7ClientBuilder::new(ip, s7_type)
+            .connect_timeout(connect_timeout)
+            .exchange_timeout(exchange_timeout)
+            .connect()
+            .await
+    }
+
+    /// Create new standalone connection to an S7 PLC configured by a URL-style connection string
+    ///
+    /// See [`S7ClientBuilder::from_connection_str`] for the format of the string.
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use s7client::S7Client;
+    ///
+    /// // create single s7 client for a CPU in slot 1
+    /// let mut client = S7Client::connect_str("s7://192.168.10.72:102?type=1200&rack=0&slot=1")
+    ///          .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the connection string is malformed or `Error` if no connection could be established to the PLC.
+    pub async fn connect_str(connection_str: &str) -> Result<Self, Error> {
+        S7ClientBuilder::from_connection_str(connection_str)?
+            .connect()
+            .await
+    }
+
+    /// Start configuring a standalone connection with an [`S7ClientBuilder`]
+    pub fn builder(ip: impl Into<IpAddr>, s7_type: S7Types) -> S7ClientBuilder {
+        S7ClientBuilder::new(ip, s7_type)
+    }
+
+    pub(crate) async fn connect_with(config: S7ClientBuilder) -> Result<Self, Error> {
+        let mut delay = config.connect_backoff;
+        let mut attempt = 0;
+        loop {
+            match Self::connect_once(config.clone()).await {
+                Err(error) if attempt < config.connect_retries => {
+                    attempt += 1;
+                    config.emit(&ConnectionEvent::ConnectRetry {
+                        attempt,
+                        delay,
+                        error: error.to_string(),
+                    });
+                    sleep(delay).await;
+                    delay = delay.saturating_mul(2);
+                }
+                result => return result,
             }
-        }?;
+        }
+    }
+
+    async fn connect_once(config: S7ClientBuilder) -> Result<Self, Error> {
+        let tcp_client = open_stream(&config).await?;
 
         let mut client = Self {
             connection: tcp_client,
-            s7_type,
+            config,
             pdu_length: 0,
-            pdu_number: 0,
+            // the first request after the negotiation uses the next reference
+            pdu_number: NEGOTIATION_PDU_REFERENCE,
             max_amq_caller: 0,
             max_amq_calle: 0,
             closed: true,
+            requests_issued: 0,
+            current_operation: None,
+            protection_level: None,
+            dry_run_requests: None,
+            timeout_override: None,
         };
         client.connect().await?;
 
@@ -80,17 +209,66 @@ impl S7Client {
     ///
     /// Will return `Error` if no connection could be established to the PLC.
     pub async fn connect(&mut self) -> Result<(), Error> {
-        let connection_parameters = connect(&mut self.connection, self.s7_type).await?;
+        let (rack, slot) = self.config.rack_slot_or_default();
+        let connection_parameters = connect(
+            &mut self.connection,
+            rack,
+            slot,
+            self.config.requested_pdu_size,
+            self.config.exchange_timeout,
+        )
+        .await?;
 
         self.pdu_length = connection_parameters.pdu_length;
         self.max_amq_caller = connection_parameters.max_amq_caller;
         self.max_amq_calle = connection_parameters.max_amq_calle;
+        self.protection_level = None;
+        if self.max_amq_caller < REQUESTED_MAX_AMQ {
+            self.config.emit(&ConnectionEvent::MaxAmqCallerReduced {
+                requested: REQUESTED_MAX_AMQ,
+                negotiated: self.max_amq_caller,
+            });
+        }
 
         self.closed = false;
 
         Ok(())
     }
 
+    /// Reconnect automatically once a request failed because of a connection error (default off)
+    ///
+    /// Without this a client stays closed after a connection error and every following request fails.
+    /// With auto-reconnect enabled a new connection to the PLC is established and the failed request
+    /// is retried once before the error is returned. A closed client also tries to reconnect before
+    /// sending the next request. Note that a retried write might already have been executed by the PLC.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)
+    ///     .await?
+    ///     .with_auto_reconnect(true);
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    #[must_use]
+    pub fn with_auto_reconnect(mut self, auto_reconnect: bool) -> Self {
+        self.config.auto_reconnect = auto_reconnect;
+        self
+    }
+
+    /// Replace the connection to the PLC by a new one
+    ///
+    /// The old connection is released with a disconnect request, sent the same way as on dropping the client.
+    pub(crate) async fn reconnect(&mut self) -> Result<(), Error> {
+        disconnect_now(&self.connection);
+        self.closed = true;
+        self.connection = open_stream(&self.config).await?;
+        self.connect().await
+    }
+
     /// Gracefully disconnect from the PLC
     /// # Errors
     ///
@@ -101,7 +279,298 @@ impl S7Client {
         Ok(())
     }
 
-    pub(crate) fn validate_connection_info(&mut self) -> Result<(), Error> {
+    /// Maximum PDU length in bytes, as negotiated during connection
+    ///
+    /// Requests and responses exceeding this length are split or rejected with
+    /// `Error::TooManyItemsInOneRequest`.
+    #[must_use]
+    pub fn pdu_length(&self) -> u16 {
+        self.pdu_length
+    }
+
+    /// Maximum numbers of unacknowledged requests as `(caller, calle)`, as negotiated during connection
+    ///
+    /// See [`S7Client::max_amq_caller`] and [`S7Client::max_amq_calle`].
+    #[must_use]
+    pub fn max_amq(&self) -> (u16, u16) {
+        (self.max_amq_caller, self.max_amq_calle)
+    }
+
+    /// Maximum number of unacknowledged requests the PLC accepts in parallel, as negotiated during connection
+    #[must_use]
+    pub fn max_amq_calle(&self) -> u16 {
+        self.max_amq_calle
+    }
+
+    /// Maximum number of unacknowledged requests this client may send in parallel, as negotiated during connection
+    ///
+    /// Reads and writes that are split into several requests keep up to this number of requests
+    /// in flight. A value below the requested 256 fires [`ConnectionEvent::MaxAmqCallerReduced`].
+    #[must_use]
+    pub fn max_amq_caller(&self) -> u16 {
+        self.max_amq_caller
+    }
+
+    /// Enable or disable the dry-run mode
+    ///
+    /// In dry-run mode the requests are built as usual but recorded instead of being sent to the PLC,
+    /// see [`S7Client::take_dry_run_requests`]. This allows to verify address maps against the
+    /// encoding of this crate without touching a live PLC.
+    ///
+    /// The results of all requests in dry-run mode are synthetic: writes always succeed, reads return
+    /// zeroed data of the requested length and functions reading lists of the PLC (e.g.
+    /// [`S7Client::read_ip_config`]) find no entries.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// client.set_dry_run(true);
+    /// client.db_write(100, 0, &[0x01, 0x02]).await?;
+    /// let requests = client.take_dry_run_requests();
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        if dry_run {
+            self.dry_run_requests.get_or_insert_with(Vec::new);
+        } else {
+            self.dry_run_requests = None;
+        }
+    }
+
+    /// Whether the client is in dry-run mode
+    #[must_use]
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run_requests.is_some()
+    }
+
+    /// Take the telegrams (including TPKT and COTP headers) recorded in dry-run mode since the last call
+    pub fn take_dry_run_requests(&mut self) -> Vec<Vec<u8>> {
+        self.dry_run_requests
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Request that is currently exchanged with the PLC
+    ///
+    /// The operation is set while a read or write job waits for the PLC and cleared after the
+    /// response was received. If the future of a request is dropped, e.g. by a surrounding
+    /// timeout, the operation stays set until the next request and tells which request got stuck.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let read = tokio::time::timeout(Duration::from_secs(1), client.db_read(100, 0, 4)).await;
+    /// if read.is_err() {
+    ///     println!("PLC does not answer {:?}", client.current_operation());
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    #[must_use]
+    pub fn current_operation(&self) -> Option<OpInfo> {
+        self.current_operation
+    }
+
+    /// Exchange a read or write job with the PLC, reporting it as current operation meanwhile
+    pub(crate) async fn exchange_operation(
+        &mut self,
+        data: BytesMut,
+        operation: Option<OpInfo>,
+    ) -> Result<BytesMut, Error> {
+        self.current_operation = operation;
+        let response = self.exchange(data).await;
+        self.current_operation = None;
+        response
+    }
+
+    /// Exchange several read or write jobs with the PLC, keeping up to `max_amq_caller` of them unacknowledged
+    ///
+    /// The responses are matched to the requests by their PDU reference and returned in the order
+    /// of the requests. Meanwhile the oldest request without response is reported as current operation.
+    /// Without strict checking of the PDU reference the requests are exchanged one after another.
+    pub(crate) async fn exchange_pipelined(
+        &mut self,
+        requests: Vec<(BytesMut, Option<OpInfo>)>,
+    ) -> Result<Vec<BytesMut>, Error> {
+        if requests.len() < 2
+            || self.max_amq_caller < 2
+            || self.dry_run_requests.is_some()
+            || !self.config.strict_pdu_reference
+        {
+            let mut responses = Vec::with_capacity(requests.len());
+            for (data, operation) in requests {
+                responses.push(self.exchange_operation(data, operation).await?);
+            }
+            return Ok(responses);
+        }
+
+        let mut responses = vec![None; requests.len()];
+        let cancellation_token = self.config.cancellation_token.clone();
+        let result = until_cancelled(
+            cancellation_token.as_ref(),
+            self.exchange_in_flight(&requests, &mut responses),
+        )
+        .await;
+        let result = match result {
+            Err(error) if error.is_connection_error() && self.config.auto_reconnect => {
+                // repeat the requests without response one after another on a new connection
+                self.current_operation = None;
+                self.reconnect().await?;
+                for ((data, operation), response) in requests.into_iter().zip(&mut responses) {
+                    if response.is_none() {
+                        *response = Some(self.exchange_operation(data, operation).await?);
+                    }
+                }
+                Ok(())
+            }
+            Err(Error::Cancelled) => {
+                self.set_closed();
+                Err(Error::Cancelled)
+            }
+            result => result,
+        };
+        self.current_operation = None;
+        result?;
+
+        Ok(responses.into_iter().flatten().collect())
+    }
+
+    async fn exchange_in_flight(
+        &mut self,
+        requests: &[(BytesMut, Option<OpInfo>)],
+        responses: &mut [Option<BytesMut>],
+    ) -> Result<(), Error> {
+        let exchange_timeout = self.exchange_timeout();
+        let mut in_flight = VecDeque::with_capacity(usize::from(self.max_amq_caller));
+        let mut next = 0;
+        while next < requests.len() || !in_flight.is_empty() {
+            while next < requests.len() && in_flight.len() < usize::from(self.max_amq_caller) {
+                let data = &requests[next].0;
+                let reference = S7ProtocolHeader::pdu_reference_of(data)?;
+                send_request(&mut self.connection, data.clone(), exchange_timeout).await?;
+                in_flight.push_back((reference, next));
+                next += 1;
+            }
+
+            self.current_operation = in_flight.front().and_then(|(_, index)| requests[*index].1);
+            let response = receive_response(&mut self.connection, exchange_timeout).await?;
+            let reference = S7ProtocolHeader::pdu_reference_of(&response)?;
+            let position = in_flight
+                .iter()
+                .position(|(request_reference, _)| *request_reference == reference)
+                .ok_or(Error::ResponseDoesNotBelongToCurrentPDU)?;
+            if let Some((_, index)) = in_flight.remove(position) {
+                responses[index] = Some(response);
+            }
+            self.requests_issued += 1;
+        }
+        Ok(())
+    }
+
+    /// Send S7 data to the PLC and receive its response
+    pub(crate) async fn exchange(&mut self, data: BytesMut) -> Result<BytesMut, Error> {
+        if let Some(requests) = &mut self.dry_run_requests {
+            let response = synthetic_response(&data);
+            requests.push(build_telegram(data)?.to_vec());
+            return Ok(response);
+        }
+        let exchange_timeout = self.exchange_timeout();
+        let cancellation_token = self.config.cancellation_token.clone();
+        let response = until_cancelled(cancellation_token.as_ref(), async {
+            if self.config.auto_reconnect {
+                match exchange_buffer(&mut self.connection, data.clone(), exchange_timeout).await {
+                    Err(error) if error.is_connection_error() => {
+                        self.reconnect().await?;
+                        exchange_buffer(&mut self.connection, data, exchange_timeout).await
+                    }
+                    result => result,
+                }
+            } else {
+                exchange_buffer(&mut self.connection, data, exchange_timeout).await
+            }
+        })
+        .await
+        .inspect_err(|error| self.close_if_cancelled(error))?;
+
+        self.requests_issued += 1;
+        Ok(response)
+    }
+
+    /// Timeout for exchanging a single request, honouring a [`TimeoutGuard`](crate::TimeoutGuard)
+    pub(crate) fn exchange_timeout(&self) -> Duration {
+        self.timeout_override
+            .unwrap_or(self.config.exchange_timeout)
+    }
+
+    /// Send S7 data to the PLC without waiting for its response
+    pub(crate) async fn send(&mut self, data: BytesMut) -> Result<(), Error> {
+        let exchange_timeout = self.exchange_timeout();
+        let cancellation_token = self.config.cancellation_token.clone();
+        until_cancelled(
+            cancellation_token.as_ref(),
+            send_request(&mut self.connection, data, exchange_timeout),
+        )
+        .await
+        .inspect_err(|error| self.close_if_cancelled(error))
+    }
+
+    /// Receive S7 data from the PLC, waiting at most the exchange timeout
+    pub(crate) async fn receive(&mut self) -> Result<BytesMut, Error> {
+        let exchange_timeout = self.exchange_timeout();
+        let cancellation_token = self.config.cancellation_token.clone();
+        until_cancelled(
+            cancellation_token.as_ref(),
+            receive_response(&mut self.connection, exchange_timeout),
+        )
+        .await
+        .inspect_err(|error| self.close_if_cancelled(error))
+    }
+
+    /// Receive S7 data the PLC pushes on its own (e.g. alarm indications) without a timeout
+    pub(crate) async fn receive_pushed(&mut self) -> Result<BytesMut, Error> {
+        let cancellation_token = self.config.cancellation_token.clone();
+        until_cancelled(
+            cancellation_token.as_ref(),
+            recv_buffer(&mut self.connection),
+        )
+        .await
+        .inspect_err(|error| self.close_if_cancelled(error))
+    }
+
+    // A cancelled exchange may have left a partial telegram on the connection, so it must not be used again
+    fn close_if_cancelled(&mut self, error: &Error) {
+        if *error == Error::Cancelled {
+            self.set_closed();
+        }
+    }
+
+    /// Number of requests successfully exchanged with the PLC since connecting or the last [`S7Client::reset_counters`]
+    ///
+    /// Every request counts once, e.g. a read that had to be split into several requests counts
+    /// multiple times. The negotiation during connect and requests in dry-run mode are not counted.
+    #[must_use]
+    pub fn requests_issued(&self) -> u64 {
+        self.requests_issued
+    }
+
+    /// Reset the statistics counters of the client to zero
+    pub fn reset_counters(&mut self) {
+        self.requests_issued = 0;
+    }
+
+    pub(crate) async fn validate_connection_info(&mut self) -> Result<(), Error> {
+        if self.closed && self.config.auto_reconnect {
+            self.reconnect().await?;
+        }
         if self.closed {
             return Err(Error::Connection("Connection is closed".to_string()));
         }
@@ -117,9 +586,294 @@ impl S7Client {
     }
 }
 
-// impl Drop for S7Client {
-//     fn drop(&mut self) {
-//         // TODO implement drop for async
-//         let _ = self.disconnect();
-//     }
-// }
+/// Run `exchange` until it completes or `cancellation_token` is cancelled
+async fn until_cancelled<T>(
+    cancellation_token: Option<&CancellationToken>,
+    exchange: impl Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    match cancellation_token {
+        Some(token) => token
+            .run_until_cancelled(exchange)
+            .await
+            .unwrap_or(Err(Error::Cancelled)),
+        None => exchange.await,
+    }
+}
+
+/// Open a TCP connection to the first reachable address of the PLC
+async fn open_stream(config: &S7ClientBuilder) -> Result<TcpStream, Error> {
+    let connect_timeout = config.connect_timeout;
+    let mut last_error = None;
+    for addr in config.socket_addrs().await? {
+        let connecting = async {
+            match config.local_addr {
+                Some(local_addr) => {
+                    if local_addr.is_ipv4() != addr.is_ipv4() {
+                        return Err(IOError::new(
+                            ErrorKind::AddrNotAvailable,
+                            format!("Local address {local_addr} can not reach '{addr}'"),
+                        ));
+                    }
+                    let socket = if addr.is_ipv4() {
+                        TcpSocket::new_v4()?
+                    } else {
+                        TcpSocket::new_v6()?
+                    };
+                    socket.bind(local_addr)?;
+                    socket.connect(addr).await
+                }
+                None => TcpStream::connect(addr).await,
+            }
+        };
+        match timeout(connect_timeout, connecting).await {
+            Ok(Ok(connection)) => {
+                connection.set_nodelay(config.tcp_nodelay)?;
+                return Ok(connection);
+            }
+            Ok(Err(error)) => last_error = Some(error.into()),
+            Err(_err) => {
+                last_error = Some(Error::Connection(format!(
+                    "Error on connecting to '{addr}': Timed out after {connect_timeout:?}"
+                )));
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| Error::Connection("No address to connect to".to_string())))
+}
+
+/// Dropping an open client sends a disconnect request to the PLC without waiting for its response
+///
+/// Many CPUs only accept a few simultaneous connections, so leaked connections must not wait for a
+/// TCP timeout on the PLC side. As `drop` can not be async the request is only written if the socket
+/// is immediately writable. Call [`S7Client::disconnect`] for a graceful disconnect that waits for
+/// the confirmation of the PLC. Pooled connections are disconnected the same way when the pool drops them.
+impl Drop for S7Client {
+    fn drop(&mut self) {
+        if !self.closed {
+            disconnect_now(&self.connection);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+    use std::net::Ipv4Addr;
+    use std::time::{Duration, Instant};
+
+    use tokio_util::sync::CancellationToken;
+
+    use crate::errors::Error;
+    use crate::mock::MockPlc;
+    use crate::{Area, OpInfo, OpKind};
+
+    #[tokio::test]
+    async fn pdu_number_wraps_without_reusing_negotiation() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        let mut client = plc.client().await;
+        client.pdu_number = u16::MAX - 1;
+
+        for _ in 0..3 {
+            assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+        }
+
+        let references = plc
+            .state()
+            .requests
+            .iter()
+            .map(|request| u16::from_le_bytes([request[4], request[5]]))
+            .collect::<Vec<_>>();
+        assert_eq!(references, [0, u16::MAX, 1, 2]);
+    }
+
+    async fn wait_for_disconnect(plc: &MockPlc) -> usize {
+        for _ in 0..50 {
+            if plc.state().disconnect_requests > 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        plc.state().disconnect_requests
+    }
+
+    #[tokio::test]
+    async fn cancellation_aborts_running_read() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        plc.state().read_delays.insert(1, Duration::from_secs(2));
+        let shutdown = CancellationToken::new();
+        let mut client = plc
+            .builder()
+            .cancellation_token(shutdown.clone())
+            .connect()
+            .await
+            .unwrap();
+
+        let started = Instant::now();
+        let cancel = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            shutdown.cancel();
+        });
+        assert_eq!(client.db_read(1, 0, 1).await, Err(Error::Cancelled));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(client.is_closed());
+        cancel.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn connection_is_bound_to_local_address() {
+        let plc = MockPlc::start().await;
+
+        let client = plc
+            .builder()
+            .local_addr("127.0.0.1:0".parse().unwrap())
+            .connect()
+            .await
+            .unwrap();
+        assert_eq!(
+            client.connection.local_addr().unwrap().ip(),
+            Ipv4Addr::LOCALHOST
+        );
+
+        let result = plc
+            .builder()
+            .local_addr("[::1]:0".parse().unwrap())
+            .connect()
+            .await;
+        assert!(matches!(result, Err(Error::IO(e)) if e.kind() == ErrorKind::AddrNotAvailable));
+    }
+
+    #[tokio::test]
+    async fn nagle_algorithm_is_disabled_by_default() {
+        let plc = MockPlc::start().await;
+
+        let client = plc.client().await;
+        assert!(client.connection.nodelay().unwrap());
+
+        let client = plc.builder().tcp_nodelay(false).connect().await.unwrap();
+        assert!(!client.connection.nodelay().unwrap());
+    }
+
+    #[tokio::test]
+    async fn drop_sends_disconnect() {
+        let plc = MockPlc::start().await;
+        let client = plc.client().await;
+
+        drop(client);
+
+        assert_eq!(wait_for_disconnect(&plc).await, 1);
+    }
+
+    #[tokio::test]
+    async fn drop_after_disconnect_sends_nothing() {
+        let plc = MockPlc::start().await;
+        let mut client = plc.client().await;
+
+        client.disconnect().await.unwrap();
+        drop(client);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(plc.state().disconnect_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn dropped_pool_disconnects_connections() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0; 4]);
+        let pool = plc.pool(1);
+        pool.db_read(1, 0, 4).await.unwrap();
+
+        drop(pool);
+
+        assert_eq!(wait_for_disconnect(&plc).await, 1);
+    }
+
+    #[tokio::test]
+    async fn auto_reconnect_retries_after_connection_error() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        let mut client = plc.client().await.with_auto_reconnect(true);
+        plc.state().close_after_response = true;
+        assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+        plc.state().close_after_response = false;
+
+        assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+        assert_eq!(plc.state().connection_requests.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn reconnect_disconnects_old_connection() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        let mut client = plc.client().await;
+
+        client.reconnect().await.unwrap();
+
+        assert_eq!(wait_for_disconnect(&plc).await, 1);
+        assert_eq!(plc.state().connection_requests.len(), 2);
+        assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+    }
+
+    #[tokio::test]
+    async fn closed_client_stays_closed_without_auto_reconnect() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        let mut client = plc.client().await;
+        plc.state().close_after_response = true;
+        assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+        plc.state().close_after_response = false;
+
+        assert!(client
+            .db_read(1, 0, 1)
+            .await
+            .unwrap_err()
+            .is_connection_error());
+        assert_eq!(
+            client.db_read(1, 0, 1).await,
+            Err(Error::Connection("Connection is closed".to_string()))
+        );
+        assert_eq!(plc.state().connection_requests.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn requests_are_counted() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0; 4]);
+        let mut client = plc.client().await;
+        assert_eq!(client.requests_issued(), 0);
+
+        for _ in 0..5 {
+            client.db_read(1, 0, 4).await.unwrap();
+        }
+        assert_eq!(client.requests_issued(), 5);
+
+        client.reset_counters();
+        client.db_write(1, 0, &[1]).await.unwrap();
+        assert_eq!(client.requests_issued(), 1);
+    }
+
+    #[tokio::test]
+    async fn stuck_request_is_reported_as_current_operation() {
+        let plc = MockPlc::start().await;
+        plc.state().pdu_length = 240;
+        plc.set_db(3, &[0x00; 400]);
+        let mut client = plc.client().await;
+        assert_eq!(client.current_operation(), None);
+
+        // the PLC answers the first request of the split read only
+        plc.state().stall_after_jobs = Some(1);
+        let read = tokio::time::timeout(Duration::from_millis(200), client.db_read(3, 0, 400));
+        assert!(read.await.is_err());
+        assert_eq!(
+            client.current_operation(),
+            Some(OpInfo {
+                kind: OpKind::Read,
+                area: Area::DataBlock,
+                db_number: 3,
+                start: 222,
+                chunk: 1,
+            })
+        );
+    }
+}
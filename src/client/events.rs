@@ -0,0 +1,50 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Notable events in the lifecycle of a connection to the PLC
+///
+/// Register a handler with [`S7ClientBuilder::on_event`](crate::S7ClientBuilder::on_event) to
+/// log them or to expose them in monitoring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConnectionEvent {
+    /// The PLC accepts fewer unacknowledged requests from this client than requested during negotiation
+    ///
+    /// This limits the number of requests that can be processed in parallel on one connection.
+    MaxAmqCallerReduced {
+        /// Number of unacknowledged requests proposed to the PLC
+        requested: u16,
+        /// Number of unacknowledged requests the PLC accepted
+        negotiated: u16,
+    },
+    /// Connecting to the PLC failed and is retried after a delay, see [`S7ClientBuilder::connect_retries`](crate::S7ClientBuilder::connect_retries)
+    ConnectRetry {
+        /// Number of the failed attempt, starting at 1
+        attempt: u32,
+        /// Time until the next attempt
+        delay: Duration,
+        /// Error of the failed attempt
+        error: String,
+    },
+}
+
+/// Callback for connection events, shared between all connections of a configuration
+#[derive(Clone)]
+pub(crate) struct EventHandler(Arc<dyn Fn(&ConnectionEvent) + Send + Sync>);
+
+impl EventHandler {
+    pub(crate) fn new(handler: impl Fn(&ConnectionEvent) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(handler))
+    }
+
+    pub(crate) fn emit(&self, event: &ConnectionEvent) {
+        (self.0)(event);
+    }
+}
+
+impl fmt::Debug for EventHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EventHandler")
+    }
+}
@@ -1,10 +1,34 @@
 use std::borrow::Cow;
 
-use crate::{errors::Error, s7_protocol::types::S7DataTypes};
+use crate::{
+    errors::Error,
+    s7_protocol::types::{Area, S7DataTypes},
+};
 
+pub(crate) mod alarms;
+pub(crate) mod benchmark;
+pub(crate) mod bits;
+#[cfg(feature = "blocking")]
+pub(crate) mod blocking;
+pub(crate) mod blocks;
+pub(crate) mod builder;
+pub(crate) mod clock;
+pub(crate) mod control;
 pub(crate) mod create;
+pub(crate) mod events;
+pub(crate) mod operation;
 pub(crate) mod pooled;
+pub(crate) mod priority;
+pub(crate) mod probe;
+pub(crate) mod protection;
 pub(crate) mod read;
+pub(crate) mod recipe;
+pub(crate) mod schema;
+pub(crate) mod snapshot;
+pub(crate) mod symbols;
+pub(crate) mod szl;
+pub(crate) mod text;
+pub(crate) mod timeout;
 pub(crate) mod triggers;
 pub(crate) mod write;
 
@@ -15,9 +39,36 @@ pub(crate) fn verify_max_bit(bit: u8) -> Result<(), Error> {
     Ok(())
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Selects whether inputs and outputs are accessed through the process image or directly at the peripheral modules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IoMode {
+    /// Process image of the inputs (`I`) and outputs (`Q`), updated by the PLC once per cycle (default)
+    #[default]
+    ProcessImage,
+    /// Live values of the peripheral inputs (`PE`) and outputs (`PA`), bypassing the process image
+    Peripheral,
+}
+
+impl IoMode {
+    pub(crate) fn input_area(self) -> Area {
+        match self {
+            Self::ProcessImage => Area::ProcessInput,
+            Self::Peripheral => Area::PeripheralIO,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
 #[must_use]
 /// Allows configuration of reading access to S7 PLC
+///
+/// With the `serde` feature the access is (de)serialized tagged by its type, e.g.
+/// `{"type":"bit","db_number":100,"byte":0,"bit":3}` or `{"type":"bytes","db_number":100,"start":4,"length":2}`.
 pub enum S7ReadAccess {
     /// Configure reading access for a chunk of bytes
     Bytes {
@@ -180,3 +231,124 @@ impl<'a> S7WriteAccess<'a> {
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
+#[must_use]
+/// Owned configuration of writing access to S7 PLC, e.g. for tag tables loaded from configuration files
+///
+/// Use [`S7WriteValue::as_access`] to pass it to the writing functions. With the `serde` feature
+/// the value is (de)serialized tagged by its type like [`S7ReadAccess`].
+pub enum S7WriteValue {
+    /// Configure writing access for a chunk of bytes
+    Bytes {
+        /// Number of data block to access
+        db_number: u16,
+        /// Number of byte to start writing
+        start: u32,
+        /// Data bytes to write to the PLC
+        data: Vec<u8>,
+    },
+    /// Configure writing access for a single bit
+    Bit {
+        /// Number of data block to access
+        db_number: u16,
+        /// Number of byte to write to
+        byte: u32,
+        /// Number of bit to write to
+        bit: u8,
+        /// Value to write
+        value: bool,
+    },
+}
+
+impl S7WriteValue {
+    /// Borrow the value as writing access
+    pub fn as_access(&self) -> S7WriteAccess<'_> {
+        match self {
+            Self::Bytes {
+                db_number,
+                start,
+                data,
+            } => S7WriteAccess::bytes(*db_number, *start, data),
+            Self::Bit {
+                db_number,
+                byte,
+                bit,
+                value,
+            } => S7WriteAccess::bit(*db_number, *byte, *bit, *value),
+        }
+    }
+}
+
+impl From<S7WriteAccess<'_>> for S7WriteValue {
+    fn from(access: S7WriteAccess<'_>) -> Self {
+        match access {
+            S7WriteAccess::Bytes {
+                db_number,
+                start,
+                data,
+            } => Self::Bytes {
+                db_number,
+                start,
+                data: data.to_vec(),
+            },
+            S7WriteAccess::Bit {
+                db_number,
+                byte,
+                bit,
+                value,
+            } => Self::Bit {
+                db_number,
+                byte,
+                bit,
+                value,
+            },
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::S7Types;
+
+    #[test]
+    fn read_access_is_tagged_by_type() {
+        let json = r#"{"type":"bit","db_number":100,"byte":0,"bit":3}"#;
+        let access: S7ReadAccess = serde_json::from_str(json).unwrap();
+
+        assert_eq!(access, S7ReadAccess::bit(100, 0, 3));
+        assert_eq!(serde_json::to_string(&access).unwrap(), json);
+
+        let access: S7ReadAccess =
+            serde_json::from_str(r#"{"type":"bytes","db_number":1,"start":4,"length":2}"#).unwrap();
+        assert_eq!(access, S7ReadAccess::bytes(1, 4, 2));
+    }
+
+    #[test]
+    fn write_value_round_trips() {
+        let values = vec![
+            S7WriteValue::from(S7WriteAccess::bytes(1, 4, &[0x01, 0x02])),
+            S7WriteValue::from(S7WriteAccess::bit(1, 6, 7, true)),
+        ];
+        let json = serde_json::to_string(&values).unwrap();
+
+        assert_eq!(
+            json,
+            r#"[{"type":"bytes","db_number":1,"start":4,"data":[1,2]},{"type":"bit","db_number":1,"byte":6,"bit":7,"value":true}]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<S7WriteValue>>(&json).unwrap(),
+            values
+        );
+        assert_eq!(
+            serde_json::from_str::<S7Types>(r#""S71200""#).unwrap(),
+            S7Types::S71200
+        );
+    }
+}
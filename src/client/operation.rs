@@ -0,0 +1,25 @@
+use crate::Area;
+
+/// Description of the request currently exchanged with the PLC, see [`S7Client::current_operation`](crate::S7Client::current_operation)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpInfo {
+    /// Kind of the request
+    pub kind: OpKind,
+    /// Memory area of the (first) accessed item
+    pub area: Area,
+    /// Number of the data block of the (first) accessed item, 0 for other areas
+    pub db_number: u16,
+    /// Start byte of the (first) accessed item
+    pub start: u32,
+    /// Index of the request if a large access is split into multiple requests, otherwise 0
+    pub chunk: usize,
+}
+
+/// Kind of a request exchanged with the PLC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpKind {
+    /// Read job
+    Read,
+    /// Write job
+    Write,
+}
@@ -1,14 +1,111 @@
+use std::future::{poll_fn, Future};
 use std::hash::Hash;
-use std::net::Ipv4Addr;
+use std::net::IpAddr;
+use std::ops::{Deref, DerefMut};
+use std::pin::pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::Poll;
+use std::time::Duration;
 
 use async_trait::async_trait;
+use tokio::time::{sleep, timeout_at, Instant};
+use tokio_util::sync::CancellationToken;
 
+use super::builder::S7ClientBuilder;
+use super::priority::{Permit, Priority, PriorityGate};
+use crate::connection::tcp::is_closed_by_peer;
+use crate::s7_protocol::{read_area::read_area_single, types::Area};
 use crate::S7ReadAccess;
-use crate::{errors::Error, S7Client, S7Types, TriggerCollection};
+use crate::{errors::Error, S7Client, S7Types, TriggerCollection, ValueTriggerCollection};
+
+/// Check of an idle pooled connection before it is handed out
+///
+/// Connections that fail the check are discarded and replaced by a new connection, so a connection
+/// that died while idle (e.g. PLC rebooted or cable replugged) does not cause a timeout of the next request.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum HealthCheck {
+    /// Connections are only discarded after an error occurred while using them
+    Disabled,
+    /// Check whether the PLC closed the connection, without sending any data (default)
+    ///
+    /// See [`S7Client::probe`]. This does not detect connections that died without the PLC closing them, e.g. because of a pulled cable.
+    #[default]
+    Socket,
+    /// Check the socket and read the given address of a data block before handing out a connection
+    ///
+    /// This detects every dead connection, but adds a request to the PLC every time a connection
+    /// is taken from the pool. Choose an address that exists and is safe to read.
+    Read(S7ReadAccess),
+}
+
+impl S7Client {
+    /// Check without sending any data whether the connection is still usable, closing it if not
+    ///
+    /// Detects connections that were closed or reset by the PLC as well as errors pending on the
+    /// socket, without waiting for the exchange timeout. A PLC that vanished without closing or
+    /// resetting the connection can only be detected by exchanging data, e.g. with
+    /// [`HealthCheck::Read`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// if client.probe().await.is_err() {
+    ///     client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if the connection was closed or the socket reported an error.
+    pub async fn probe(&mut self) -> Result<(), Error> {
+        self.validate_connection_info().await?;
+
+        let error = match self.connection.take_error() {
+            Ok(None) => match self.connection.try_write(&[]) {
+                Err(error) if error.kind() != std::io::ErrorKind::WouldBlock => Some(error),
+                _ => None,
+            },
+            Ok(Some(error)) | Err(error) => Some(error),
+        };
+        if let Some(error) = error {
+            self.set_closed();
+            return Err(Error::IO(error));
+        }
+
+        if is_closed_by_peer(&self.connection).await {
+            self.set_closed();
+            return Err(Error::Connection("Connection closed by PLC".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Check whether the connection is still usable, closing it if not
+    pub(crate) async fn check_health(&mut self, health_check: HealthCheck) -> Result<(), Error> {
+        if matches!(health_check, HealthCheck::Disabled) {
+            return Ok(());
+        }
+        self.probe().await?;
+
+        if let HealthCheck::Read(access) = health_check {
+            if let Err(error) = read_area_single(self, Area::DataBlock, access).await {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+}
 
 pub(crate) struct S7PoolManager {
-    s7_ip: Ipv4Addr,
-    s7_type: S7Types,
+    config: S7ClientBuilder,
+    // shared with the pool handles, so the check can be changed after the pool was created
+    health_check: Arc<Mutex<HealthCheck>>,
 }
 
 #[async_trait]
@@ -17,11 +114,15 @@ impl bb8::ManageConnection for S7PoolManager {
     type Error = Error;
 
     async fn connect(&self) -> Result<Self::Connection, Self::Error> {
-        Ok(S7Client::new(self.s7_ip, self.s7_type).await?)
+        Ok(self.config.clone().connect().await?)
     }
 
-    async fn is_valid(&self, _connection: &mut Self::Connection) -> Result<(), Self::Error> {
-        Ok(())
+    async fn is_valid(&self, connection: &mut Self::Connection) -> Result<(), Self::Error> {
+        let health_check = *self
+            .health_check
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        connection.check_health(health_check).await
     }
 
     fn has_broken(&self, connection: &mut Self::Connection) -> bool {
@@ -29,8 +130,79 @@ impl bb8::ManageConnection for S7PoolManager {
     }
 }
 
-// type S7PooledConnection = managed::Pool<S7PoolManager>;
-type S7PooledConnection = bb8::Pool<S7PoolManager>;
+// Default number of connections held by a pool
+const DEFAULT_MAX_POOL_SIZE: u32 = 3;
+
+// Time an operation waits for a free connection of the pool
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Interval of checking whether all connections were returned to the pool during shutdown
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Connections of a pool, handed out by priority until the pool is shut down
+#[derive(Clone)]
+pub(crate) struct S7PooledConnection {
+    pool: bb8::Pool<S7PoolManager>,
+    health_check: Arc<Mutex<HealthCheck>>,
+    shutdown: CancellationToken,
+    gate: PriorityGate,
+    priority: Priority,
+}
+
+/// Connection taken from the pool, returned to it once dropped
+pub(crate) struct PooledConnection<'a> {
+    // declared before the permit so the connection is back in the pool once the next waiter is admitted
+    connection: bb8::PooledConnection<'a, S7PoolManager>,
+    _permit: Permit,
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = S7Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.connection
+    }
+}
+
+impl DerefMut for PooledConnection<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.connection
+    }
+}
+
+impl S7PooledConnection {
+    /// Take a connection from the pool, failing with `Error::PoolShuttingDown` once the shutdown began
+    pub(crate) async fn get(&self) -> Result<PooledConnection<'_>, Error> {
+        if self.shutdown.is_cancelled() {
+            return Err(Error::PoolShuttingDown);
+        }
+
+        let deadline = Instant::now() + CONNECTION_TIMEOUT;
+        let mut shutdown = pin!(self.shutdown.cancelled());
+        let mut checkout = pin!(timeout_at(deadline, async {
+            let permit = self.gate.acquire(self.priority).await;
+            let connection = self.pool.get().await?;
+            Ok(PooledConnection {
+                connection,
+                _permit: permit,
+            })
+        }));
+        poll_fn(|cx| {
+            if shutdown.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Err(Error::PoolShuttingDown));
+            }
+            checkout
+                .as_mut()
+                .poll(cx)
+                .map(|result| result.unwrap_or_else(|_| Err(Error::from(bb8::RunError::TimedOut))))
+        })
+        .await
+    }
+
+    pub(crate) fn state(&self) -> bb8::State {
+        self.pool.state()
+    }
+}
 
 /// Pooled connection to a PLC device from the S7 family
 #[allow(missing_debug_implementations)]
@@ -50,18 +222,315 @@ impl S7Pool {
     /// # Errors
     ///
     /// Will return `Error` if the `Pool` could not be created.
-    pub fn new(ip: Ipv4Addr, s7_type: S7Types) -> Result<Self, Error> {
-        let mgr = S7PoolManager { s7_ip: ip, s7_type };
-        // let pool = S7PooledConnection::builder(mgr).max_size(3).build()?;
-        let pool = S7PooledConnection::builder()
-            .max_size(3)
+    pub fn new(ip: impl Into<IpAddr>, s7_type: S7Types) -> Result<Self, Error> {
+        Self::from_builder(S7ClientBuilder::new(ip, s7_type), DEFAULT_MAX_POOL_SIZE)
+    }
+
+    /// Create new pooled connection to an S7 PLC addressed by a host name or IP literal
+    ///
+    /// The name is resolved every time the pool establishes a new connection.
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use s7client::{S7Pool, S7Types};
+    ///
+    /// // create S7 pool for a PLC with a DNS name
+    /// let mut pool = S7Pool::from_host("plc-line-1.plant.local", S7Types::S71200)?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if the `Pool` could not be created.
+    pub fn from_host(host: &str, s7_type: S7Types) -> Result<Self, Error> {
+        Self::from_builder(
+            S7ClientBuilder::from_host(host, s7_type),
+            DEFAULT_MAX_POOL_SIZE,
+        )
+    }
+
+    /// Create new pooled connection to an S7 PLC with custom timeouts for every pooled connection
+    ///
+    /// `connect_timeout` limits the time for establishing the TCP connection (default 3 seconds),
+    /// `exchange_timeout` limits sending a request and receiving its response (default 4 seconds).
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use std::net::Ipv4Addr;
+    /// use std::time::Duration;
+    /// use s7client::{S7Pool, S7Types};
+    ///
+    /// // create S7 pool for a slow PLC
+    /// let mut pool = S7Pool::with_timeouts(
+    ///     Ipv4Addr::new(127, 0, 0, 1),
+    ///     S7Types::S71200,
+    ///     Duration::from_secs(10),
+    ///     Duration::from_secs(15),
+    /// )?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if the `Pool` could not be created.
+    pub fn with_timeouts(
+        ip: impl Into<IpAddr>,
+        s7_type: S7Types,
+        connect_timeout: Duration,
+        exchange_timeout: Duration,
+    ) -> Result<Self, Error> {
+        Self::from_builder(
+            S7ClientBuilder::new(ip, s7_type)
+                .connect_timeout(connect_timeout)
+                .exchange_timeout(exchange_timeout),
+            DEFAULT_MAX_POOL_SIZE,
+        )
+    }
+
+    /// Create new pooled connection to an S7 PLC holding at most `max_size` connections
+    ///
+    /// The default pool size is 3. Choose the size according to the number of simultaneous
+    /// connections the CPU accepts: many S7-300 CPUs only support a couple of simultaneous PG/OP
+    /// connections, while a data collection service polling many variables concurrently might
+    /// benefit from a larger pool on an S7-1500.
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use std::net::Ipv4Addr;
+    /// use s7client::{S7Pool, S7Types};
+    ///
+    /// // create S7 pool with a single connection
+    /// let mut pool = S7Pool::with_max_size(Ipv4Addr::new(127, 0, 0, 1), S7Types::S7300, 1)?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if `max_size` is 0.
+    pub fn with_max_size(
+        ip: impl Into<IpAddr>,
+        s7_type: S7Types,
+        max_size: u32,
+    ) -> Result<Self, Error> {
+        Self::from_builder(S7ClientBuilder::new(ip, s7_type), max_size)
+    }
+
+    /// Create new pooled connection to an S7 PLC configured by a builder, holding at most `max_size` connections
+    ///
+    /// Every connection of the pool is established with the configuration of `builder`.
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use std::net::Ipv4Addr;
+    /// use s7client::{S7Client, S7Pool, S7Types};
+    ///
+    /// // create S7 pool with two connections to a CPU in rack 1 / slot 3
+    /// let builder = S7Client::builder(Ipv4Addr::new(127, 0, 0, 1), S7Types::S7400).rack_slot(1, 3);
+    /// let mut pool = S7Pool::from_builder(builder, 2)?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if `max_size` is 0.
+    pub fn from_builder(builder: S7ClientBuilder, max_size: u32) -> Result<Self, Error> {
+        if max_size == 0 {
+            return Err(Error::Conversion(
+                "Pool must hold at least one connection".to_string(),
+            ));
+        }
+
+        let health_check = Arc::new(Mutex::new(HealthCheck::default()));
+        let mgr = S7PoolManager {
+            config: builder,
+            health_check: Arc::clone(&health_check),
+        };
+        let pool = bb8::Pool::builder()
+            .max_size(max_size)
+            .connection_timeout(CONNECTION_TIMEOUT)
             .build_unchecked(mgr);
 
-        Ok(S7Pool(pool))
+        Ok(S7Pool(S7PooledConnection {
+            pool,
+            health_check,
+            shutdown: CancellationToken::new(),
+            gate: PriorityGate::new(max_size as usize),
+            priority: Priority::default(),
+        }))
+    }
+
+    /// Check idle connections with `health_check` before handing them out
+    ///
+    /// By default the pool only checks whether the PLC closed an idle connection, see [`HealthCheck`].
+    /// The check applies to the pool and all of its clones.
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use std::net::Ipv4Addr;
+    /// use s7client::{HealthCheck, S7Pool, S7ReadAccess, S7Types};
+    ///
+    /// // create S7 pool that reads DB100.DBB0 to detect dead connections
+    /// let mut pool = S7Pool::new(Ipv4Addr::new(127, 0, 0, 1), S7Types::S71200)?
+    ///     .health_check(HealthCheck::Read(S7ReadAccess::bytes(100, 0, 1)));
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn health_check(self, health_check: HealthCheck) -> Self {
+        *self
+            .0
+            .health_check
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = health_check;
+        self
+    }
+
+    /// Handle of the pool whose operations wait for a free connection with the given priority
+    ///
+    /// The handle shares the connections with the pool, like a clone. Whenever a connection is
+    /// returned, it is handed to the waiting operation of the highest priority, and among
+    /// operations of the same priority to the one that waited longest. Operations that already
+    /// took a connection are never interrupted, so a high priority operation waits at most until
+    /// the first operation in progress completes. Operations of a low priority are only served
+    /// while no operation of a higher priority waits, they may starve if those never stop.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Priority, S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let polling = pool.with_priority(Priority::Low);
+    /// let data = polling.db_read(100, 0, 4).await?;
+    ///
+    /// pool.with_priority(Priority::High)
+    ///     .db_write(100, 4, &[0x01])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    #[must_use]
+    pub fn with_priority(&self, priority: Priority) -> Self {
+        let mut pool = self.clone();
+        pool.0.priority = priority;
+        pool
+    }
+
+    /// Shut the pool down, closing all of its connections
+    ///
+    /// Once the shutdown began, every operation of the pool and of its clones fails with
+    /// `Error::PoolShuttingDown`, including operations waiting for a free connection. Operations
+    /// that already took a connection are completed, the shutdown waits for their connections to
+    /// be returned and closes all connections afterwards.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// pool.shutdown().await;
+    /// assert!(pool.db_read(100, 0, 4).await.is_err());
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    pub async fn shutdown(&self) {
+        self.0.shutdown.cancel();
+
+        loop {
+            let state = self.0.pool.state();
+            if state.connections == state.idle_connections {
+                break;
+            }
+            sleep(SHUTDOWN_POLL_INTERVAL).await;
+        }
+
+        while self.0.pool.state().idle_connections > 0 {
+            let Ok(mut connection) = self.0.pool.get().await else {
+                break;
+            };
+            // the connection is discarded by the pool once closed, errors on closing are of no interest
+            let _ = connection.disconnect().await;
+        }
+    }
+
+    /// Maximum PDU length in bytes negotiated by a connection of the pool
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// let pdu_length = pool.pdu_length().await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if no connection to the PLC could be established.
+    pub async fn pdu_length(&self) -> Result<u16, Error> {
+        let connection = self.0.get().await?;
+
+        Ok(connection.pdu_length())
+    }
+
+    /// Maximum numbers of unacknowledged requests as `(caller, calle)` negotiated by a connection of the pool
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// let (max_amq_caller, max_amq_calle) = pool.max_amq().await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if no connection to the PLC could be established.
+    pub async fn max_amq(&self) -> Result<(u16, u16), Error> {
+        let connection = self.0.get().await?;
+
+        Ok(connection.max_amq())
+    }
+
+    /// Replace every connection of the pool by a new one, returning the PDU length they negotiated
+    ///
+    /// Connections keep the parameters negotiated when they were established. Call this after the
+    /// configuration or firmware of the PLC changed (e.g. a different PDU size or max AMQ), so all
+    /// connections of the pool split and batch requests the same way again. Connections that are in
+    /// use are replaced once they are returned to the pool, waiting at most the connection timeout of the pool.
+    /// The replaced connections are released with a disconnect request to the PLC.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// // after a firmware update of the PLC
+    /// let pdu_length = pool.renegotiate_all().await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if a connection could not be taken from the pool or could not be established again.
+    /// Connections that failed to reconnect are discarded by the pool.
+    pub async fn renegotiate_all(&self) -> Result<u16, Error> {
+        let mut connections = Vec::new();
+        for _ in 0..self.0.state().connections.max(1) {
+            connections.push(self.0.get().await?);
+        }
+
+        let mut pdu_length = u16::MAX;
+        for connection in &mut connections {
+            connection.reconnect().await?;
+            pdu_length = pdu_length.min(connection.pdu_length());
+        }
+        Ok(pdu_length)
     }
 
     /// Create new collection of observed `Bool` variables of S7 PLC
     ///```rust
+    /// # tokio_test::block_on(async {
     /// use std::net::Ipv4Addr;
     /// use s7client::{S7Pool, S7Types, S7ReadAccess};
     ///
@@ -73,6 +542,7 @@ impl S7Pool {
     ///         ("TRIGGER_TWO", S7ReadAccess::bit(100, 0, 2)),
     ///     ])?;
     /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
     /// ```
     /// # Errors
     ///
@@ -84,6 +554,211 @@ impl S7Pool {
     where
         T: Hash + Eq + Clone,
     {
-        TriggerCollection::new(self, triggers)
+        TriggerCollection::new(self.clone(), triggers)
+    }
+
+    /// Create new collection of observed variables of S7 PLC, e.g. status words
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use std::net::Ipv4Addr;
+    /// use s7client::{S7Pool, S7Types, S7ReadAccess};
+    ///
+    /// // create S7 pool
+    /// let mut pool = S7Pool::new(Ipv4Addr::new(127, 0, 0, 1), S7Types::S71200)?;
+    /// // create value trigger collection
+    /// let  trigger_collection = pool.new_value_trigger_collection(&[
+    ///         ("STATUS_WORD", S7ReadAccess::bytes(100, 0, 2)),
+    ///         ("READY", S7ReadAccess::bit(100, 2, 0)),
+    ///     ]);
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    pub fn new_value_trigger_collection<T>(
+        &self,
+        triggers: &[(T, S7ReadAccess)],
+    ) -> ValueTriggerCollection<T>
+    where
+        T: Hash + Eq + Clone,
+    {
+        ValueTriggerCollection::new(self, triggers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::mock::MockPlc;
+
+    #[test]
+    fn empty_pool_is_rejected() {
+        assert!(matches!(
+            S7Pool::with_max_size(Ipv4Addr::LOCALHOST, S7Types::S71200, 0),
+            Err(Error::Conversion(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn connection_closed_by_plc_is_replaced() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        let pool = S7Pool::from_builder(plc.builder(), 1)
+            .unwrap()
+            .health_check(HealthCheck::Socket);
+        plc.state().close_after_response = true;
+        assert_eq!(pool.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+        plc.state().close_after_response = false;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(pool.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+        assert_eq!(plc.state().connection_requests.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn probe_detects_rst_from_plc() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        let mut client = plc.client().await;
+        assert_eq!(client.probe().await, Ok(()));
+
+        plc.state().close_after_response = true;
+        plc.state().reset_on_close = true;
+        assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let started = tokio::time::Instant::now();
+        assert!(client.probe().await.is_err());
+        assert!(started.elapsed() < Duration::from_millis(100));
+        assert!(client.is_closed());
+    }
+
+    #[tokio::test]
+    async fn read_health_check_probes_idle_connections() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        plc.set_db(2, &[0x00]);
+        let pool = S7Pool::from_builder(plc.builder(), 1)
+            .unwrap()
+            .health_check(HealthCheck::Read(S7ReadAccess::bytes(2, 0, 1)));
+
+        pool.db_read(1, 0, 1).await.unwrap();
+        pool.db_read(1, 0, 1).await.unwrap();
+
+        let probes = plc
+            .state()
+            .requests
+            .iter()
+            .filter(|pdu| pdu[10] == 0x04 && pdu[18..20] == [0x00, 0x02])
+            .count();
+        // every checkout of a connection is probed
+        assert_eq!(probes, 2);
+        assert_eq!(plc.state().connection_requests.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn negotiated_limits_are_reported() {
+        let plc = MockPlc::start().await;
+        plc.state().pdu_length = 240;
+        plc.state().max_amq_caller = 0x0002;
+        plc.state().max_amq_calle = 0x0003;
+        let pool = plc.pool(1);
+
+        assert_eq!(pool.pdu_length().await, Ok(240));
+        assert_eq!(pool.max_amq().await, Ok((2, 3)));
+    }
+
+    #[tokio::test]
+    async fn shutdown_fails_waiting_operations() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x2A]);
+        plc.state().response_delay = Duration::from_millis(200);
+        let pool = plc.pool(1);
+
+        let in_flight = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.db_read(1, 0, 1).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let waiting = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.db_read(1, 0, 1).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let shutdown = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.shutdown().await }
+        });
+        let started = tokio::time::Instant::now();
+        assert_eq!(waiting.await.unwrap(), Err(Error::PoolShuttingDown));
+        assert!(started.elapsed() < Duration::from_millis(100));
+
+        // the operation that already took the connection is completed
+        assert_eq!(in_flight.await.unwrap(), Ok(vec![0x2A]));
+        shutdown.await.unwrap();
+        assert_eq!(pool.0.state().connections, 0);
+        assert_eq!(pool.db_read(1, 0, 1).await, Err(Error::PoolShuttingDown));
+    }
+
+    #[tokio::test]
+    async fn high_priority_write_skips_queued_reads() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00; 4]);
+        plc.state().response_delay = Duration::from_millis(100);
+        let pool = plc.pool(1);
+        let polling = pool.with_priority(Priority::Low);
+
+        let mut reads = Vec::new();
+        for _ in 0..4 {
+            let polling = polling.clone();
+            reads.push(tokio::spawn(async move { polling.db_read(1, 0, 4).await }));
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let critical = pool.with_priority(Priority::High);
+        assert_eq!(
+            critical.db_write(1, 0, &[0x01, 0x02, 0x03, 0x04]).await,
+            Ok(())
+        );
+        for read in reads {
+            assert!(read.await.unwrap().is_ok());
+        }
+
+        // only the read in progress is answered before the write
+        let function_codes = plc
+            .state()
+            .requests
+            .iter()
+            .map(|pdu| pdu[10])
+            .filter(|function_code| [0x04, 0x05].contains(function_code))
+            .collect::<Vec<_>>();
+        assert_eq!(function_codes, [0x04, 0x05, 0x04, 0x04, 0x04]);
+    }
+
+    #[tokio::test]
+    async fn renegotiate_all_replaces_every_connection() {
+        let plc = MockPlc::start().await;
+        let pool = plc.pool(2);
+        let (first, second) = tokio::join!(pool.0.get(), pool.0.get());
+        assert_eq!(first.unwrap().pdu_length(), 480);
+        assert_eq!(second.unwrap().pdu_length(), 480);
+
+        plc.state().pdu_length = 240;
+        assert_eq!(pool.renegotiate_all().await, Ok(240));
+
+        assert_eq!(plc.state().connection_requests.len(), 4);
+        // the replaced connections are not left open on the PLC
+        for _ in 0..50 {
+            if plc.state().disconnect_requests == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(plc.state().disconnect_requests, 2);
+        let (first, second) = tokio::join!(pool.0.get(), pool.0.get());
+        assert_eq!(first.unwrap().pdu_length(), 240);
+        assert_eq!(second.unwrap().pdu_length(), 240);
+        assert_eq!(plc.state().connection_requests.len(), 4);
     }
 }
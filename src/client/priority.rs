@@ -0,0 +1,172 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tokio::sync::oneshot;
+
+/// Priority of the operations of an [`S7Pool`](crate::S7Pool) when waiting for a free connection
+///
+/// See [`S7Pool::with_priority`](crate::S7Pool::with_priority) for the guarantees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Served after all waiting operations of a higher priority, e.g. for polling
+    Low,
+    /// Priority of the operations of a pool unless configured otherwise (default)
+    #[default]
+    Normal,
+    /// Served before all waiting operations of a lower priority, e.g. for critical writes
+    High,
+}
+
+/// Operation waiting for a permit, ordered by priority and then by arrival
+struct Waiter {
+    priority: Priority,
+    ticket: Reverse<u64>,
+    sender: oneshot::Sender<Permit>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.priority, self.ticket).cmp(&(other.priority, other.ticket))
+    }
+}
+
+#[derive(Default)]
+struct GateState {
+    available: usize,
+    next_ticket: u64,
+    waiters: BinaryHeap<Waiter>,
+}
+
+/// Semaphore handing its permits to the waiter of the highest priority first
+///
+/// Waiters of the same priority are served in the order they arrived.
+#[derive(Clone)]
+pub(crate) struct PriorityGate(Arc<Mutex<GateState>>);
+
+impl PriorityGate {
+    pub(crate) fn new(permits: usize) -> Self {
+        Self(Arc::new(Mutex::new(GateState {
+            available: permits,
+            ..GateState::default()
+        })))
+    }
+
+    /// Wait for a permit, which is returned to the gate once dropped
+    pub(crate) async fn acquire(&self, priority: Priority) -> Permit {
+        let receiver = {
+            let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+            if state.available > 0 {
+                state.available -= 1;
+                return Permit(Some(self.clone()));
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let ticket = Reverse(state.next_ticket);
+            state.next_ticket += 1;
+            state.waiters.push(Waiter {
+                priority,
+                ticket,
+                sender,
+            });
+            receiver
+        };
+
+        // waiters are only removed by sending them a permit and the gate outlives them
+        receiver
+            .await
+            .expect("Waiter of the pool dropped without a permit")
+    }
+
+    fn release(&self) {
+        loop {
+            let waiter = {
+                let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+                if let Some(waiter) = state.waiters.pop() {
+                    waiter
+                } else {
+                    state.available += 1;
+                    return;
+                }
+            };
+
+            // a waiter that gave up is skipped, a permit it drops unreceived is returned by its drop
+            match waiter.sender.send(Permit(Some(self.clone()))) {
+                Ok(()) => return,
+                Err(mut permit) => permit.0 = None,
+            }
+        }
+    }
+}
+
+/// Permit of a [`PriorityGate`], allowing to take a connection from the pool
+pub(crate) struct Permit(Option<PriorityGate>);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Some(gate) = self.0.take() {
+            gate.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Priority, PriorityGate};
+
+    #[tokio::test]
+    async fn permits_go_to_higher_priorities_first() {
+        let gate = PriorityGate::new(1);
+        let permit = gate.acquire(Priority::Normal).await;
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut waiters = Vec::new();
+        for (id, priority) in [
+            (0, Priority::Low),
+            (1, Priority::Normal),
+            (2, Priority::Low),
+            (3, Priority::High),
+            (4, Priority::Normal),
+        ] {
+            let (gate, sender) = (gate.clone(), sender.clone());
+            waiters.push(tokio::spawn(async move {
+                let _permit = gate.acquire(priority).await;
+                sender.send(id).unwrap();
+            }));
+            tokio::task::yield_now().await;
+        }
+        // a waiter that gives up does not take a permit with it
+        let cancelled = tokio::spawn({
+            let gate = gate.clone();
+            async move { gate.acquire(Priority::High).await }
+        });
+        tokio::task::yield_now().await;
+        cancelled.abort();
+
+        drop(permit);
+        for waiter in waiters {
+            waiter.await.unwrap();
+        }
+        drop(sender);
+        let mut order = Vec::new();
+        while let Some(id) = receiver.recv().await {
+            order.push(id);
+        }
+        assert_eq!(order, [3, 1, 4, 0, 2]);
+        drop(gate.acquire(Priority::Low).await);
+    }
+}
@@ -0,0 +1,105 @@
+use std::ops::RangeInclusive;
+
+use super::create::S7Client;
+use crate::errors::Error;
+
+/// Byte order of a multi-byte field as detected by [`S7Client::db_probe_endianness`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    /// Only the big-endian interpretation (the S7 native byte order) is plausible
+    Big,
+    /// Only the little-endian interpretation is plausible
+    Little,
+    /// Both or none of the interpretations are plausible
+    Ambiguous,
+}
+
+fn probe(bytes: &[u8], plausible: &RangeInclusive<u64>) -> Endianness {
+    let big = bytes
+        .iter()
+        .fold(0_u64, |value, byte| (value << 8) | u64::from(*byte));
+    let little = bytes
+        .iter()
+        .rev()
+        .fold(0_u64, |value, byte| (value << 8) | u64::from(*byte));
+
+    match (plausible.contains(&big), plausible.contains(&little)) {
+        (true, false) => Endianness::Big,
+        (false, true) => Endianness::Little,
+        _ => Endianness::Ambiguous,
+    }
+}
+
+impl S7Client {
+    /// Guess the byte order of an unsigned multi-byte field in a data block
+    ///
+    /// Meant as a commissioning aid when migrating data written by other drivers, not for production use.
+    /// The field is read once and interpreted as unsigned big- and little-endian integer. The interpretation
+    /// that falls into the `plausible` range of values wins. This is a heuristic: values that are
+    /// symmetric (e.g. `0`) or a range that is too wide result in [`Endianness::Ambiguous`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Endianness, S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// // a motor speed is expected to be between 0 and 3000 rpm
+    /// let endianness = client.db_probe_endianness(100, 4, 2, 0..=3000)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if `len` is not between 2 and 8 bytes or `Error` if any errors occurred during reading.
+    pub async fn db_probe_endianness(
+        &mut self,
+        db_number: u16,
+        byte: u32,
+        len: u16,
+        plausible: RangeInclusive<u64>,
+    ) -> Result<Endianness, Error> {
+        if !(2..=8).contains(&len) {
+            return Err(Error::Conversion(format!(
+                "Byte order can only be probed for fields of 2 to 8 bytes, not {len}"
+            )));
+        }
+
+        let data = self.db_read(db_number, byte, len).await?;
+        Ok(probe(&data, &plausible))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    #[test]
+    fn probe_interpretations() {
+        assert_eq!(probe(&[0x0B, 0xB8], &(0..=3000)), Endianness::Big);
+        assert_eq!(probe(&[0xB8, 0x0B], &(0..=3000)), Endianness::Little);
+        assert_eq!(probe(&[0x00, 0x00], &(0..=3000)), Endianness::Ambiguous);
+        assert_eq!(probe(&[0xFF, 0xFE], &(0..=3000)), Endianness::Ambiguous);
+    }
+
+    #[tokio::test]
+    async fn probe_detects_big_endian_field() {
+        let plc = MockPlc::start().await;
+        // 1_000_000 as big-endian DINT, little-endian it would be 1_089_801_216
+        plc.set_db(100, &[0, 0, 0, 0, 0x00, 0x0F, 0x42, 0x40]);
+        let mut client = plc.client().await;
+
+        let endianness = client
+            .db_probe_endianness(100, 4, 4, 0..=10_000_000)
+            .await
+            .unwrap();
+
+        assert_eq!(endianness, Endianness::Big);
+        assert!(matches!(
+            client.db_probe_endianness(100, 4, 1, 0..=10).await,
+            Err(Error::Conversion(_))
+        ));
+    }
+}
@@ -0,0 +1,194 @@
+use super::create::S7Client;
+use super::pooled::S7Pool;
+use crate::errors::{Error, S7DataItemResponseError};
+use crate::s7_protocol::user_data::read_szl;
+
+// SZL partial list with the protection levels of the CPU
+const SZL_ID_PROTECTION: u16 = 0x0232;
+const SZL_INDEX_PROTECTION: u16 = 0x0004;
+
+/// Protection level of a CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtectionLevel {
+    /// Level 1: reading and writing is allowed
+    None,
+    /// Level 2: writing requires a password
+    WriteProtected,
+    /// Level 3: reading and writing requires a password
+    ReadWriteProtected,
+    /// Level not known to this crate
+    Unknown(u16),
+}
+
+impl From<u16> for ProtectionLevel {
+    fn from(level: u16) -> Self {
+        match level {
+            1 => Self::None,
+            2 => Self::WriteProtected,
+            3 => Self::ReadWriteProtected,
+            level => Self::Unknown(level),
+        }
+    }
+}
+
+impl ProtectionLevel {
+    /// Parse a record of the SZL partial list `0x0232` with index `0x0004`
+    ///
+    /// The record starts with the index, the level set with the mode selector and the password
+    /// level, followed by the protection level that is in effect (2 bytes each).
+    fn from_szl_record(record: &[u8]) -> Result<Self, Error> {
+        if record.len() < 8 {
+            return Err(Error::TryFrom(
+                record.to_vec(),
+                "Invalid length for protection level record".to_string(),
+            ));
+        }
+        Ok(Self::from(u16::from_be_bytes([record[6], record[7]])))
+    }
+}
+
+impl S7Client {
+    /// Read the protection level that is in effect for the CPU
+    ///
+    /// The level is cached for the current connection, it is used to explain rejected reads with
+    /// [`Error::ReadProtected`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{ProtectionLevel, S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300).await?;
+    /// if client.protection_level().await? == ProtectionLevel::ReadWriteProtected {
+    ///     println!("The CPU requires a password");
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the CPU does not report its protection level or `Error`
+    /// if any errors occurred during reading.
+    pub async fn protection_level(&mut self) -> Result<ProtectionLevel, Error> {
+        if let Some(level) = self.protection_level {
+            return Ok(level);
+        }
+
+        self.validate_connection_info().await?;
+        let level = match read_szl(self, SZL_ID_PROTECTION, SZL_INDEX_PROTECTION).await {
+            Ok(list) => list
+                .records()
+                .next()
+                .ok_or(Error::FunctionNotSupported)
+                .and_then(ProtectionLevel::from_szl_record)?,
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                return Err(error);
+            }
+        };
+        self.protection_level = Some(level);
+        Ok(level)
+    }
+
+    /// Explain a read rejected with "access not allowed" by the protection level of the CPU
+    pub(crate) async fn read_access_denied_error(&mut self) -> Error {
+        match self.protection_level().await {
+            Ok(ProtectionLevel::ReadWriteProtected) => Error::ReadProtected,
+            _ => Error::DataItemError(S7DataItemResponseError::AccessNotAllowed),
+        }
+    }
+}
+
+impl S7Pool {
+    /// Read the protection level that is in effect for the CPU
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// let level = pool.protection_level()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the CPU does not report its protection level or `Error`
+    /// if any errors occurred during reading.
+    pub async fn protection_level(&self) -> Result<ProtectionLevel, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.protection_level().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+    use crate::S7ReadAccess;
+
+    fn protection_record(level: u16) -> Vec<u8> {
+        let mut record = vec![0x00, 0x04, 0x00, 0x01, 0x00, 0x00];
+        record.extend(level.to_be_bytes());
+        record.extend([0x00; 12]);
+        record
+    }
+
+    #[tokio::test]
+    async fn access_denied_on_read_protected_cpu() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00; 4]);
+        plc.set_szl(
+            SZL_ID_PROTECTION,
+            SZL_INDEX_PROTECTION,
+            20,
+            &protection_record(3),
+        );
+        plc.state().read_protected = true;
+        let mut client = plc.client().await;
+
+        assert_eq!(client.db_read(1, 0, 4).await, Err(Error::ReadProtected));
+        assert_eq!(
+            client.db_read_multi(&[S7ReadAccess::bytes(1, 0, 2)]).await,
+            Ok(vec![Err(Error::ReadProtected)])
+        );
+        assert_eq!(
+            client.protection_level().await,
+            Ok(ProtectionLevel::ReadWriteProtected)
+        );
+        // the protection level was read only once
+        let szl_requests = plc
+            .state()
+            .requests
+            .iter()
+            .filter(|pdu| pdu[1] == 0x07)
+            .count();
+        assert_eq!(szl_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn access_denied_without_read_protection() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00; 4]);
+        plc.set_szl(
+            SZL_ID_PROTECTION,
+            SZL_INDEX_PROTECTION,
+            20,
+            &protection_record(1),
+        );
+        plc.state().read_protected = true;
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.db_read(1, 0, 4).await,
+            Err(Error::DataItemError(
+                S7DataItemResponseError::AccessNotAllowed
+            ))
+        );
+    }
+}
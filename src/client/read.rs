@@ -1,13 +1,49 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_util::future::join_all;
+use tokio::time::{timeout_at, Instant};
+
+use super::bits;
 use super::create::S7Client;
-use super::{verify_max_bit, S7ReadAccess};
-use crate::S7Pool;
+use super::text::{self, Encoding};
+use super::{verify_max_bit, IoMode, S7ReadAccess};
+use crate::time::{self, S7DateTime};
+use crate::values::FromS7Bytes;
 use crate::{
     errors::Error,
     s7_protocol::{
-        read_area::{read_area_multi, read_area_single},
+        read_area::{
+            batch_read_accesses, max_read_item_len, merge_read_accesses, read_area_multi,
+            read_area_single, read_area_single_bytes, read_area_single_into,
+            read_area_single_partial, read_items_multi, read_items_multi_raw,
+        },
+        segments::data_item::RawDataItem,
         types::Area,
     },
 };
+use crate::{IntType, S7DataType, S7Pool, S7Value};
+
+// Parameters of the 64 bit FNV-1a hash
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Fast non-cryptographic hash (FNV-1a) of data read from the PLC
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn typed_access(db_number: u16, offset: u32, data_type: S7DataType) -> S7ReadAccess {
+    match data_type {
+        #[allow(clippy::cast_possible_truncation)]
+        S7DataType::S7BIT => S7ReadAccess::bit(db_number, offset / 8, (offset % 8) as u8),
+        _ => S7ReadAccess::bytes(db_number, offset, data_type.size()),
+    }
+}
 
 /// * Methods for reading from the PLC device*
 impl S7Client {
@@ -34,7 +70,7 @@ impl S7Client {
         start: u32,
         length: u16,
     ) -> Result<Vec<u8>, Error> {
-        self.validate_connection_info()?;
+        self.validate_connection_info().await?;
         match read_area_single(
             self,
             Area::DataBlock,
@@ -56,17 +92,18 @@ impl S7Client {
         }
     }
 
-    /// Read a specific bit from a specified data block
+    /// Read a defined number bytes from a specified data block with an offset into a shared buffer
+    ///
+    /// Like [`db_read`](S7Client::db_read), but the buffer the responses are collected in is
+    /// returned as is instead of being copied into a `Vec<u8>`. Meant for reading large data blocks at high rates.
     ///
-    /// The bit number must be within the range 0..7
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
     /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
     /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
-    /// let (data_block, byte, bit) = (100, 0, 0);
-    /// let bit = client.db_read_bit(data_block, byte, bit)
+    /// let data = client.db_read_bytes(100, 0, 2000)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
@@ -74,23 +111,25 @@ impl S7Client {
     /// # Errors
     ///
     /// Will return `Error` if any errors occurred during reading.
-    pub async fn db_read_bit(&mut self, db_number: u16, byte: u32, bit: u8) -> Result<bool, Error> {
-        self.validate_connection_info()?;
-
-        verify_max_bit(bit)?;
-
-        match read_area_single(
+    pub async fn db_read_bytes(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+    ) -> Result<Bytes, Error> {
+        self.validate_connection_info().await?;
+        match read_area_single_bytes(
             self,
             Area::DataBlock,
-            S7ReadAccess::Bit {
+            S7ReadAccess::Bytes {
                 db_number,
-                byte,
-                bit,
+                start,
+                length,
             },
         )
         .await
         {
-            Ok(result) => Ok(result[0] > 0),
+            Ok(result) => Ok(result),
             Err(error) => {
                 if error.is_connection_error() {
                     self.set_closed();
@@ -100,36 +139,47 @@ impl S7Client {
         }
     }
 
-    /// Read multiple bytes or bits from different locations of the PLC
+    /// Read bytes from a specified data block with an offset into a provided buffer
+    ///
+    /// Fills `buf` with `buf.len()` bytes starting at byte `start`, e.g. to reuse one scratch buffer for
+    /// every poll instead of allocating the data for each read. Reads exceeding the PDU length are
+    /// split like for [`db_read`](S7Client::db_read). Returns the number of bytes written into `buf`.
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Client, S7Types, S7ReadAccess};
+    /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
     /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
-    /// let data = client.db_read_multi(&[
-    ///        S7ReadAccess::bytes(100, 0, 300),
-    ///        S7ReadAccess::bit(101, 0, 1),
-    ///    ])
-    ///    .await?;
+    /// let mut buf = [0; 200];
+    /// let length = client.db_read_into(100, 0, &mut buf)
+    ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
     /// # Errors
     ///
-    /// Will return `Error` if any errors occurred during reading.
-    pub async fn db_read_multi(
+    /// Will return `Error::DataItemTooLarge` if `buf` is longer than 65535 bytes or `Error` if any errors occurred during reading.
+    pub async fn db_read_into(
         &mut self,
-        info: &[S7ReadAccess],
-    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
-        self.validate_connection_info()?;
-
-        for access in info {
-            verify_max_bit(access.max_bit())?;
-        }
-
-        match read_area_multi(self, Area::DataBlock, info).await {
+        db_number: u16,
+        start: u32,
+        buf: &mut [u8],
+    ) -> Result<usize, Error> {
+        let length = u16::try_from(buf.len()).map_err(|_| Error::DataItemTooLarge)?;
+        self.validate_connection_info().await?;
+        match read_area_single_into(
+            self,
+            Area::DataBlock,
+            S7ReadAccess::Bytes {
+                db_number,
+                start,
+                length,
+            },
+            buf,
+        )
+        .await
+        {
             Ok(result) => Ok(result),
             Err(error) => {
                 if error.is_connection_error() {
@@ -140,7 +190,14 @@ impl S7Client {
         }
     }
 
-    /// Read a defined number of bytes from the 'Merker area' of the PLC with a certain offset
+    /// Read a defined number bytes from a specified data block, keeping the bytes read before an error
+    ///
+    /// Best-effort variant of [`db_read`](S7Client::db_read), e.g. for a diagnostic dump of a large
+    /// data block. The requests of a read exceeding the PDU length are sent one after another
+    /// instead of being pipelined and the read stops at the first request that fails. Returns the
+    /// bytes read up to that point together with the error, which is `None` if all bytes were read.
+    /// The bytes are continuous from `start`, so the first byte that could not be read is at
+    /// `start + data.len()`.
     ///
     /// # Example
     /// ```rust
@@ -148,79 +205,80 @@ impl S7Client {
     /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
     /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
-    /// let (offset, length) = (0, 10);
-    /// let bit = client.mb_read(offset, length)
-    ///     .await?;
+    /// let (data, error) = client.db_read_partial(100, 0, 4000)
+    ///     .await;
+    /// if let Some(error) = error {
+    ///     println!("stopped at byte {}: {error}", data.len());
+    /// }
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
-    /// # Errors
-    ///
-    /// Will return `Error` if any errors occurred during reading.
-    pub async fn mb_read(&mut self, start: u32, length: u16) -> Result<Vec<u8>, Error> {
-        self.validate_connection_info()?;
-        match read_area_single(
+    pub async fn db_read_partial(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+    ) -> (Vec<u8>, Option<Error>) {
+        if let Err(error) = self.validate_connection_info().await {
+            return (Vec::new(), Some(error));
+        }
+        let (data, error) = read_area_single_partial(
             self,
-            Area::Merker,
+            Area::DataBlock,
             S7ReadAccess::Bytes {
-                db_number: 0,
+                db_number,
                 start,
                 length,
             },
         )
-        .await
-        {
-            Ok(result) => Ok(result),
-            Err(error) => {
-                if error.is_connection_error() {
-                    self.set_closed();
-                }
-                Err(error)
-            }
+        .await;
+        if error.as_ref().is_some_and(Error::is_connection_error) {
+            self.set_closed();
         }
+        (data, error)
     }
 
-    /// Read multiple bytes or bits from different 'Merker area' locations of the PLC
+    /// Read a defined number bytes from a specified data block with an offset before a deadline
+    ///
+    /// The deadline bounds the whole operation, including all requests of a read that has to be
+    /// split across multiple PDUs. If the deadline passes before the read is complete, the
+    /// connection is closed because it was interrupted mid exchange.
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Client, S7Types, S7ReadAccess};
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
     /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
-    /// let data = client.mb_read_multi(&[
-    ///        S7ReadAccess::bytes(0, 0, 10),
-    ///        S7ReadAccess::bit(0, 0, 1),
-    ///    ])
-    ///    .await?;
+    /// let deadline = tokio::time::Instant::now() + Duration::from_millis(500);
+    /// let data = client.db_read_until(100, 0, 2000, deadline)
+    ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
     /// # Errors
     ///
-    /// Will return `Error` if any errors occurred during reading.
-    pub async fn mb_read_multi(
+    /// Will return `Error::DataExchangeTimedOut` if the deadline passed or `Error` if any other errors occurred during reading.
+    pub async fn db_read_until(
         &mut self,
-        info: &[S7ReadAccess],
-    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
-        self.validate_connection_info()?;
-
-        for access in info {
-            verify_max_bit(access.max_bit())?;
-        }
-
-        match read_area_multi(self, Area::Merker, info).await {
-            Ok(result) => Ok(result),
-            Err(error) => {
-                if error.is_connection_error() {
-                    self.set_closed();
-                }
-                Err(error)
-            }
+        db_number: u16,
+        start: u32,
+        length: u16,
+        deadline: Instant,
+    ) -> Result<Vec<u8>, Error> {
+        if let Ok(result) = timeout_at(deadline, self.db_read(db_number, start, length)).await {
+            result
+        } else {
+            self.set_closed();
+            Err(Error::DataExchangeTimedOut)
         }
     }
 
-    /// Read a defined number of bytes from the 'input value area' of the PLC with a certain offset
+    /// Read a defined number bytes from a specified data block together with a hash of the data
+    ///
+    /// The hash is a fast non-cryptographic 64 bit FNV-1a hash, so callers polling a large data
+    /// block can cache it and compare it with the hash of the next read instead of the data.
     ///
     /// # Example
     /// ```rust
@@ -228,8 +286,7 @@ impl S7Client {
     /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
     /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
-    /// let (offset, length) = (0, 10);
-    /// let bit = client.i_read(offset, length)
+    /// let (hash, data) = client.db_read_hash(100, 0, 400)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
@@ -237,30 +294,21 @@ impl S7Client {
     /// # Errors
     ///
     /// Will return `Error` if any errors occurred during reading.
-    pub async fn i_read(&mut self, start: u32, length: u16) -> Result<Vec<u8>, Error> {
-        self.validate_connection_info()?;
-        match read_area_single(
-            self,
-            Area::ProcessInput,
-            S7ReadAccess::Bytes {
-                db_number: 0,
-                start,
-                length,
-            },
-        )
-        .await
-        {
-            Ok(result) => Ok(result),
-            Err(error) => {
-                if error.is_connection_error() {
-                    self.set_closed();
-                }
-                Err(error)
-            }
-        }
+    pub async fn db_read_hash(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+    ) -> Result<(u64, Vec<u8>), Error> {
+        let data = self.db_read(db_number, start, length).await?;
+        Ok((fnv1a(&data), data))
     }
 
-    /// Read a defined number of bytes from the 'output value area' of the PLC with a certain offset
+    /// Read a defined number bytes from a specified data block and return them only if their hash changed
+    ///
+    /// `hash` is the hash of a previous read with [`db_read_hash`](Self::db_read_hash). The data
+    /// is still read from the PLC, but `None` is returned if it did not change, which spares the
+    /// caller reprocessing it.
     ///
     /// # Example
     /// ```rust
@@ -268,8 +316,38 @@ impl S7Client {
     /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
     /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
-    /// let (offset, length) = (0, 10);
-    /// let bit = client.o_read(offset, length)
+    /// let (mut hash, _) = client.db_read_hash(100, 0, 400).await?;
+    /// if let Some((new_hash, data)) = client.db_changed_since(100, 0, 400, hash).await? {
+    ///     hash = new_hash;
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_changed_since(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+        hash: u64,
+    ) -> Result<Option<(u64, Vec<u8>)>, Error> {
+        let (new_hash, data) = self.db_read_hash(db_number, start, length).await?;
+        Ok((new_hash != hash).then_some((new_hash, data)))
+    }
+
+    /// Read a specific bit from a specified data block
+    ///
+    /// The bit number must be within the range 0..7
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, byte, bit) = (100, 0, 0);
+    /// let bit = client.db_read_bit(data_block, byte, bit)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
@@ -277,20 +355,23 @@ impl S7Client {
     /// # Errors
     ///
     /// Will return `Error` if any errors occurred during reading.
-    pub async fn o_read(&mut self, start: u32, length: u16) -> Result<Vec<u8>, Error> {
-        self.validate_connection_info()?;
+    pub async fn db_read_bit(&mut self, db_number: u16, byte: u32, bit: u8) -> Result<bool, Error> {
+        self.validate_connection_info().await?;
+
+        verify_max_bit(bit)?;
+
         match read_area_single(
             self,
-            Area::ProcessOutput,
-            S7ReadAccess::Bytes {
-                db_number: 0,
-                start,
-                length,
+            Area::DataBlock,
+            S7ReadAccess::Bit {
+                db_number,
+                byte,
+                bit,
             },
         )
         .await
         {
-            Ok(result) => Ok(result),
+            Ok(result) => Ok(result[0] > 0),
             Err(error) => {
                 if error.is_connection_error() {
                     self.set_closed();
@@ -299,102 +380,1858 @@ impl S7Client {
             }
         }
     }
-}
 
-/// # Methods for reading from the PLC device
-impl S7Pool {
-    /// Read a defined number bytes from a specified data block with an offset
+    /// Read `count` consecutive bits from a specified data block, e.g. an `ARRAY of BOOL`
+    ///
+    /// Only the bytes covering the bits are read. The bits are returned in the order of the PLC:
+    /// starting with bit `start_bit` of byte `start_byte`, continuing with bit 0 of the next byte
+    /// after bit 7.
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Pool, S7Types};
+    /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
-    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
-    /// let (data_block, offset, length) = (100, 0, 4);
-    /// let data = pool.db_read(data_block, offset, length)
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// // DB100.DBX4.2 to DB100.DBX6.1
+    /// let flags = client.db_read_bits(100, 4, 2, 16)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
     /// # Errors
     ///
-    /// Will return `Error` if any errors occurred during reading.
-    pub async fn db_read(&self, db_number: u16, start: u32, length: u16) -> Result<Vec<u8>, Error> {
-        let mut connection = self.0.get().await?;
+    /// Will return `Error::RequestedBitOutOfRange` if `start_bit` is larger than 7 or `Error` if any errors occurred during reading.
+    pub async fn db_read_bits(
+        &mut self,
+        db_number: u16,
+        start_byte: u32,
+        start_bit: u8,
+        count: u16,
+    ) -> Result<Vec<bool>, Error> {
+        verify_max_bit(start_bit)?;
+        if count == 0 {
+            return Ok(Vec::new());
+        }
 
-        connection.db_read(db_number, start, length).await
+        let length = u16::try_from(bits::byte_span(start_bit, count.into()))
+            .map_err(|_| Error::DataItemTooLarge)?;
+        let data = self.db_read(db_number, start_byte, length).await?;
+        bits::unpack_bits(&data, start_bit, count.into())
     }
 
-    /// Read a specific bit from a specified data block
+    /// Read an unsigned value packed into `bit_width` bits of a specified data block
+    ///
+    /// The bitfield starts at bit `start_bit` of byte `byte` (least significant bit first) and may
+    /// continue into the next byte, e.g. a 3-bit mode at bits 2 to 4 of a byte.
     ///
-    /// The bit number must be within the range 0..7
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Pool, S7Types};
+    /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
-    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
-    /// let (data_block, byte, bit) = (100, 0, 0);
-    /// let bit = pool.db_read_bit(data_block, byte, bit)
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// // DB100.DBX4.2 to DB100.DBX4.4
+    /// let mode = client.db_read_bitfield(100, 4, 2, 3)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
     /// # Errors
     ///
-    /// Will return `Error` if any errors occurred during reading.
-    pub async fn db_read_bit(&self, db_number: u16, byte: u32, bit: u8) -> Result<bool, Error> {
-        let mut connection = self.0.get().await?;
-
-        connection.db_read_bit(db_number, byte, bit).await
+    /// Will return `Error::RequestedBitOutOfRange` if the bitfield is empty or covers more than two bytes or `Error` if any errors occurred during reading.
+    pub async fn db_read_bitfield(
+        &mut self,
+        db_number: u16,
+        byte: u32,
+        start_bit: u8,
+        bit_width: u8,
+    ) -> Result<u32, Error> {
+        let length = bits::bitfield_span(start_bit, bit_width)?;
+        let data = self.db_read(db_number, byte, length).await?;
+        bits::extract_bitfield(&data, start_bit, bit_width)
     }
 
-    /// Read multiple bytes or bits from different locations of the PLC
+    /// Read a WORD from a specified data block and expand it into its 16 bits, e.g. a status register
+    ///
+    /// Index `n` of the result holds bit `n` of the WORD. As the WORD is stored big-endian,
+    /// indices 0 to 7 are the bits of byte `byte + 1` and indices 8 to 15 the bits of byte `byte`.
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Pool, S7Types, S7ReadAccess};
+    /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
-    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
-    /// let data = pool.db_read_multi(&[
-    ///        S7ReadAccess::bytes(100, 0, 300),
-    ///        S7ReadAccess::bit(101, 0, 1),
-    ///    ])
-    ///    .await?;
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// // DB100.DBW4
+    /// let flags = client.db_read_word_flags(100, 4)
+    ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
     /// # Errors
     ///
     /// Will return `Error` if any errors occurred during reading.
-    pub async fn db_read_multi(
-        &self,
-        info: &[S7ReadAccess],
-    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
-        let mut connection = self.0.get().await?;
-
-        connection.db_read_multi(info).await
+    pub async fn db_read_word_flags(
+        &mut self,
+        db_number: u16,
+        byte: u32,
+    ) -> Result<[bool; 16], Error> {
+        let data = self.db_read(db_number, byte, 2).await?;
+        Ok(bits::word_to_flags(u16::from_be_bytes([data[0], data[1]])))
     }
 
-    /// Read a defined number of bytes from the 'Merker area' of the PLC with a certain offset
+    /// Read an IEC `TIME` (signed milliseconds, 4 bytes) from a specified data block
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Pool, S7Types};
+    /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
-    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
-    /// let (offset, length) = (0, 10);
-    /// let bit = pool.mb_read(offset, length)
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// // DB100.DBD4
+    /// let duration = client.db_read_time(100, 4)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
     /// # Errors
     ///
-    /// Will return `Error` if any errors occurred during reading.
+    /// Will return `Error::Conversion` if the `TIME` is negative or `Error` if any errors occurred during reading.
+    pub async fn db_read_time(&mut self, db_number: u16, start: u32) -> Result<Duration, Error> {
+        let data = self.db_read(db_number, start, 4).await?;
+        time::decode_time(&data)
+    }
+
+    /// Read an `S5TIME` (BCD factor and time base, 2 bytes) from a specified data block, e.g. a timer preset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300).await?;
+    /// // DB100.DBW4
+    /// let preset = client.db_read_s5time(100, 4)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the bytes are no valid `S5TIME` or `Error` if any errors occurred during reading.
+    pub async fn db_read_s5time(&mut self, db_number: u16, start: u32) -> Result<Duration, Error> {
+        let data = self.db_read(db_number, start, 2).await?;
+        time::decode_s5time(&data)
+    }
+
+    /// Read a `DTL` (date and time with nanoseconds, 12 bytes) from a specified data block
+    ///
+    /// Unlike `DATE_AND_TIME` the year is stored as plain number and not as BCD.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71500).await?;
+    /// let timestamp = client.db_read_dtl(100, 0)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the bytes are no valid `DTL` or `Error` if any errors occurred during reading.
+    pub async fn db_read_dtl(&mut self, db_number: u16, start: u32) -> Result<S7DateTime, Error> {
+        let data = self.db_read(db_number, start, 12).await?;
+        time::decode_dtl(&data)
+    }
+
+    /// Read an integer from a specified data block and multiply it by `scale`, e.g. an `INT` holding tenths of a degree
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{IntType, S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300).await?;
+    /// // DB100.DBW4 in 0.1 °C
+    /// let temperature = client.db_read_fixed(100, 4, IntType::Int, 0.1)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_fixed(
+        &mut self,
+        db_number: u16,
+        byte: u32,
+        int_type: IntType,
+        scale: f64,
+    ) -> Result<f64, Error> {
+        let data = self.db_read(db_number, byte, int_type.size()).await?;
+        int_type.decode_scaled(&data, scale)
+    }
+
+    /// Read multiple bytes or bits from different locations of the PLC
+    ///
+    /// The accesses are read with as few requests as the PDU size allows, the results are returned
+    /// in the order of the given accesses.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let data = client.db_read_multi(&[
+    ///        S7ReadAccess::bytes(100, 0, 300),
+    ///        S7ReadAccess::bit(101, 0, 1),
+    ///    ])
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_multi(
+        &mut self,
+        info: &[S7ReadAccess],
+    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
+        self.validate_connection_info().await?;
+
+        for access in info {
+            verify_max_bit(access.max_bit())?;
+        }
+
+        match read_area_multi(self, Area::DataBlock, info).await {
+            Ok(result) => Ok(result),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read multiple bytes or bits from different locations of the PLC, merging accesses that lie close together
+    ///
+    /// Accesses of the same data block whose gaps are at most `max_gap` bytes are read as one
+    /// larger chunk of bytes and sliced back out afterwards, so densely packed data blocks need
+    /// fewer items per request. The results are returned in the order of the given accesses.
+    /// If a merged chunk can not be read, its accesses are read again one by one to report the
+    /// error of every single access.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let data = client.db_read_multi_merged(&[
+    ///        S7ReadAccess::bytes(100, 0, 4),
+    ///        S7ReadAccess::bytes(100, 6, 2),
+    ///        S7ReadAccess::bit(100, 8, 1),
+    ///    ], 4)
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_multi_merged(
+        &mut self,
+        info: &[S7ReadAccess],
+        max_gap: u16,
+    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
+        self.validate_connection_info().await?;
+
+        for access in info {
+            verify_max_bit(access.max_bit())?;
+        }
+
+        let merged = merge_read_accesses(info, max_gap, max_read_item_len(self.pdu_length));
+        let accesses = merged.iter().map(|read| read.access).collect::<Vec<_>>();
+        let results = self.db_read_multi(&accesses).await?;
+
+        let mut data = (0..info.len()).map(|_| None).collect::<Vec<_>>();
+        let mut failed: Vec<usize> = Vec::new();
+        for (read, result) in merged.iter().zip(results) {
+            match result {
+                Ok(result) => {
+                    for index in &read.members {
+                        data[*index] = Some(Ok(read.extract(&info[*index], &result)));
+                    }
+                }
+                Err(error) if read.members.len() == 1 => data[read.members[0]] = Some(Err(error)),
+                Err(_) => failed.extend(&read.members),
+            }
+        }
+
+        if !failed.is_empty() {
+            let accesses = failed.iter().map(|index| info[*index]).collect::<Vec<_>>();
+            let results = self.db_read_multi(&accesses).await?;
+            for (index, result) in failed.into_iter().zip(results) {
+                data[index] = Some(result);
+            }
+        }
+
+        Ok(data.into_iter().flatten().collect())
+    }
+
+    /// Read multiple variables from different data blocks with one request, returning the data items of the response as they are
+    ///
+    /// Diagnostic variant of [`S7Client::db_read_multi`] exposing the return code, transport size
+    /// and length the PLC sent for every item. Return codes other than success are not turned into
+    /// errors, items are only `Err` if they could not be parsed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let items = client.db_read_multi_raw(&[
+    ///        S7ReadAccess::bytes(100, 0, 4),
+    ///        S7ReadAccess::bit(101, 0, 1),
+    ///    ])
+    ///    .await?;
+    /// for item in items.into_iter().flatten() {
+    ///     println!("{:#04X} {:#04X} {} {:?}", item.error_code, item.var_type, item.count, item.data);
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_multi_raw(
+        &mut self,
+        info: &[S7ReadAccess],
+    ) -> Result<Vec<Result<RawDataItem, Error>>, Error> {
+        self.validate_connection_info().await?;
+
+        for access in info {
+            verify_max_bit(access.max_bit())?;
+        }
+
+        let items = info
+            .iter()
+            .map(|access| (Area::DataBlock, *access))
+            .collect::<Vec<_>>();
+        match read_items_multi_raw(self, &items).await {
+            Ok(result) => Ok(result),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read multiple values of different types from different locations of the PLC with one request
+    ///
+    /// Every access is decoded as the data type it is paired with, so the length of a byte access
+    /// has to match the size of the data type.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7DataType, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let values = client.db_read_multi_typed(&[
+    ///        (S7ReadAccess::bytes(100, 0, 4), S7DataType::S7REAL),
+    ///        (S7ReadAccess::bytes(100, 4, 2), S7DataType::S7INT),
+    ///        (S7ReadAccess::bit(101, 0, 1), S7DataType::S7BIT),
+    ///    ])
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading. Errors of single items, including
+    /// decoding errors, are returned per item.
+    pub async fn db_read_multi_typed(
+        &mut self,
+        info: &[(S7ReadAccess, S7DataType)],
+    ) -> Result<Vec<Result<S7Value, Error>>, Error> {
+        let accesses = info.iter().map(|(access, _)| *access).collect::<Vec<_>>();
+        let results = self.db_read_multi(&accesses).await?;
+
+        Ok(results
+            .into_iter()
+            .zip(info)
+            .map(|(result, (_, data_type))| {
+                result.and_then(|data| S7Value::decode(&data, *data_type))
+            })
+            .collect())
+    }
+
+    /// Read the same number of bytes from the start of several data blocks as one continuous buffer
+    ///
+    /// Useful for recipes or blobs that are spread across consecutive data blocks (e.g. DB100 to DB105).
+    /// The data blocks are read in the given order and as few requests as the PDU size allows.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let data = client.db_read_sequence(&[100, 101, 102, 103, 104, 105], 64)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataBlockAccess` if a single data block could not be read or `Error` if any other errors occurred during reading.
+    pub async fn db_read_sequence(
+        &mut self,
+        db_numbers: &[u16],
+        per_db_len: u16,
+    ) -> Result<Vec<u8>, Error> {
+        let accesses = db_numbers
+            .iter()
+            .map(|db_number| S7ReadAccess::bytes(*db_number, 0, per_db_len))
+            .collect::<Vec<_>>();
+
+        let mut data = Vec::with_capacity(db_numbers.len() * usize::from(per_db_len));
+        for batch in batch_read_accesses(&accesses, self.pdu_length.into()) {
+            let batch = &accesses[batch];
+            if let [access] = batch {
+                // a single data block might be too large for one request and has to be split
+                let result = self
+                    .db_read(access.db_number(), 0, per_db_len)
+                    .await
+                    .map_err(|error| {
+                        if error.is_connection_error() {
+                            error
+                        } else {
+                            Error::DataBlockAccess {
+                                db_number: access.db_number(),
+                                error: Box::new(error),
+                            }
+                        }
+                    })?;
+                data.extend(result);
+            } else {
+                let results = self.db_read_multi(batch).await?;
+                for (access, result) in batch.iter().zip(results) {
+                    data.extend(result.map_err(|error| Error::DataBlockAccess {
+                        db_number: access.db_number(),
+                        error: Box::new(error),
+                    })?);
+                }
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Read variables of different types and areas and decode them into values stored under a key
+    ///
+    /// Every item consists of the key, the area, the data block number (ignored for areas other than
+    /// `Area::DataBlock`), the offset and the data type of the variable. The offset is given in bytes,
+    /// except for `S7DataType::S7BIT` where it is the bit address (`byte * 8 + bit`).
+    /// The items are read with as few requests as the PDU size allows.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Area, S7Client, S7DataType, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let values = client.read_typed_map(&[
+    ///        ("speed", Area::DataBlock, 100, 0, S7DataType::S7REAL),
+    ///        ("running", Area::DataBlock, 100, 4 * 8 + 1, S7DataType::S7BIT),
+    ///        ("mode", Area::Merker, 0, 10, S7DataType::S7INT),
+    ///    ])
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading. Errors of single items are returned in the map.
+    pub async fn read_typed_map<K>(
+        &mut self,
+        items: &[(K, Area, u16, u32, S7DataType)],
+    ) -> Result<HashMap<K, Result<S7Value, Error>>, Error>
+    where
+        K: Hash + Eq + Clone,
+    {
+        self.validate_connection_info().await?;
+
+        let accesses = items
+            .iter()
+            .map(|(_, area, db_number, offset, data_type)| {
+                (*area, typed_access(*db_number, *offset, *data_type))
+            })
+            .collect::<Vec<_>>();
+        let read_accesses = accesses
+            .iter()
+            .map(|(_, access)| *access)
+            .collect::<Vec<_>>();
+
+        let mut values = HashMap::with_capacity(items.len());
+        for batch in batch_read_accesses(&read_accesses, self.pdu_length.into()) {
+            let results = match read_items_multi(self, &accesses[batch.clone()]).await {
+                Ok(results) => results,
+                Err(error) => {
+                    if error.is_connection_error() {
+                        self.set_closed();
+                    }
+                    return Err(error);
+                }
+            };
+            for ((key, _, _, _, data_type), result) in items[batch].iter().zip(results) {
+                values.insert(
+                    key.clone(),
+                    result.and_then(|data| S7Value::decode(&data, *data_type)),
+                );
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Read an `ARRAY of CHAR` from a specified data block and decode it with the given encoding
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Encoding, S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, offset, length) = (100, 0, 20);
+    /// let text = client.db_read_char_array(data_block, offset, length, Encoding::Latin1)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading or if the bytes are invalid for the chosen encoding.
+    pub async fn db_read_char_array(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+        encoding: Encoding,
+    ) -> Result<String, Error> {
+        let data = self.db_read(db_number, start, length).await?;
+        text::decode(&data, encoding)
+    }
+
+    /// Read a fixed `ARRAY of CHAR` from a specified data block as Latin-1 text
+    ///
+    /// S7 stores characters in ISO-8859-1, so every byte maps to exactly one character. Unlike a
+    /// `STRING`, the array has no length header and all `length` bytes are returned.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, offset, length) = (100, 0, 20);
+    /// let text = client.db_read_chars(data_block, offset, length)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_chars(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+    ) -> Result<String, Error> {
+        self.db_read_char_array(db_number, start, length, Encoding::Latin1)
+            .await
+    }
+
+    /// Read an `ARRAY of REAL` with `count` elements from a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, offset, count) = (100, 0, 16);
+    /// let values = client.db_read_real_array(data_block, offset, count)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_real_array(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        count: u16,
+    ) -> Result<Vec<f32>, Error> {
+        self.db_read_array(db_number, start, count).await
+    }
+
+    /// Read an `ARRAY of REAL` with `count` elements from a specified data block, replacing non-finite values by `None`
+    ///
+    /// Uninitialized or invalid elements (`NaN` and infinity) are returned as `None`, use
+    /// `unwrap_or` on the elements to replace them with a sentinel value instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, offset, count) = (100, 0, 16);
+    /// let values: Vec<f32> = client.db_read_real_array_filtered(data_block, offset, count)
+    ///     .await?
+    ///     .into_iter()
+    ///     .map(|value| value.unwrap_or(-1.0))
+    ///     .collect();
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_real_array_filtered(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        count: u16,
+    ) -> Result<Vec<Option<f32>>, Error> {
+        Ok(self
+            .db_read_real_array(db_number, start, count)
+            .await?
+            .into_iter()
+            .map(|value| value.is_finite().then_some(value))
+            .collect())
+    }
+
+    /// Read `count` REAL values placed every `stride` bytes in a specified data block, e.g. one channel of interleaved sensor data
+    ///
+    /// The region covering all values is read at once and the first value starts at `start`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// // second channel of [ch0, ch1, ch0, ch1, ...]
+    /// let (data_block, offset, stride, count) = (100, 4, 8, 16);
+    /// let values = client.db_read_real_strided(data_block, offset, stride, count)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if `stride` is smaller than 4 bytes, `Error::DataItemTooLarge` if the covered
+    /// region exceeds 65535 bytes or `Error` if any errors occurred during reading.
+    pub async fn db_read_real_strided(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        stride: u16,
+        count: u16,
+    ) -> Result<Vec<f32>, Error> {
+        if stride < 4 {
+            return Err(Error::Conversion(format!(
+                "REAL values need a stride of at least 4 bytes, not {stride}"
+            )));
+        }
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let length = u16::try_from(usize::from(count - 1) * usize::from(stride) + 4)
+            .map_err(|_| Error::DataItemTooLarge)?;
+        let data = self.db_read(db_number, start, length).await?;
+        if data.len() < usize::from(length) {
+            return Err(Error::Conversion(format!(
+                "Expected {length} bytes but got {}",
+                data.len()
+            )));
+        }
+        Ok(data
+            .chunks(usize::from(stride))
+            .take(usize::from(count))
+            .map(|chunk| f32::from_be_slice(&chunk[..4]))
+            .collect())
+    }
+
+    /// Read an `ARRAY of INT` with `count` elements from a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, offset, count) = (100, 0, 10);
+    /// let values = client.db_read_int_array(data_block, offset, count)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataItemTooLarge` if the array exceeds 65535 bytes or `Error` if any errors occurred during reading.
+    pub async fn db_read_int_array(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        count: u16,
+    ) -> Result<Vec<i16>, Error> {
+        self.db_read_array(db_number, start, count).await
+    }
+
+    /// Read an `ARRAY of DINT` with `count` elements from a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, offset, count) = (100, 0, 10);
+    /// let values = client.db_read_dint_array(data_block, offset, count)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataItemTooLarge` if the array exceeds 65535 bytes or `Error` if any errors occurred during reading.
+    pub async fn db_read_dint_array(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        count: u16,
+    ) -> Result<Vec<i32>, Error> {
+        self.db_read_array(db_number, start, count).await
+    }
+
+    /// Read `count` consecutive big-endian elements with one (split) read
+    async fn db_read_array<T: FromS7Bytes>(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        count: u16,
+    ) -> Result<Vec<T>, Error> {
+        let length =
+            u16::try_from(usize::from(count) * T::SIZE).map_err(|_| Error::DataItemTooLarge)?;
+        let data = self.db_read(db_number, start, length).await?;
+        T::from_s7_array(&data)
+    }
+
+    /// Read a defined number of bytes from the 'Merker area' of the PLC with a certain offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (offset, length) = (0, 10);
+    /// let bit = client.mb_read(offset, length)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn mb_read(&mut self, start: u32, length: u16) -> Result<Vec<u8>, Error> {
+        self.validate_connection_info().await?;
+        match read_area_single(
+            self,
+            Area::Merker,
+            S7ReadAccess::Bytes {
+                db_number: 0,
+                start,
+                length,
+            },
+        )
+        .await
+        {
+            Ok(result) => Ok(result),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read multiple bytes or bits from different 'Merker area' locations of the PLC
+    ///
+    /// The accesses are read with as few requests as the PDU size allows, the results are returned
+    /// in the order of the given accesses.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let data = client.mb_read_multi(&[
+    ///        S7ReadAccess::bytes(0, 0, 10),
+    ///        S7ReadAccess::bit(0, 0, 1),
+    ///    ])
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn mb_read_multi(
+        &mut self,
+        info: &[S7ReadAccess],
+    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
+        self.validate_connection_info().await?;
+
+        for access in info {
+            verify_max_bit(access.max_bit())?;
+        }
+
+        match read_area_multi(self, Area::Merker, info).await {
+            Ok(result) => Ok(result),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read a defined number of bytes from the 'input value area' of the PLC with a certain offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (offset, length) = (0, 10);
+    /// let bit = client.i_read(offset, length)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn i_read(&mut self, start: u32, length: u16) -> Result<Vec<u8>, Error> {
+        self.validate_connection_info().await?;
+        match read_area_single(
+            self,
+            Area::ProcessInput,
+            S7ReadAccess::Bytes {
+                db_number: 0,
+                start,
+                length,
+            },
+        )
+        .await
+        {
+            Ok(result) => Ok(result),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read a defined number of bytes from the 'output value area' of the PLC with a certain offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (offset, length) = (0, 10);
+    /// let bit = client.o_read(offset, length)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn o_read(&mut self, start: u32, length: u16) -> Result<Vec<u8>, Error> {
+        self.validate_connection_info().await?;
+        match read_area_single(
+            self,
+            Area::ProcessOutput,
+            S7ReadAccess::Bytes {
+                db_number: 0,
+                start,
+                length,
+            },
+        )
+        .await
+        {
+            Ok(result) => Ok(result),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read a defined number of bytes of the inputs of the PLC with a certain offset, either from the process image or the peripheral modules
+    ///
+    /// With [`IoMode::ProcessImage`] this is the same as [`i_read`](Self::i_read), with
+    /// [`IoMode::Peripheral`] the current values of the input modules are read.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{IoMode, S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (offset, length) = (0, 10);
+    /// let inputs = client.io_read(IoMode::Peripheral, offset, length)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn io_read(
+        &mut self,
+        mode: IoMode,
+        start: u32,
+        length: u16,
+    ) -> Result<Vec<u8>, Error> {
+        self.validate_connection_info().await?;
+        match read_area_single(
+            self,
+            mode.input_area(),
+            S7ReadAccess::Bytes {
+                db_number: 0,
+                start,
+                length,
+            },
+        )
+        .await
+        {
+            Ok(result) => Ok(result),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read bytes or a bit from any area of the PLC
+    ///
+    /// Generic variant of the area specific reading functions, e.g. for areas chosen at runtime.
+    /// The data block number of the access is ignored for areas other than `Area::DataBlock`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Area, S7Client, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let data = client.read(Area::Merker, S7ReadAccess::bytes(0, 10, 2))
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn read(&mut self, area: Area, access: S7ReadAccess) -> Result<Vec<u8>, Error> {
+        self.validate_connection_info().await?;
+        verify_max_bit(access.max_bit())?;
+
+        match read_area_single(self, area, access).await {
+            Ok(result) => Ok(result),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+/// # Methods for reading from the PLC device
+impl S7Pool {
+    /// Read a defined number bytes from a specified data block with an offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, length) = (100, 0, 4);
+    /// let data = pool.db_read(data_block, offset, length)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read(&self, db_number: u16, start: u32, length: u16) -> Result<Vec<u8>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read(db_number, start, length).await
+    }
+
+    /// Read a defined number bytes from a specified data block with an offset into a shared buffer
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let data = pool.db_read_bytes(100, 0, 2000)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_bytes(
+        &self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+    ) -> Result<Bytes, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_bytes(db_number, start, length).await
+    }
+
+    /// Read bytes from a specified data block with an offset into a provided buffer
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let mut buf = [0; 200];
+    /// let length = pool.db_read_into(100, 0, &mut buf)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataItemTooLarge` if `buf` is longer than 65535 bytes or `Error` if any errors occurred during reading.
+    pub async fn db_read_into(
+        &self,
+        db_number: u16,
+        start: u32,
+        buf: &mut [u8],
+    ) -> Result<usize, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_into(db_number, start, buf).await
+    }
+
+    /// Read a defined number bytes from a specified data block, keeping the bytes read before an error
+    ///
+    /// See [`S7Client::db_read_partial`]. If no connection could be taken from the pool, no bytes
+    /// are returned together with the error.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data, error) = pool.db_read_partial(100, 0, 4000)
+    ///     .await;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    pub async fn db_read_partial(
+        &self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+    ) -> (Vec<u8>, Option<Error>) {
+        match self.0.get().await {
+            Ok(mut connection) => connection.db_read_partial(db_number, start, length).await,
+            Err(error) => (Vec::new(), Some(error)),
+        }
+    }
+
+    /// Read a defined number bytes from a specified data block with an offset before a deadline
+    ///
+    /// The deadline bounds the whole operation, including waiting for a free pooled connection.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let deadline = tokio::time::Instant::now() + Duration::from_millis(500);
+    /// let data = pool.db_read_until(100, 0, 2000, deadline)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataExchangeTimedOut` if the deadline passed or `Error` if any other errors occurred during reading.
+    pub async fn db_read_until(
+        &self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+        deadline: Instant,
+    ) -> Result<Vec<u8>, Error> {
+        let mut connection = timeout_at(deadline, self.0.get())
+            .await
+            .map_err(|_| Error::DataExchangeTimedOut)??;
+
+        connection
+            .db_read_until(db_number, start, length, deadline)
+            .await
+    }
+
+    /// Read a defined number bytes from a specified data block together with a hash of the data
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (hash, data) = pool.db_read_hash(100, 0, 400)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_hash(
+        &self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+    ) -> Result<(u64, Vec<u8>), Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_read_hash(db_number, start, length).await
+    }
+
+    /// Read a defined number bytes from a specified data block and return them only if their hash changed
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (hash, _) = pool.db_read_hash(100, 0, 400).await?;
+    /// let changed = pool.db_changed_since(100, 0, 400, hash)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_changed_since(
+        &self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+        hash: u64,
+    ) -> Result<Option<(u64, Vec<u8>)>, Error> {
+        let mut connection = self.0.get().await?;
+        connection
+            .db_changed_since(db_number, start, length, hash)
+            .await
+    }
+
+    /// Read multiple bytes or bits from different locations of the PLC concurrently
+    ///
+    /// Unlike [`db_read_multi`](Self::db_read_multi) every access is read with an own request,
+    /// spread across the connections of the pool. The results are guaranteed to be in the same
+    /// order as the accesses, regardless of which read finishes first.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let data = pool.db_read_many(&[
+    ///        S7ReadAccess::bytes(100, 0, 2000),
+    ///        S7ReadAccess::bytes(101, 0, 2000),
+    ///        S7ReadAccess::bit(102, 0, 1),
+    ///    ])
+    ///    .await;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    pub async fn db_read_many(&self, info: &[S7ReadAccess]) -> Vec<Result<Vec<u8>, Error>> {
+        // the reads are driven by the calling task, so dropping the returned future cancels all of them
+        join_all(info.iter().map(|access| async move {
+            match *access {
+                S7ReadAccess::Bytes {
+                    db_number,
+                    start,
+                    length,
+                } => self.db_read(db_number, start, length).await,
+                S7ReadAccess::Bit {
+                    db_number,
+                    byte,
+                    bit,
+                } => self
+                    .db_read_bit(db_number, byte, bit)
+                    .await
+                    .map(|value| vec![u8::from(value)]),
+            }
+        }))
+        .await
+    }
+
+    /// Read a specific bit from a specified data block
+    ///
+    /// The bit number must be within the range 0..7
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, byte, bit) = (100, 0, 0);
+    /// let bit = pool.db_read_bit(data_block, byte, bit)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_bit(&self, db_number: u16, byte: u32, bit: u8) -> Result<bool, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_bit(db_number, byte, bit).await
+    }
+
+    /// Read `count` consecutive bits from a specified data block, e.g. an `ARRAY of BOOL`
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// // DB100.DBX4.2 to DB100.DBX6.1
+    /// let flags = pool.db_read_bits(100, 4, 2, 16)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::RequestedBitOutOfRange` if `start_bit` is larger than 7 or `Error` if any errors occurred during reading.
+    pub async fn db_read_bits(
+        &self,
+        db_number: u16,
+        start_byte: u32,
+        start_bit: u8,
+        count: u16,
+    ) -> Result<Vec<bool>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection
+            .db_read_bits(db_number, start_byte, start_bit, count)
+            .await
+    }
+
+    /// Read an unsigned value packed into `bit_width` bits of a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// // DB100.DBX4.2 to DB100.DBX4.4
+    /// let mode = pool.db_read_bitfield(100, 4, 2, 3)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::RequestedBitOutOfRange` if the bitfield is empty or covers more than two bytes or `Error` if any errors occurred during reading.
+    pub async fn db_read_bitfield(
+        &self,
+        db_number: u16,
+        byte: u32,
+        start_bit: u8,
+        bit_width: u8,
+    ) -> Result<u32, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection
+            .db_read_bitfield(db_number, byte, start_bit, bit_width)
+            .await
+    }
+
+    /// Read a WORD from a specified data block and expand it into its 16 bits, e.g. a status register
+    ///
+    /// Index `n` of the result holds bit `n` of the WORD.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// // DB100.DBW4
+    /// let flags = pool.db_read_word_flags(100, 4)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_word_flags(&self, db_number: u16, byte: u32) -> Result<[bool; 16], Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_read_word_flags(db_number, byte).await
+    }
+
+    /// Read an IEC `TIME` (signed milliseconds, 4 bytes) from a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// // DB100.DBD4
+    /// let duration = pool.db_read_time(100, 4)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the `TIME` is negative or `Error` if any errors occurred during reading.
+    pub async fn db_read_time(&self, db_number: u16, start: u32) -> Result<Duration, Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_read_time(db_number, start).await
+    }
+
+    /// Read an `S5TIME` (BCD factor and time base, 2 bytes) from a specified data block, e.g. a timer preset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// // DB100.DBW4
+    /// let preset = pool.db_read_s5time(100, 4)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the bytes are no valid `S5TIME` or `Error` if any errors occurred during reading.
+    pub async fn db_read_s5time(&self, db_number: u16, start: u32) -> Result<Duration, Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_read_s5time(db_number, start).await
+    }
+
+    /// Read a `DTL` (date and time with nanoseconds, 12 bytes) from a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71500)?;
+    /// let timestamp = pool.db_read_dtl(100, 0)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the bytes are no valid `DTL` or `Error` if any errors occurred during reading.
+    pub async fn db_read_dtl(&self, db_number: u16, start: u32) -> Result<S7DateTime, Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_read_dtl(db_number, start).await
+    }
+
+    /// Read an integer from a specified data block and multiply it by `scale`
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{IntType, S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// let temperature = pool.db_read_fixed(100, 4, IntType::Int, 0.1)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_fixed(
+        &self,
+        db_number: u16,
+        byte: u32,
+        int_type: IntType,
+        scale: f64,
+    ) -> Result<f64, Error> {
+        let mut connection = self.0.get().await?;
+        connection
+            .db_read_fixed(db_number, byte, int_type, scale)
+            .await
+    }
+
+    /// Read multiple bytes or bits from different locations of the PLC
+    ///
+    /// The accesses are read with as few requests as the PDU size allows, the results are returned
+    /// in the order of the given accesses.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let data = pool.db_read_multi(&[
+    ///        S7ReadAccess::bytes(100, 0, 300),
+    ///        S7ReadAccess::bit(101, 0, 1),
+    ///    ])
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_multi(
+        &self,
+        info: &[S7ReadAccess],
+    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_multi(info).await
+    }
+
+    /// Read multiple bytes or bits from different locations of the PLC, merging accesses that lie close together
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let data = pool.db_read_multi_merged(&[
+    ///        S7ReadAccess::bytes(100, 0, 4),
+    ///        S7ReadAccess::bytes(100, 6, 2),
+    ///        S7ReadAccess::bit(100, 8, 1),
+    ///    ], 4)
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_multi_merged(
+        &self,
+        info: &[S7ReadAccess],
+        max_gap: u16,
+    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_multi_merged(info, max_gap).await
+    }
+
+    /// Read multiple variables from different data blocks with one request, returning the data items of the response as they are
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let items = pool.db_read_multi_raw(&[S7ReadAccess::bytes(100, 0, 4)])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_multi_raw(
+        &self,
+        info: &[S7ReadAccess],
+    ) -> Result<Vec<Result<RawDataItem, Error>>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_multi_raw(info).await
+    }
+
+    /// Read multiple values of different types from different locations of the PLC with one request
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7DataType, S7Pool, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let values = pool.db_read_multi_typed(&[
+    ///        (S7ReadAccess::bytes(100, 0, 4), S7DataType::S7REAL),
+    ///        (S7ReadAccess::bit(101, 0, 1), S7DataType::S7BIT),
+    ///    ])
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading. Errors of single items, including
+    /// decoding errors, are returned per item.
+    pub async fn db_read_multi_typed(
+        &self,
+        info: &[(S7ReadAccess, S7DataType)],
+    ) -> Result<Vec<Result<S7Value, Error>>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_multi_typed(info).await
+    }
+
+    /// Read the same number of bytes from the start of several data blocks as one continuous buffer
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let data = pool.db_read_sequence(&[100, 101, 102, 103, 104, 105], 64)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataBlockAccess` if a single data block could not be read or `Error` if any other errors occurred during reading.
+    pub async fn db_read_sequence(
+        &self,
+        db_numbers: &[u16],
+        per_db_len: u16,
+    ) -> Result<Vec<u8>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_sequence(db_numbers, per_db_len).await
+    }
+
+    /// Read variables of different types and areas and decode them into values stored under a key
+    ///
+    /// See [`S7Client::read_typed_map`] for the layout of the items.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Area, S7Pool, S7DataType, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let values = pool.read_typed_map(&[
+    ///        ("speed", Area::DataBlock, 100, 0, S7DataType::S7REAL),
+    ///        ("running", Area::DataBlock, 100, 4 * 8 + 1, S7DataType::S7BIT),
+    ///    ])
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading. Errors of single items are returned in the map.
+    pub async fn read_typed_map<K>(
+        &self,
+        items: &[(K, Area, u16, u32, S7DataType)],
+    ) -> Result<HashMap<K, Result<S7Value, Error>>, Error>
+    where
+        K: Hash + Eq + Clone,
+    {
+        let mut connection = self.0.get().await?;
+
+        connection.read_typed_map(items).await
+    }
+
+    /// Read an `ARRAY of CHAR` from a specified data block and decode it with the given encoding
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Encoding, S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, length) = (100, 0, 20);
+    /// let text = pool.db_read_char_array(data_block, offset, length, Encoding::Latin1)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading or if the bytes are invalid for the chosen encoding.
+    pub async fn db_read_char_array(
+        &self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+        encoding: Encoding,
+    ) -> Result<String, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection
+            .db_read_char_array(db_number, start, length, encoding)
+            .await
+    }
+
+    /// Read a fixed `ARRAY of CHAR` from a specified data block as Latin-1 text
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, length) = (100, 0, 20);
+    /// let text = pool.db_read_chars(data_block, offset, length)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_chars(
+        &self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+    ) -> Result<String, Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_read_chars(db_number, start, length).await
+    }
+
+    /// Read an `ARRAY of REAL` with `count` elements from a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, count) = (100, 0, 16);
+    /// let values = pool.db_read_real_array(data_block, offset, count)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_real_array(
+        &self,
+        db_number: u16,
+        start: u32,
+        count: u16,
+    ) -> Result<Vec<f32>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_real_array(db_number, start, count).await
+    }
+
+    /// Read an `ARRAY of REAL` with `count` elements from a specified data block, replacing non-finite values by `None`
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, count) = (100, 0, 16);
+    /// let values = pool.db_read_real_array_filtered(data_block, offset, count)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_read_real_array_filtered(
+        &self,
+        db_number: u16,
+        start: u32,
+        count: u16,
+    ) -> Result<Vec<Option<f32>>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection
+            .db_read_real_array_filtered(db_number, start, count)
+            .await
+    }
+
+    /// Read `count` REAL values placed every `stride` bytes in a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, stride, count) = (100, 4, 8, 16);
+    /// let values = pool.db_read_real_strided(data_block, offset, stride, count)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if `stride` is smaller than 4 bytes, `Error::DataItemTooLarge` if the covered
+    /// region exceeds 65535 bytes or `Error` if any errors occurred during reading.
+    pub async fn db_read_real_strided(
+        &self,
+        db_number: u16,
+        start: u32,
+        stride: u16,
+        count: u16,
+    ) -> Result<Vec<f32>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection
+            .db_read_real_strided(db_number, start, stride, count)
+            .await
+    }
+
+    /// Read an `ARRAY of INT` with `count` elements from a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, count) = (100, 0, 10);
+    /// let values = pool.db_read_int_array(data_block, offset, count)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataItemTooLarge` if the array exceeds 65535 bytes or `Error` if any errors occurred during reading.
+    pub async fn db_read_int_array(
+        &self,
+        db_number: u16,
+        start: u32,
+        count: u16,
+    ) -> Result<Vec<i16>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_int_array(db_number, start, count).await
+    }
+
+    /// Read an `ARRAY of DINT` with `count` elements from a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, count) = (100, 0, 10);
+    /// let values = pool.db_read_dint_array(data_block, offset, count)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataItemTooLarge` if the array exceeds 65535 bytes or `Error` if any errors occurred during reading.
+    pub async fn db_read_dint_array(
+        &self,
+        db_number: u16,
+        start: u32,
+        count: u16,
+    ) -> Result<Vec<i32>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.db_read_dint_array(db_number, start, count).await
+    }
+
+    /// Read a defined number of bytes from the 'Merker area' of the PLC with a certain offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (offset, length) = (0, 10);
+    /// let bit = pool.mb_read(offset, length)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
     pub async fn mb_read(&self, start: u32, length: u16) -> Result<Vec<u8>, Error> {
         let mut connection = self.0.get().await?;
 
@@ -446,4 +2283,647 @@ impl S7Pool {
 
         connection.o_read(start, length).await
     }
+
+    /// Read a defined number of bytes of the inputs of the PLC with a certain offset, either from the process image or the peripheral modules
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{IoMode, S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (offset, length) = (0, 10);
+    /// let inputs = pool.io_read(IoMode::Peripheral, offset, length)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn io_read(&self, mode: IoMode, start: u32, length: u16) -> Result<Vec<u8>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.io_read(mode, start, length).await
+    }
+
+    /// Read bytes or a bit from any area of the PLC
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Area, S7Pool, S7Types, S7ReadAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let data = pool.read(Area::Merker, S7ReadAccess::bytes(0, 10, 2))
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn read(&self, area: Area, access: S7ReadAccess) -> Result<Vec<u8>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.read(area, access).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use crate::errors::Error;
+    use crate::mock::MockPlc;
+    use crate::{Area, IoMode, RawDataItem, S7DataType, S7ReadAccess, S7Value, S7WriteAccess};
+
+    #[tokio::test]
+    async fn partial_read_keeps_chunks_before_error() {
+        let plc = MockPlc::start().await;
+        plc.state().pdu_length = 240;
+        let data: Vec<u8> = (0..600_u16).map(|i| (i % 251) as u8).collect();
+        plc.set_db(1, &data);
+        let mut client = plc.client().await;
+
+        // responses hold 222 bytes each, the third chunk exceeds the data block
+        let (partial, error) = client.db_read_partial(1, 0, 800).await;
+
+        assert_eq!(partial, data[..444]);
+        assert!(matches!(error, Some(Error::DataItemError(_))));
+        assert!(client.db_read(1, 0, 800).await.is_err());
+        assert_eq!(
+            client.db_read_partial(1, 100, 500).await,
+            (data[100..].to_vec(), None)
+        );
+    }
+
+    async fn slow_plc() -> MockPlc {
+        let plc = MockPlc::start().await;
+        let data: Vec<u8> = (0..1000_u16).map(|i| (i % 251) as u8).collect();
+        plc.set_db(1, &data);
+        // 1000 bytes need 5 requests with a PDU length of 240 bytes
+        plc.state().pdu_length = 240;
+        plc.state().response_delay = Duration::from_millis(100);
+        plc
+    }
+
+    #[tokio::test]
+    async fn read_bytes_matches_read() {
+        let plc = MockPlc::start().await;
+        let data: Vec<u8> = (0..1000_u16).map(|i| (i % 251) as u8).collect();
+        plc.set_db(1, &data);
+        plc.state().pdu_length = 240;
+        let mut client = plc.client().await;
+
+        let bytes = client.db_read_bytes(1, 0, 1000).await.unwrap();
+
+        assert_eq!(bytes, data);
+        assert_eq!(client.db_read(1, 0, 1000).await, Ok(data));
+    }
+
+    #[tokio::test]
+    async fn time_values_are_decoded() {
+        let plc = MockPlc::start().await;
+        // T#1m30s as TIME, S5T#2m30s as S5TIME (150 * 1 s) and an invalid BCD factor
+        plc.set_db(1, &[0x00, 0x01, 0x5F, 0x90, 0x21, 0x50, 0x0A, 0x00]);
+        let mut client = plc.client().await;
+
+        assert_eq!(client.db_read_time(1, 0).await, Ok(Duration::from_secs(90)));
+        assert_eq!(
+            client.db_read_s5time(1, 4).await,
+            Ok(Duration::from_secs(150))
+        );
+        assert!(matches!(
+            client.db_read_s5time(1, 6).await,
+            Err(Error::Conversion(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_into_reuses_buffer() {
+        let plc = MockPlc::start().await;
+        let data: Vec<u8> = (0..1000_u16).map(|i| (i % 251) as u8).collect();
+        plc.set_db(1, &data);
+        plc.state().pdu_length = 240;
+        let mut client = plc.client().await;
+        let mut buf = vec![0; 1000];
+
+        assert_eq!(client.db_read_into(1, 0, &mut buf).await, Ok(1000));
+        assert_eq!(buf, data);
+        assert_eq!(client.db_read_into(1, 500, &mut buf[..10]).await, Ok(10));
+        assert_eq!(buf[..10], data[500..510]);
+        assert_eq!(
+            client.db_read_into(1, 0, &mut vec![0; 70_000]).await,
+            Err(Error::DataItemTooLarge)
+        );
+    }
+
+    #[tokio::test]
+    async fn io_read_targets_process_image_or_peripherals() {
+        let plc = MockPlc::start().await;
+        plc.state().areas.insert((0x81, 0), vec![0x01, 0x02]);
+        plc.state().areas.insert((0x80, 0), vec![0x03, 0x04]);
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.io_read(IoMode::default(), 0, 2).await,
+            Ok(vec![0x01, 0x02])
+        );
+        assert_eq!(
+            client.io_read(IoMode::Peripheral, 0, 2).await,
+            Ok(vec![0x03, 0x04])
+        );
+
+        let areas = plc.state().requests[1..]
+            .iter()
+            .map(|request| request[20])
+            .collect::<Vec<_>>();
+        assert_eq!(areas, [0x81, 0x80]);
+    }
+
+    #[tokio::test]
+    async fn generic_read_and_write_target_area() {
+        let plc = MockPlc::start().await;
+        plc.state().areas.insert((0x83, 0), vec![0x00; 4]);
+        let mut client = plc.client().await;
+
+        client
+            .write(Area::Merker, S7WriteAccess::bytes(0, 1, &[0xAB, 0xCD]))
+            .await
+            .unwrap();
+        client
+            .write(Area::Merker, S7WriteAccess::bit(0, 3, 0, true))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            client
+                .read(Area::Merker, S7ReadAccess::bytes(0, 0, 4))
+                .await,
+            Ok(vec![0x00, 0xAB, 0xCD, 0x01])
+        );
+        assert_eq!(
+            client.read(Area::Merker, S7ReadAccess::bit(0, 1, 0)).await,
+            Ok(vec![0x01])
+        );
+        assert_eq!(
+            client.read(Area::Merker, S7ReadAccess::bit(0, 1, 8)).await,
+            Err(Error::RequestedBitOutOfRange)
+        );
+        assert_eq!(
+            client
+                .write(Area::PeripheralIO, S7WriteAccess::bytes(0, 0, &[0xFF]))
+                .await,
+            Err(Error::DirectOutputWriteNotAllowed)
+        );
+        assert!(plc.state().requests[1..]
+            .iter()
+            .all(|request| request[20] == 0x83));
+    }
+
+    #[tokio::test]
+    async fn read_multi_is_split_into_requests() {
+        let plc = MockPlc::start().await;
+        plc.state().pdu_length = 240;
+        let data: Vec<u8> = (0..600_u16).map(|i| (i % 251) as u8).collect();
+        plc.set_db(1, &data);
+        let mut client = plc.client().await;
+
+        // 30 items of 10 bytes and one item that needs split requests on its own
+        let mut accesses = (0..30_u32)
+            .map(|i| S7ReadAccess::bytes(1, 10 * i, 10))
+            .collect::<Vec<_>>();
+        accesses.insert(10, S7ReadAccess::bytes(1, 300, 300));
+        let results = client.db_read_multi(&accesses).await.unwrap();
+
+        assert_eq!(results.len(), accesses.len());
+        for (access, result) in accesses.iter().zip(results) {
+            let start = access.start() as usize;
+            assert_eq!(
+                result,
+                Ok(data[start..start + usize::from(access.len())].to_vec())
+            );
+        }
+        assert!(client.requests_issued() > 3);
+    }
+
+    #[tokio::test]
+    async fn close_accesses_are_merged_into_one_item() {
+        let plc = MockPlc::start().await;
+        plc.set_db(
+            1,
+            &[0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0b0000_0100],
+        );
+        plc.set_db(2, &[0xAA, 0xBB]);
+        let mut client = plc.client().await;
+
+        let results = client
+            .db_read_multi_merged(
+                &[
+                    S7ReadAccess::bytes(1, 6, 2),
+                    S7ReadAccess::bytes(2, 0, 2),
+                    S7ReadAccess::bytes(1, 0, 2),
+                    S7ReadAccess::bit(1, 8, 2),
+                    S7ReadAccess::bytes(1, 1, 2),
+                ],
+                4,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            results,
+            [
+                Ok(vec![0x06, 0x07]),
+                Ok(vec![0xAA, 0xBB]),
+                Ok(vec![0x00, 0x01]),
+                Ok(vec![0x01]),
+                Ok(vec![0x01, 0x02]),
+            ]
+        );
+        assert_eq!(client.requests_issued(), 1);
+        // one item for DB1 and one for DB2
+        assert_eq!(plc.state().requests[1][11], 2);
+    }
+
+    #[tokio::test]
+    async fn failed_merged_access_is_read_one_by_one() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00, 0x01, 0x02, 0x03]);
+        let mut client = plc.client().await;
+
+        let results = client
+            .db_read_multi_merged(
+                &[S7ReadAccess::bytes(1, 0, 2), S7ReadAccess::bytes(1, 4, 2)],
+                2,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results[0], Ok(vec![0x00, 0x01]));
+        assert!(results[1].is_err());
+        assert_eq!(client.requests_issued(), 2);
+    }
+
+    #[tokio::test]
+    async fn read_until_bounds_split_read() {
+        let plc = slow_plc().await;
+        let mut client = plc.client().await;
+
+        let started = Instant::now();
+        let result = client
+            .db_read_until(1, 0, 1000, started + Duration::from_millis(250))
+            .await;
+
+        assert_eq!(result, Err(Error::DataExchangeTimedOut));
+        assert!(started.elapsed() < Duration::from_millis(400));
+        assert!(client.is_closed());
+        assert!(matches!(
+            client.db_read(1, 0, 1).await,
+            Err(Error::Connection(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_until_succeeds_before_deadline() {
+        let plc = slow_plc().await;
+        let mut client = plc.client().await;
+
+        let data = client
+            .db_read_until(1, 0, 1000, Instant::now() + Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(data, plc.db(1));
+        assert!(!client.is_closed());
+    }
+
+    #[tokio::test]
+    async fn read_multi_skips_fill_byte_after_odd_length_item() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x11, 0x22, 0x33]);
+        plc.set_db(2, &[0x44, 0x55]);
+        let mut client = plc.client().await;
+
+        let data = client
+            .db_read_multi(&[S7ReadAccess::bytes(1, 0, 3), S7ReadAccess::bytes(2, 0, 2)])
+            .await
+            .unwrap();
+
+        assert_eq!(data, [Ok(vec![0x11, 0x22, 0x33]), Ok(vec![0x44, 0x55])]);
+    }
+
+    #[tokio::test]
+    async fn read_sequence_concatenates_data_blocks() {
+        let plc = MockPlc::start().await;
+        plc.set_db(100, &[1; 16]);
+        plc.set_db(101, &[2; 16]);
+        let mut client = plc.client().await;
+
+        let data = client.db_read_sequence(&[100, 101], 8).await.unwrap();
+
+        assert_eq!(data.len(), 16);
+        assert_eq!(data, [[1; 8], [2; 8]].concat());
+        // both data blocks are read with a single request after the negotiation
+        assert_eq!(plc.state().requests.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn read_sequence_identifies_failing_data_block() {
+        let plc = MockPlc::start().await;
+        plc.set_db(100, &[1; 16]);
+        let mut client = plc.client().await;
+
+        let result = client.db_read_sequence(&[100, 101], 8).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::DataBlockAccess { db_number: 101, .. })
+        ));
+        assert!(!client.is_closed());
+    }
+
+    #[tokio::test]
+    async fn read_typed_map_decodes_values() {
+        let plc = MockPlc::start().await;
+        let mut data = vec![0_u8; 8];
+        data[0] = 0b0000_1000;
+        data[4..8].copy_from_slice(&21.5_f32.to_be_bytes());
+        plc.set_db(100, &data);
+        let mut client = plc.client().await;
+
+        let values = client
+            .read_typed_map(&[
+                ("running", Area::DataBlock, 100, 3, S7DataType::S7BIT),
+                ("temperature", Area::DataBlock, 100, 4, S7DataType::S7REAL),
+                ("missing", Area::DataBlock, 101, 0, S7DataType::S7INT),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(values["running"], Ok(S7Value::Bool(true)));
+        assert_eq!(values["temperature"], Ok(S7Value::Real(21.5)));
+        assert!(values["missing"].is_err());
+        // all values are read with a single request after the negotiation
+        assert_eq!(plc.state().requests.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn read_many_keeps_input_order() {
+        let plc = MockPlc::start().await;
+        for (db_number, delay) in [(1, 200), (2, 0), (3, 100)] {
+            plc.set_db(db_number, &[u8::try_from(db_number).unwrap(); 4]);
+            plc.state()
+                .read_delays
+                .insert(db_number, Duration::from_millis(delay));
+        }
+        let pool = plc.pool(3);
+
+        let started = Instant::now();
+        let results = pool
+            .db_read_many(&[
+                S7ReadAccess::bytes(1, 0, 4),
+                S7ReadAccess::bytes(2, 0, 4),
+                S7ReadAccess::bytes(3, 0, 4),
+                S7ReadAccess::bit(2, 0, 0),
+            ])
+            .await;
+
+        assert_eq!(
+            results,
+            [Ok(vec![1; 4]), Ok(vec![2; 4]), Ok(vec![3; 4]), Ok(vec![0])]
+        );
+        // the slow reads overlap
+        assert!(started.elapsed() < Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn read_real_array_filters_non_finite_values() {
+        let plc = MockPlc::start().await;
+        let data: Vec<u8> = [1.5_f32, f32::NAN, -2.0, f32::INFINITY]
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect();
+        plc.set_db(1, &data);
+        let mut client = plc.client().await;
+
+        let values = client.db_read_real_array(1, 0, 4).await.unwrap();
+        assert_eq!(values[0].to_bits(), 1.5_f32.to_bits());
+        assert!(values[1].is_nan());
+
+        assert_eq!(
+            client.db_read_real_array_filtered(1, 0, 4).await,
+            Ok(vec![Some(1.5), None, Some(-2.0), None])
+        );
+    }
+
+    #[tokio::test]
+    async fn read_multi_raw_exposes_transport_fields() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x01, 0x02, 0x03, 0b0000_0100]);
+        let mut client = plc.client().await;
+
+        let items = client
+            .db_read_multi_raw(&[
+                S7ReadAccess::bytes(1, 0, 3),
+                S7ReadAccess::bit(1, 3, 2),
+                S7ReadAccess::bytes(9, 0, 1),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            items,
+            [
+                Ok(RawDataItem {
+                    error_code: 0xFF,
+                    var_type: 0x04,
+                    count: 24,
+                    data: vec![0x01, 0x02, 0x03],
+                }),
+                Ok(RawDataItem {
+                    error_code: 0xFF,
+                    var_type: 0x03,
+                    count: 1,
+                    data: vec![0x01],
+                }),
+                Ok(RawDataItem {
+                    error_code: 0x0A,
+                    var_type: 0x00,
+                    count: 0,
+                    data: vec![],
+                }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn read_real_strided_extracts_one_channel() {
+        let plc = MockPlc::start().await;
+        let data: Vec<u8> = [1.0_f32, 10.0, 2.0, 20.0, 3.0, 30.0]
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect();
+        plc.set_db(1, &data);
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.db_read_real_strided(1, 4, 8, 3).await,
+            Ok(vec![10.0, 20.0, 30.0])
+        );
+        assert_eq!(
+            client.db_read_real_strided(1, 0, 8, 3).await,
+            Ok(vec![1.0, 2.0, 3.0])
+        );
+        // one read per channel, covering only the bytes up to the last value
+        assert_eq!(client.requests_issued(), 2);
+        let request = plc.state().requests[1].clone();
+        assert_eq!(u16::from_be_bytes([request[16], request[17]]), 20);
+
+        assert!(matches!(
+            client.db_read_real_strided(1, 0, 3, 3).await,
+            Err(Error::Conversion(_))
+        ));
+
+        // a response missing the last value is rejected
+        plc.state().read_length_limit = Some(16);
+        assert!(matches!(
+            client.db_read_real_strided(1, 4, 8, 3).await,
+            Err(Error::Conversion(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_multi_typed_decodes_each_item() {
+        let plc = MockPlc::start().await;
+        let mut data = (-1.25_f32).to_be_bytes().to_vec();
+        data.extend((-300_i16).to_be_bytes());
+        data.push(0b0000_0100);
+        plc.set_db(1, &data);
+        let mut client = plc.client().await;
+
+        let values = client
+            .db_read_multi_typed(&[
+                (S7ReadAccess::bytes(1, 0, 4), S7DataType::S7REAL),
+                (S7ReadAccess::bytes(1, 4, 2), S7DataType::S7INT),
+                (S7ReadAccess::bit(1, 6, 2), S7DataType::S7BIT),
+                (S7ReadAccess::bytes(1, 4, 1), S7DataType::S7WORD),
+                (S7ReadAccess::bytes(2, 0, 2), S7DataType::S7WORD),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(values[0], Ok(S7Value::Real(-1.25)));
+        assert_eq!(values[1], Ok(S7Value::Int(-300)));
+        assert_eq!(values[2], Ok(S7Value::Bool(true)));
+        assert!(matches!(values[3], Err(Error::Conversion(_))));
+        assert!(matches!(values[4], Err(Error::DataItemError(_))));
+        // all items were read with a single request
+        assert_eq!(plc.state().requests.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn read_bits_across_byte_boundaries() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0xFF, 0b1100_0000, 0b1010_0101, 0b0000_0001, 0xFF]);
+        let mut client = plc.client().await;
+
+        let bits = client.db_read_bits(1, 1, 6, 11).await.unwrap();
+
+        assert_eq!(
+            bits,
+            [true, true, true, false, true, false, false, true, false, true, true]
+        );
+        // only the bytes 1 to 3 were read
+        let request = plc.state().requests[1].clone();
+        assert_eq!(u16::from_be_bytes([request[16], request[17]]), 3);
+    }
+
+    #[tokio::test]
+    async fn split_read_is_pipelined() {
+        let plc = MockPlc::start().await;
+        plc.state().pdu_length = 240;
+        plc.state().max_amq_caller = 3;
+        plc.state().response_delay = Duration::from_millis(20);
+        let data = (0..1000_u16).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        plc.set_db(1, &data);
+        let mut client = plc.client().await;
+
+        assert_eq!(client.db_read(1, 0, 1000).await, Ok(data.clone()));
+        // 5 requests, successors are sent before the response of their predecessor
+        assert!(plc.state().pipelined_jobs > 0);
+        assert_eq!(client.requests_issued(), 5);
+
+        plc.state().max_amq_caller = 1;
+        plc.state().pipelined_jobs = 0;
+        let mut client = plc.client().await;
+        assert_eq!(client.db_read(1, 0, 1000).await, Ok(data));
+        assert_eq!(plc.state().pipelined_jobs, 0);
+    }
+
+    #[tokio::test]
+    async fn zero_max_amq_caller_reads_one_at_a_time() {
+        let plc = MockPlc::start().await;
+        plc.state().pdu_length = 240;
+        plc.state().max_amq_caller = 0;
+        plc.state().response_delay = Duration::from_millis(5);
+        let data = (0..1000_u16).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+        plc.set_db(1, &data);
+        let mut client = plc.client().await;
+
+        assert_eq!(client.max_amq_caller(), 1);
+        assert_eq!(client.db_read(1, 0, 1000).await, Ok(data));
+        assert_eq!(client.requests_issued(), 5);
+        assert_eq!(plc.state().pipelined_jobs, 0);
+    }
+
+    #[tokio::test]
+    async fn mismatched_pdu_reference_is_accepted_when_relaxed() {
+        let plc = MockPlc::start().await;
+        plc.state().mismatched_pdu_reference = true;
+        plc.set_db(1, &[0x01, 0x02, 0x03, 0x04]);
+
+        let mut client = plc.client().await;
+        assert_eq!(
+            client.db_read(1, 0, 4).await,
+            Err(Error::ResponseDoesNotBelongToCurrentPDU)
+        );
+
+        let mut client = plc
+            .builder()
+            .relax_pdu_reference_check()
+            .connect()
+            .await
+            .unwrap();
+        assert_eq!(
+            client.db_read(1, 0, 4).await,
+            Ok(vec![0x01, 0x02, 0x03, 0x04])
+        );
+        client.db_write(1, 0, &[0x05]).await.unwrap();
+        assert_eq!(plc.db(1), [0x05, 0x02, 0x03, 0x04]);
+    }
+
+    #[tokio::test]
+    async fn hash_changes_with_data() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x01, 0x02, 0x03, 0x04]);
+        let mut client = plc.client().await;
+
+        let (hash, data) = client.db_read_hash(1, 0, 4).await.unwrap();
+        assert_eq!(data, [0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(client.db_read_hash(1, 0, 4).await.unwrap().0, hash);
+        assert_eq!(client.db_changed_since(1, 0, 4, hash).await, Ok(None));
+
+        plc.set_db(1, &[0x01, 0x02, 0x03, 0x05]);
+        let (new_hash, data) = client
+            .db_changed_since(1, 0, 4, hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(new_hash, hash);
+        assert_eq!(data, [0x01, 0x02, 0x03, 0x05]);
+    }
 }
@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+
+use super::create::S7Client;
+use super::pooled::S7Pool;
+use crate::errors::Error;
+use crate::{Area, S7DataType, S7Value, S7WriteAccess};
+
+/// Set of named parameters stored in a data block, e.g. the settings of a product
+///
+/// Every field consists of its offset in the data block and its value, which also determines the
+/// data type. The offset is given in bytes, except for `S7Value::Bool` where it is the bit
+/// address (`byte * 8 + bit`), like the items of [`S7Client::read_typed_map`].
+///
+/// ```rust
+/// use s7client::{Recipe, S7Value};
+///
+/// let recipe = Recipe::new()
+///     .with("speed", 0, S7Value::Real(12.5))
+///     .with("count", 4, S7Value::Int(250))
+///     .with("enabled", 6 * 8, S7Value::Bool(true));
+/// assert_eq!(recipe.get("count"), Some(&S7Value::Int(250)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[must_use]
+pub struct Recipe {
+    fields: BTreeMap<String, (u32, S7Value)>,
+}
+
+impl Recipe {
+    /// Create an empty recipe
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a field to the recipe, replacing a field of the same name
+    pub fn with(mut self, name: impl Into<String>, offset: u32, value: S7Value) -> Self {
+        self.insert(name, offset, value);
+        self
+    }
+
+    /// Add a field to the recipe, replacing a field of the same name
+    pub fn insert(&mut self, name: impl Into<String>, offset: u32, value: S7Value) {
+        self.fields.insert(name.into(), (offset, value));
+    }
+
+    /// Value of the field with the given name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&S7Value> {
+        self.fields.get(name).map(|(_, value)| value)
+    }
+
+    /// All fields of the recipe with their name, offset and value, ordered by name
+    pub fn fields(&self) -> impl Iterator<Item = (&str, u32, &S7Value)> {
+        self.fields
+            .iter()
+            .map(|(name, (offset, value))| (name.as_str(), *offset, value))
+    }
+
+    /// Number of fields of the recipe
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Whether the recipe has no fields
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Addresses and values of all `BOOL` fields
+    fn bits(&self) -> Vec<(u32, bool)> {
+        self.fields
+            .values()
+            .filter_map(|(address, value)| match value {
+                S7Value::Bool(value) => Some((*address, *value)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Continuous byte ranges covering all fields except `BOOL`s
+    ///
+    /// Fields that touch or overlap are merged into one range, the field with the higher offset wins on overlaps.
+    fn byte_ranges(&self) -> Vec<(u32, Vec<u8>)> {
+        let mut fields = self
+            .fields
+            .values()
+            .filter(|(_, value)| !matches!(value, S7Value::Bool(_)))
+            .map(|(offset, value)| (*offset, value.encode()))
+            .collect::<Vec<_>>();
+        fields.sort_by_key(|(offset, _)| *offset);
+
+        let mut ranges: Vec<(u32, Vec<u8>)> = Vec::new();
+        for (offset, data) in fields {
+            match ranges.last_mut() {
+                #[allow(clippy::cast_possible_truncation)]
+                Some((start, range)) if offset <= *start + range.len() as u32 => {
+                    let position = (offset - *start) as usize;
+                    let end = position + data.len();
+                    if range.len() < end {
+                        range.resize(end, 0);
+                    }
+                    range[position..end].copy_from_slice(&data);
+                }
+                _ => ranges.push((offset, data)),
+            }
+        }
+        ranges
+    }
+}
+
+impl S7Client {
+    /// Write all fields of a recipe into a specified data block
+    ///
+    /// Adjacent fields are combined into continuous writes and all writes are sent with as few
+    /// requests as the PDU size allows. Bytes between fields are not touched.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Recipe, S7Client, S7Types, S7Value};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let recipe = Recipe::new()
+    ///     .with("speed", 0, S7Value::Real(12.5))
+    ///     .with("count", 4, S7Value::Int(250));
+    /// client.apply_recipe(100, &recipe)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing, including the rejection of a single field.
+    pub async fn apply_recipe(&mut self, db_number: u16, recipe: &Recipe) -> Result<(), Error> {
+        let ranges = recipe.byte_ranges();
+        let bits = recipe.bits();
+        let mut accesses = ranges
+            .iter()
+            .map(|(start, data)| S7WriteAccess::bytes(db_number, *start, data))
+            .collect::<Vec<_>>();
+        #[allow(clippy::cast_possible_truncation)]
+        accesses.extend(bits.iter().map(|(address, value)| {
+            S7WriteAccess::bit(db_number, address / 8, (address % 8) as u8, *value)
+        }));
+
+        for result in self.db_write_multi(&accesses).await? {
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Read a recipe with the given layout from a specified data block
+    ///
+    /// Every item of the layout consists of the name, the offset and the data type of a field
+    /// (see [`Recipe`] for the offsets).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7DataType, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let recipe = client.read_recipe(100, &[
+    ///        ("speed", 0, S7DataType::S7REAL),
+    ///        ("count", 4, S7DataType::S7INT),
+    ///    ])
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading, including a field that could not be read.
+    pub async fn read_recipe(
+        &mut self,
+        db_number: u16,
+        layout: &[(&str, u32, S7DataType)],
+    ) -> Result<Recipe, Error> {
+        let items = layout
+            .iter()
+            .map(|(name, offset, data_type)| {
+                (*name, Area::DataBlock, db_number, *offset, *data_type)
+            })
+            .collect::<Vec<_>>();
+        let mut values = self.read_typed_map(&items).await?;
+
+        let mut recipe = Recipe::new();
+        for (name, offset, _) in layout {
+            if let Some(value) = values.remove(name) {
+                recipe.insert(*name, *offset, value?);
+            }
+        }
+        Ok(recipe)
+    }
+}
+
+impl S7Pool {
+    /// Write all fields of a recipe into a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Recipe, S7Pool, S7Types, S7Value};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let recipe = Recipe::new().with("speed", 0, S7Value::Real(12.5));
+    /// pool.apply_recipe(100, &recipe)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing, including the rejection of a single field.
+    pub async fn apply_recipe(&self, db_number: u16, recipe: &Recipe) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.apply_recipe(db_number, recipe).await
+    }
+
+    /// Read a recipe with the given layout from a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7DataType, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let recipe = pool.read_recipe(100, &[("speed", 0, S7DataType::S7REAL)])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading, including a field that could not be read.
+    pub async fn read_recipe(
+        &self,
+        db_number: u16,
+        layout: &[(&str, u32, S7DataType)],
+    ) -> Result<Recipe, Error> {
+        let mut connection = self.0.get().await?;
+        connection.read_recipe(db_number, layout).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    #[test]
+    fn adjacent_fields_are_merged() {
+        let recipe = Recipe::new()
+            .with("count", 4, S7Value::Int(2))
+            .with("speed", 0, S7Value::Real(1.0))
+            .with("mode", 10, S7Value::Byte(3))
+            .with("enabled", 6 * 8 + 1, S7Value::Bool(true));
+
+        assert_eq!(
+            recipe.byte_ranges(),
+            [
+                (0, vec![0x3F, 0x80, 0x00, 0x00, 0x00, 0x02]),
+                (10, vec![0x03])
+            ]
+        );
+        assert_eq!(recipe.bits(), [(49, true)]);
+    }
+
+    #[tokio::test]
+    async fn recipe_round_trip() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0xAA; 8]);
+        let mut client = plc.client().await;
+        let recipe = Recipe::new()
+            .with("speed", 0, S7Value::Real(12.5))
+            .with("count", 4, S7Value::Int(-250))
+            .with("enabled", 6 * 8 + 1, S7Value::Bool(false));
+
+        client.apply_recipe(1, &recipe).await.unwrap();
+
+        // one write request, the unused bits and bytes are untouched
+        assert_eq!(client.requests_issued(), 1);
+        assert_eq!(plc.db(1), [0x41, 0x48, 0x00, 0x00, 0xFF, 0x06, 0xA8, 0xAA]);
+        let layout = [
+            ("speed", 0, S7DataType::S7REAL),
+            ("count", 4, S7DataType::S7INT),
+            ("enabled", 6 * 8 + 1, S7DataType::S7BIT),
+        ];
+        assert_eq!(client.read_recipe(1, &layout).await, Ok(recipe));
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use super::create::S7Client;
+use super::pooled::S7Pool;
+use crate::errors::Error;
+use crate::{Area, S7DataType, S7Value};
+
+/// Fields of a data block as key, offset and data type
+type Layout<K> = Vec<(K, u32, S7DataType)>;
+
+/// Layouts of a data block that changed between versions of the PLC program
+///
+/// The version of the layout is stored as a byte at a fixed offset of the data block, every
+/// version has its own layout of fields. The items of a layout consist of the key, the offset
+/// and the data type of a field, like the items of [`S7Client::read_typed_map`].
+///
+/// ```rust
+/// use s7client::{DbSchema, S7DataType};
+///
+/// let schema = DbSchema::new(0)
+///     .with_version(1, vec![("speed", 2, S7DataType::S7INT)])
+///     .with_version(2, vec![("speed", 2, S7DataType::S7REAL), ("count", 6, S7DataType::S7INT)]);
+/// assert_eq!(schema.layout(1).map(<[_]>::len), Some(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct DbSchema<K> {
+    version_offset: u32,
+    layouts: BTreeMap<u8, Layout<K>>,
+}
+
+impl<K> DbSchema<K> {
+    /// Create a schema without layouts whose version is stored at the given byte offset
+    pub fn new(version_offset: u32) -> Self {
+        Self {
+            version_offset,
+            layouts: BTreeMap::new(),
+        }
+    }
+
+    /// Add the layout of a version, replacing the layout of the same version
+    pub fn with_version(mut self, version: u8, layout: Vec<(K, u32, S7DataType)>) -> Self {
+        self.insert_version(version, layout);
+        self
+    }
+
+    /// Add the layout of a version, replacing the layout of the same version
+    pub fn insert_version(&mut self, version: u8, layout: Vec<(K, u32, S7DataType)>) {
+        self.layouts.insert(version, layout);
+    }
+
+    /// Byte offset of the version in the data block
+    #[must_use]
+    pub fn version_offset(&self) -> u32 {
+        self.version_offset
+    }
+
+    /// Layout of the given version
+    #[must_use]
+    pub fn layout(&self, version: u8) -> Option<&[(K, u32, S7DataType)]> {
+        self.layouts.get(&version).map(Vec::as_slice)
+    }
+
+    /// All versions of the schema in ascending order
+    pub fn versions(&self) -> impl Iterator<Item = u8> + '_ {
+        self.layouts.keys().copied()
+    }
+}
+
+impl S7Client {
+    /// Read the fields of a specified data block with the layout matching the version stored in it
+    ///
+    /// The version byte is read first, then all fields of the matching layout are read with as few
+    /// requests as the PDU size allows. Returns the version together with the values by key.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{DbSchema, S7Client, S7DataType, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let schema = DbSchema::new(0)
+    ///     .with_version(1, vec![("speed", 2, S7DataType::S7INT)])
+    ///     .with_version(2, vec![("speed", 2, S7DataType::S7REAL)]);
+    /// let (version, values) = client.read_versioned(100, &schema)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::UnknownSchemaVersion` if the schema has no layout for the stored version or `Error` if any errors
+    /// occurred during reading, including a field that could not be read.
+    pub async fn read_versioned<K>(
+        &mut self,
+        db_number: u16,
+        schema: &DbSchema<K>,
+    ) -> Result<(u8, HashMap<K, S7Value>), Error>
+    where
+        K: Hash + Eq + Clone,
+    {
+        let version = self.db_read(db_number, schema.version_offset, 1).await?[0];
+        let layout = schema
+            .layout(version)
+            .ok_or(Error::UnknownSchemaVersion { db_number, version })?;
+
+        let items = layout
+            .iter()
+            .map(|(key, offset, data_type)| {
+                (key.clone(), Area::DataBlock, db_number, *offset, *data_type)
+            })
+            .collect::<Vec<_>>();
+        let values = self
+            .read_typed_map(&items)
+            .await?
+            .into_iter()
+            .map(|(key, value)| value.map(|value| (key, value)))
+            .collect::<Result<HashMap<_, _>, Error>>()?;
+
+        Ok((version, values))
+    }
+}
+
+impl S7Pool {
+    /// Read the fields of a specified data block with the layout matching the version stored in it
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{DbSchema, S7Pool, S7DataType, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let schema = DbSchema::new(0).with_version(1, vec![("speed", 2, S7DataType::S7INT)]);
+    /// let (version, values) = pool.read_versioned(100, &schema)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::UnknownSchemaVersion` if the schema has no layout for the stored version or `Error` if any errors
+    /// occurred during reading, including a field that could not be read.
+    pub async fn read_versioned<K>(
+        &self,
+        db_number: u16,
+        schema: &DbSchema<K>,
+    ) -> Result<(u8, HashMap<K, S7Value>), Error>
+    where
+        K: Hash + Eq + Clone,
+    {
+        let mut connection = self.0.get().await?;
+        connection.read_versioned(db_number, schema).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    #[tokio::test]
+    async fn layout_is_chosen_by_version() {
+        let plc = MockPlc::start().await;
+        let schema = DbSchema::new(0)
+            .with_version(1, vec![("speed", 2, S7DataType::S7INT)])
+            .with_version(
+                2,
+                vec![
+                    ("speed", 2, S7DataType::S7REAL),
+                    ("count", 6, S7DataType::S7INT),
+                ],
+            );
+        let mut client = plc.client().await;
+
+        plc.set_db(1, &[0x01, 0x00, 0x05, 0xDC, 0x00, 0x00, 0x00, 0x00]);
+        let (version, values) = client.read_versioned(1, &schema).await.unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(values, HashMap::from([("speed", S7Value::Int(1500))]));
+
+        let mut data = vec![0x02, 0x00];
+        data.extend(1500.5_f32.to_be_bytes());
+        data.extend(7_i16.to_be_bytes());
+        plc.set_db(1, &data);
+        let (version, values) = client.read_versioned(1, &schema).await.unwrap();
+        assert_eq!(version, 2);
+        assert_eq!(
+            values,
+            HashMap::from([("speed", S7Value::Real(1500.5)), ("count", S7Value::Int(7))])
+        );
+
+        plc.set_db(1, &[0x03; 8]);
+        assert_eq!(
+            client.read_versioned(1, &schema).await,
+            Err(Error::UnknownSchemaVersion {
+                db_number: 1,
+                version: 3
+            })
+        );
+    }
+}
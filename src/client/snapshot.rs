@@ -0,0 +1,123 @@
+use super::create::S7Client;
+use super::pooled::S7Pool;
+use crate::errors::Error;
+
+/// Content of a data block at one point in time, captured with [`S7Client::db_snapshot`]
+///
+/// Two snapshots of the same data block can be compared with [`DbSnapshot::diff`] to find out
+/// which bytes changed in between.
+///
+/// ```rust
+/// use s7client::DbSnapshot;
+///
+/// let before = DbSnapshot::new(100, vec![0x00, 0x01, 0x02]);
+/// let after = DbSnapshot::new(100, vec![0x00, 0xFF, 0x02]);
+/// assert_eq!(before.diff(&after), [(1, 0x01, 0xFF)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct DbSnapshot {
+    db_number: u16,
+    data: Vec<u8>,
+}
+
+impl DbSnapshot {
+    /// Create a snapshot from bytes of a data block starting at byte 0
+    pub fn new(db_number: u16, data: Vec<u8>) -> Self {
+        Self { db_number, data }
+    }
+
+    /// Number of the data block
+    #[must_use]
+    pub fn db_number(&self) -> u16 {
+        self.db_number
+    }
+
+    /// Bytes of the data block
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Offset, old and new value of every byte that differs in `other`
+    ///
+    /// Only the bytes present in both snapshots are compared, the offsets are ascending.
+    #[must_use]
+    pub fn diff(&self, other: &DbSnapshot) -> Vec<(u32, u8, u8)> {
+        (0_u32..)
+            .zip(self.data.iter().zip(&other.data))
+            .filter(|(_, (old, new))| old != new)
+            .map(|(offset, (old, new))| (offset, *old, *new))
+            .collect()
+    }
+}
+
+impl S7Client {
+    /// Capture the first `length` bytes of a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let before = client.db_snapshot(100, 400).await?;
+    /// let after = client.db_snapshot(100, 400).await?;
+    /// for (offset, old, new) in before.diff(&after) {
+    ///     println!("DB100.DBB{offset}: {old:#04X} -> {new:#04X}");
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_snapshot(&mut self, db_number: u16, length: u16) -> Result<DbSnapshot, Error> {
+        let data = self.db_read(db_number, 0, length).await?;
+        Ok(DbSnapshot::new(db_number, data))
+    }
+}
+
+impl S7Pool {
+    /// Capture the first `length` bytes of a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let snapshot = pool.db_snapshot(100, 400)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during reading.
+    pub async fn db_snapshot(&self, db_number: u16, length: u16) -> Result<DbSnapshot, Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_snapshot(db_number, length).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::MockPlc;
+
+    #[tokio::test]
+    async fn diff_reports_changed_bytes() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00, 0x11, 0x22, 0x33]);
+        let mut client = plc.client().await;
+
+        let before = client.db_snapshot(1, 4).await.unwrap();
+        client.db_write(1, 2, &[0xAB]).await.unwrap();
+        let after = client.db_snapshot(1, 4).await.unwrap();
+
+        assert_eq!(after.db_number(), 1);
+        assert_eq!(after.data(), [0x00, 0x11, 0xAB, 0x33]);
+        assert_eq!(before.diff(&after), [(2, 0x22, 0xAB)]);
+        assert!(after.diff(&after).is_empty());
+    }
+}
@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+
+use super::create::S7Client;
+use super::pooled::S7Pool;
+use crate::errors::Error;
+use crate::{Area, S7DataType, S7Value};
+
+/// Address of a symbol as `(area, data block number, offset, data type)`
+///
+/// The offset is given in bytes, except for `S7DataType::S7BIT` where it is the bit address
+/// (`byte * 8 + bit`), like the items of [`S7Client::read_typed_map`]. The data block number is 0
+/// for areas other than `Area::DataBlock`.
+pub type SymbolAddress = (Area, u16, u32, S7DataType);
+
+/// Map of symbol names to their addresses in the PLC, e.g. loaded from the PLC tag table of an engineering project
+///
+/// ```rust
+/// use s7client::{Area, S7DataType, SymbolTable};
+///
+/// let mut symbols = SymbolTable::new();
+/// symbols.insert("Motor_Speed", (Area::DataBlock, 10, 4, S7DataType::S7REAL));
+/// assert_eq!(symbols.get("Motor_Speed"), Some((Area::DataBlock, 10, 4, S7DataType::S7REAL)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct SymbolTable {
+    symbols: HashMap<String, SymbolAddress>,
+    // Declared sizes of data blocks in bytes, used to validate addresses before reading
+    db_sizes: HashMap<u16, u32>,
+}
+
+impl SymbolTable {
+    /// Create an empty symbol table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a symbol, replacing a symbol of the same name
+    pub fn insert(&mut self, name: impl Into<String>, address: SymbolAddress) {
+        self.symbols.insert(name.into(), address);
+    }
+
+    /// Address of the symbol with the given name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<SymbolAddress> {
+        self.symbols.get(name).copied()
+    }
+
+    /// Declare the size of a data block in bytes, e.g. as shown by the block properties of the engineering project
+    ///
+    /// Reading a symbol of the data block that does not fit into the declared size fails with
+    /// `Error::AddressOutOfRange` without sending a request, so a data block that shrank after a
+    /// program change is detected before the PLC rejects the access.
+    pub fn set_db_size(&mut self, db_number: u16, size: u32) {
+        self.db_sizes.insert(db_number, size);
+    }
+
+    /// Declared size of a data block in bytes, see [`SymbolTable::set_db_size`]
+    #[must_use]
+    pub fn db_size(&self, db_number: u16) -> Option<u32> {
+        self.db_sizes.get(&db_number).copied()
+    }
+
+    /// Check that the address fits into the declared size of its data block
+    fn check_bounds(
+        &self,
+        (area, db_number, offset, data_type): SymbolAddress,
+    ) -> Result<(), Error> {
+        let Some(size) = self.db_size(db_number).filter(|_| area == Area::DataBlock) else {
+            return Ok(());
+        };
+        let byte = if data_type == S7DataType::S7BIT {
+            offset / 8
+        } else {
+            offset
+        };
+        if u64::from(byte) + u64::from(data_type.size()) > u64::from(size) {
+            return Err(Error::AddressOutOfRange {
+                db_number,
+                offset: byte,
+            });
+        }
+        Ok(())
+    }
+
+    /// Number of symbols in the table
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Whether the table has no symbols
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Load the symbols of a PLC tag table exported as CSV
+    ///
+    /// The first line names the columns, the columns `Name`, `Data Type` and `Logical Address`
+    /// are required and may appear in any order, other columns are ignored. Fields are separated
+    /// by `,` or `;` and may be quoted with `"`. Addresses are written in the S7 notation with or
+    /// without leading `%`, e.g. `%DB10.DBX4.1`, `DB10.DBD0`, `M10.0`, `MW20`, `I0.1`, `QB2`
+    /// or `PIW256` (German mnemonics `E`, `A`, `PE` and `PA` are accepted as well). Supported data
+    /// types are `Bool`, `Byte`, `Char`, `Word`, `Int`, `DWord`, `DInt` and `Real`.
+    ///
+    /// ```rust
+    /// use s7client::{Area, S7DataType, SymbolTable};
+    ///
+    /// let symbols = SymbolTable::from_csv(
+    ///     "Name;Path;Data Type;Logical Address\n\
+    ///      Motor_Speed;Default tag table;Real;%DB10.DBD4\n\
+    ///      Motor_On;Default tag table;Bool;%Q0.1\n",
+    /// )?;
+    /// assert_eq!(symbols.get("Motor_On"), Some((Area::ProcessOutput, 0, 1, S7DataType::S7BIT)));
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if a required column is missing or a line has an unsupported data type
+    /// or an invalid address.
+    pub fn from_csv(csv: &str) -> Result<Self, Error> {
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let (_, header) = lines
+            .next()
+            .ok_or_else(|| Error::Conversion("Symbol table has no header".to_string()))?;
+        let delimiter = if header.contains(';') { ';' } else { ',' };
+        let columns = split_csv_line(header, delimiter);
+        let column = |name: &str| {
+            columns
+                .iter()
+                .position(|column| column.eq_ignore_ascii_case(name))
+                .ok_or_else(|| Error::Conversion(format!("Symbol table has no column \"{name}\"")))
+        };
+        let (name_column, type_column, address_column) = (
+            column("Name")?,
+            column("Data Type")?,
+            column("Logical Address")?,
+        );
+
+        let mut table = Self::new();
+        for (index, line) in lines {
+            let fields = split_csv_line(line, delimiter);
+            let field = |column: usize| fields.get(column).map_or("", String::as_str);
+            let address =
+                parse_symbol(field(type_column), field(address_column)).map_err(|error| {
+                    Error::Conversion(format!("Line {} of symbol table: {error}", index + 1))
+                })?;
+            table.insert(field(name_column), address);
+        }
+        Ok(table)
+    }
+}
+
+/// Fields of a CSV line, without the quotes of quoted fields
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+        .iter()
+        .map(|field| field.trim().to_string())
+        .collect()
+}
+
+/// Address of a symbol from its data type and its address in S7 notation
+fn parse_symbol(data_type: &str, address: &str) -> Result<SymbolAddress, String> {
+    let data_type = match data_type.to_ascii_uppercase().as_str() {
+        "BOOL" => S7DataType::S7BIT,
+        "BYTE" => S7DataType::S7BYTE,
+        "CHAR" => S7DataType::S7CHAR,
+        "WORD" => S7DataType::S7WORD,
+        "INT" => S7DataType::S7INT,
+        "DWORD" => S7DataType::S7DWORD,
+        "DINT" => S7DataType::S7DINT,
+        "REAL" => S7DataType::S7REAL,
+        _ => return Err(format!("Unsupported data type \"{data_type}\"")),
+    };
+    let invalid = || format!("Invalid address \"{address}\"");
+
+    let normalized = address.trim().trim_start_matches('%').to_ascii_uppercase();
+    let (area, db_number, location) = if let Some(rest) = normalized.strip_prefix("DB") {
+        let (db_number, location) = rest.split_once(".DB").ok_or_else(invalid)?;
+        (
+            Area::DataBlock,
+            db_number.parse().map_err(|_| invalid())?,
+            location,
+        )
+    } else {
+        let (area, location) = ["PI", "PE", "PQ", "PA", "I", "E", "Q", "A", "M"]
+            .iter()
+            .find_map(|prefix| normalized.strip_prefix(prefix).map(|rest| (*prefix, rest)))
+            .ok_or_else(invalid)?;
+        let area = match area {
+            "PI" | "PE" | "PQ" | "PA" => Area::PeripheralIO,
+            "I" | "E" => Area::ProcessInput,
+            "Q" | "A" => Area::ProcessOutput,
+            _ => Area::Merker,
+        };
+        (area, 0, location)
+    };
+
+    // size letter matching the data type (missing for bits outside of data blocks), byte and optional bit
+    let (size, location) = match location.chars().next() {
+        Some(letter @ ('X' | 'B' | 'W' | 'D')) => (Some(letter), &location[1..]),
+        _ => (None, location),
+    };
+    match (size, data_type.size()) {
+        (Some('X') | None, 1) if data_type == S7DataType::S7BIT => {}
+        (Some('B'), 1) | (Some('W'), 2) | (Some('D'), 4) if data_type != S7DataType::S7BIT => {}
+        _ => return Err(invalid()),
+    }
+    let (byte, bit) = match location.split_once('.') {
+        Some((byte, bit)) => (byte, Some(bit)),
+        None => (location, None),
+    };
+    let byte: u32 = byte.parse().map_err(|_| invalid())?;
+    let offset = match (data_type, bit) {
+        (S7DataType::S7BIT, Some(bit)) => match bit.parse::<u32>() {
+            Ok(bit) if bit < 8 => byte * 8 + bit,
+            _ => return Err(invalid()),
+        },
+        (S7DataType::S7BIT, None) | (_, Some(_)) => return Err(invalid()),
+        (_, None) => byte,
+    };
+    Ok((area, db_number, offset, data_type))
+}
+
+impl S7Client {
+    /// Read the value of a symbol of a symbol table
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types, SymbolTable};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let symbols = SymbolTable::from_csv(&std::fs::read_to_string("PLCTags.csv")?)?;
+    /// let speed = client.read_symbol(&symbols, "Motor_Speed")
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::UnknownSymbol` if the symbol is not in the table, `Error::AddressOutOfRange` if it does not fit into
+    /// the declared size of its data block or `Error` if any errors occurred during reading.
+    pub async fn read_symbol(
+        &mut self,
+        symbols: &SymbolTable,
+        name: &str,
+    ) -> Result<S7Value, Error> {
+        let (area, db_number, offset, data_type) = symbols
+            .get(name)
+            .ok_or_else(|| Error::UnknownSymbol(name.to_string()))?;
+        symbols.check_bounds((area, db_number, offset, data_type))?;
+
+        self.read_typed_map(&[(name, area, db_number, offset, data_type)])
+            .await?
+            .remove(name)
+            .ok_or(Error::RequestNotAcknowledged)?
+    }
+}
+
+impl S7Pool {
+    /// Read the value of a symbol of a symbol table
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types, SymbolTable};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let symbols = SymbolTable::from_csv(&std::fs::read_to_string("PLCTags.csv")?)?;
+    /// let speed = pool.read_symbol(&symbols, "Motor_Speed")
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::UnknownSymbol` if the symbol is not in the table, `Error::AddressOutOfRange` if it does not fit into
+    /// the declared size of its data block or `Error` if any errors occurred during reading.
+    pub async fn read_symbol(&self, symbols: &SymbolTable, name: &str) -> Result<S7Value, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.read_symbol(symbols, name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    const TAG_TABLE: &str = "Name,Path,Data Type,Logical Address,Comment\n\
+        Motor_Speed,Default tag table,Real,%DB10.DBD4,\"Speed in rpm, measured\"\n\
+        Motor_On,Default tag table,Bool,%DB10.DBX8.3,\n\
+        Mode,Default tag table,Int,%MW20,\n\
+        Start_Button,Default tag table,Bool,%I0.1,\n";
+
+    #[test]
+    fn parse_tag_table() {
+        let symbols = SymbolTable::from_csv(TAG_TABLE).unwrap();
+
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(
+            symbols.get("Motor_Speed"),
+            Some((Area::DataBlock, 10, 4, S7DataType::S7REAL))
+        );
+        assert_eq!(
+            symbols.get("Motor_On"),
+            Some((Area::DataBlock, 10, 67, S7DataType::S7BIT))
+        );
+        assert_eq!(
+            symbols.get("Mode"),
+            Some((Area::Merker, 0, 20, S7DataType::S7INT))
+        );
+        assert_eq!(
+            symbols.get("Start_Button"),
+            Some((Area::ProcessInput, 0, 1, S7DataType::S7BIT))
+        );
+    }
+
+    #[test]
+    fn parse_addresses() {
+        assert_eq!(
+            parse_symbol("Word", "pew256"),
+            Ok((Area::PeripheralIO, 0, 256, S7DataType::S7WORD))
+        );
+        assert_eq!(
+            parse_symbol("BOOL", "A1.7"),
+            Ok((Area::ProcessOutput, 0, 15, S7DataType::S7BIT))
+        );
+        assert!(parse_symbol("Bool", "M10.8").is_err());
+        assert!(parse_symbol("Bool", "MB10").is_err());
+        assert!(parse_symbol("Int", "DB1.DBW2.1").is_err());
+        assert!(parse_symbol("Int", "T5").is_err());
+        assert!(parse_symbol("Int", "MD4").is_err());
+        assert!(parse_symbol("String", "DB1.DBB0").is_err());
+
+        assert!(matches!(
+            SymbolTable::from_csv("Name;Data Type\nSpeed;Real\n"),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            SymbolTable::from_csv("Name;Data Type;Logical Address\nSpeed;Real;%DB1.DBX0\n"),
+            Err(Error::Conversion(message)) if message.starts_with("Line 2")
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_value_of_symbol() {
+        let plc = MockPlc::start().await;
+        let mut data = vec![0x00; 9];
+        data[4..8].copy_from_slice(&1450.5_f32.to_be_bytes());
+        data[8] = 0b0000_1000;
+        plc.set_db(10, &data);
+        let symbols = SymbolTable::from_csv(TAG_TABLE).unwrap();
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.read_symbol(&symbols, "Motor_Speed").await,
+            Ok(S7Value::Real(1450.5))
+        );
+        assert_eq!(
+            client.read_symbol(&symbols, "Motor_On").await,
+            Ok(S7Value::Bool(true))
+        );
+        assert_eq!(
+            client.read_symbol(&symbols, "Motor_Off").await,
+            Err(Error::UnknownSymbol("Motor_Off".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn address_beyond_declared_db_size_is_rejected() {
+        let plc = MockPlc::start().await;
+        plc.set_db(10, &[0x00; 9]);
+        let mut symbols = SymbolTable::from_csv(TAG_TABLE).unwrap();
+        // the data block shrank to 6 bytes, the REAL at byte 4 does not fit anymore
+        symbols.set_db_size(10, 6);
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.read_symbol(&symbols, "Motor_Speed").await,
+            Err(Error::AddressOutOfRange {
+                db_number: 10,
+                offset: 4
+            })
+        );
+        assert_eq!(
+            client.read_symbol(&symbols, "Motor_On").await,
+            Err(Error::AddressOutOfRange {
+                db_number: 10,
+                offset: 8
+            })
+        );
+        // nothing but the negotiation was sent
+        assert_eq!(plc.state().requests.len(), 1);
+
+        symbols.set_db_size(10, 9);
+        assert_eq!(
+            client.read_symbol(&symbols, "Motor_Speed").await,
+            Ok(S7Value::Real(0.0))
+        );
+    }
+}
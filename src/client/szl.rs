@@ -0,0 +1,1045 @@
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+use super::create::S7Client;
+use super::pooled::S7Pool;
+use crate::errors::Error;
+use crate::s7_protocol::user_data::{read_szl, SzlList};
+
+// SZL partial list with the Ethernet details of the module
+const SZL_ID_ETHERNET_DETAILS: u16 = 0x0037;
+// SZL partial list with the limits of all block types
+const SZL_ID_BLOCK_TYPES: u16 = 0x0015;
+// block type of data blocks in the records of the block types
+const BLOCK_TYPE_DB: u16 = 0x0A00;
+// SZL partial lists with the identification of the module and of its components
+const SZL_ID_MODULE_IDENTIFICATION: u16 = 0x0011;
+const SZL_ID_COMPONENT_IDENTIFICATION: u16 = 0x001C;
+// indices of the records with the order number of the module and its firmware version
+const INDEX_MODULE: u16 = 0x0001;
+const INDEX_FIRMWARE: u16 = 0x0007;
+// index of the record with the serial number of the module
+const INDEX_SERIAL_NUMBER: u16 = 0x0005;
+// SZL partial list with the current operating mode of the CPU
+const SZL_ID_CPU_STATUS: u16 = 0x0424;
+// SZL partial list with the communication status data and the index of its general record
+const SZL_ID_COMMUNICATION_STATUS: u16 = 0x0132;
+const INDEX_COMMUNICATION_GENERAL: u16 = 0x0001;
+const INDEX_TIME_SYSTEM: u16 = 0x0008;
+// bits of the time status of the time system record
+const TIME_STATUS_SYNC_FAILURE: u16 = 0x0001;
+const TIME_STATUS_CLOCK_STOPPED: u16 = 0x0002;
+// offset of the time status in the time system record, after the 8 run-time meters
+const TIME_STATUS_OFFSET: usize = 40;
+// SZL partial list with the startup parameters of the CPU and the index of its record
+const SZL_ID_STARTUP_PARAMETERS: u16 = 0x0131;
+const INDEX_STARTUP_PARAMETERS: u16 = 0x0009;
+// offset of the scan cycle monitoring time (ms) in the startup parameter record, after the index
+const WATCHDOG_TIME_OFFSET: usize = 2;
+
+/// IP parameters of the Ethernet interface of a PLC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpConfig {
+    /// IP address of the interface
+    pub ip: Ipv4Addr,
+    /// Subnet mask of the interface
+    pub subnet: Ipv4Addr,
+    /// Default router, `None` if no router is configured
+    pub gateway: Option<Ipv4Addr>,
+}
+
+impl IpConfig {
+    /// Parse a record of the SZL partial list `0x0037`
+    ///
+    /// The record starts with the logical address of the interface (2 bytes), followed by the
+    /// IP address, the subnet mask and the default router (4 bytes each).
+    fn from_szl_record(record: &[u8]) -> Result<Self, Error> {
+        if record.len() < 14 {
+            return Err(Error::TryFrom(
+                record.to_vec(),
+                "Invalid length for IP configuration record".to_string(),
+            ));
+        }
+        let address = |offset: usize| {
+            Ipv4Addr::new(
+                record[offset],
+                record[offset + 1],
+                record[offset + 2],
+                record[offset + 3],
+            )
+        };
+        let gateway = address(10);
+
+        Ok(Self {
+            ip: address(2),
+            subnet: address(6),
+            gateway: if gateway.is_unspecified() {
+                None
+            } else {
+                Some(gateway)
+            },
+        })
+    }
+}
+
+/// Limits of the data blocks supported by a CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DbLimits {
+    /// Maximum number of data blocks, which is the highest usable data block number
+    pub max_db_number: u16,
+    /// Maximum size of a single data block in the work memory (bytes)
+    pub max_db_size: u32,
+}
+
+impl DbLimits {
+    /// Parse the record for data blocks of the SZL partial list `0x0015`
+    ///
+    /// Each record starts with the block type (2 bytes), followed by the maximum number of blocks,
+    /// the maximum total size of the loaded blocks in KB (2 bytes each) and the maximum size of a
+    /// block in the work memory (4 bytes).
+    fn from_szl_record(record: &[u8]) -> Result<Self, Error> {
+        if record.len() < 10 {
+            return Err(Error::TryFrom(
+                record.to_vec(),
+                "Invalid length for block type record".to_string(),
+            ));
+        }
+        Ok(Self {
+            max_db_number: u16::from_be_bytes([record[2], record[3]]),
+            max_db_size: u32::from_be_bytes([record[6], record[7], record[8], record[9]]),
+        })
+    }
+}
+
+/// Identification of a CPU module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpuInfo {
+    /// Order number of the module (MLFB), e.g. `6ES7 214-1AG40-0XB0`
+    pub order_code: Option<String>,
+    /// Serial number of the module
+    pub serial_number: Option<String>,
+    /// Version of the firmware, e.g. `V4.5.2`
+    pub firmware_version: Option<String>,
+}
+
+impl CpuInfo {
+    /// Collect the identification from the records of the SZL partial lists `0x0011` and `0x001C`
+    ///
+    /// Records of `0x0011` start with the index (2 bytes), followed by the order number (20
+    /// characters) and the module type, the version `'V'` with the major version and the minor
+    /// and patch version (2 bytes each). Records of `0x001C` start with the index, followed by
+    /// 32 characters. Records that are missing or too short are skipped.
+    fn from_szl_records<'a>(
+        module_records: impl Iterator<Item = &'a [u8]>,
+        component_records: impl Iterator<Item = &'a [u8]>,
+    ) -> Self {
+        let mut info = Self {
+            order_code: None,
+            serial_number: None,
+            firmware_version: None,
+        };
+
+        for record in module_records.filter(|record| record.len() >= 28) {
+            match u16::from_be_bytes([record[0], record[1]]) {
+                INDEX_MODULE => info.order_code = szl_text(&record[2..22]),
+                INDEX_FIRMWARE if record[24] == b'V' => {
+                    info.firmware_version =
+                        Some(format!("V{}.{}.{}", record[25], record[26], record[27]));
+                }
+                _ => {}
+            }
+        }
+        for record in component_records.filter(|record| record.len() >= 34) {
+            if u16::from_be_bytes([record[0], record[1]]) == INDEX_SERIAL_NUMBER {
+                info.serial_number = szl_text(&record[2..34]);
+            }
+        }
+
+        info
+    }
+}
+
+/// Operating mode of a CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CpuStatus {
+    /// The CPU executes the user program
+    Run,
+    /// The CPU does not execute the user program
+    Stop,
+    /// The CPU executes the startup after a complete restart, cold restart or restart
+    StartUp,
+    /// The CPU reported no or an unknown operating mode, e.g. `HOLD` or `DEFECT`
+    Unknown,
+}
+
+impl CpuStatus {
+    /// Parse the record of the SZL partial list `0x0424`
+    ///
+    /// The record starts with the event ID (2 bytes) and the affected area (1 byte), followed by
+    /// a byte with the previous operating mode in its upper and the current operating mode in its
+    /// lower 4 bits.
+    fn from_szl_record(record: &[u8]) -> Self {
+        match record.get(3).map(|mode| mode & 0x0F) {
+            Some(0x01..=0x04) => Self::Stop,
+            Some(0x05..=0x07) => Self::StartUp,
+            Some(0x08) => Self::Run,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Type of the connections of a [`SessionInfo`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionKind {
+    /// Programming device connections, e.g. of the engineering software
+    Programming,
+    /// Operator panel connections, e.g. of HMIs and of clients like this one
+    OperatorPanel,
+    /// Connections configured in the hardware configuration of the CPU
+    Configured,
+}
+
+/// Connection resources of one type of connections of a CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionInfo {
+    /// Type of the connections
+    pub kind: SessionKind,
+    /// Number of connections currently established
+    pub active: u16,
+    /// Number of connections reserved for this type (guaranteed or configured)
+    pub reserved: u16,
+}
+
+impl SessionInfo {
+    /// Parse the general record (index `0x0001`) of the SZL partial list `0x0132`
+    ///
+    /// The record starts with the index, followed by the reserved number of programming device
+    /// and operator panel connections, the number of established programming device and operator
+    /// panel connections, the number of configured connections and the number of established
+    /// configured connections (2 bytes each).
+    fn from_szl_record(record: &[u8]) -> Result<Vec<Self>, Error> {
+        if record.len() < 14 {
+            return Err(Error::TryFrom(
+                record.to_vec(),
+                "Invalid length for communication status record".to_string(),
+            ));
+        }
+        let word = |offset: usize| u16::from_be_bytes([record[offset], record[offset + 1]]);
+
+        Ok(vec![
+            Self {
+                kind: SessionKind::Programming,
+                active: word(6),
+                reserved: word(2),
+            },
+            Self {
+                kind: SessionKind::OperatorPanel,
+                active: word(8),
+                reserved: word(4),
+            },
+            Self {
+                kind: SessionKind::Configured,
+                active: word(12),
+                reserved: word(10),
+            },
+        ])
+    }
+}
+
+/// State of the real-time clock of a CPU, read by [`S7Client::read_clock_status`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockStatus {
+    /// Whether the clock is running
+    pub running: bool,
+    /// Whether the clock is synchronized, i.e. no synchronization failure is reported
+    pub synchronized: bool,
+    /// Correction factor applied to the clock
+    pub correction_factor: i16,
+    /// Time status word as reported by the CPU
+    pub status: u16,
+}
+
+impl ClockStatus {
+    /// Parse the time system record (index `0x0008`) of the SZL partial list `0x0132`
+    ///
+    /// The record starts with the index, followed by the cycle of the synchronization frames, the
+    /// correction factor (2 bytes each), 8 run-time meters (4 bytes each), the overflow bits of the
+    /// run-time meters and the time status (2 bytes each).
+    fn from_szl_record(record: &[u8]) -> Result<Self, Error> {
+        if record.len() < TIME_STATUS_OFFSET + 2 {
+            return Err(Error::TryFrom(
+                record.to_vec(),
+                "Invalid length for time system record".to_string(),
+            ));
+        }
+        let status =
+            u16::from_be_bytes([record[TIME_STATUS_OFFSET], record[TIME_STATUS_OFFSET + 1]]);
+
+        Ok(Self {
+            running: status & TIME_STATUS_CLOCK_STOPPED == 0,
+            synchronized: status & TIME_STATUS_SYNC_FAILURE == 0,
+            correction_factor: i16::from_be_bytes([record[4], record[5]]),
+            status,
+        })
+    }
+}
+
+/// Parse the scan cycle monitoring time of a record of the startup parameters
+fn watchdog_time_from_szl_record(record: &[u8]) -> Result<Duration, Error> {
+    match record.get(WATCHDOG_TIME_OFFSET..WATCHDOG_TIME_OFFSET + 2) {
+        Some(time) => Ok(Duration::from_millis(u64::from(u16::from_be_bytes([
+            time[0], time[1],
+        ])))),
+        None => Err(Error::TryFrom(
+            record.to_vec(),
+            "Invalid length for startup parameter record".to_string(),
+        )),
+    }
+}
+
+/// Fixed-width text of an SZL record without padding, `None` if the field is empty
+fn szl_text(field: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(field);
+    let text = text.trim_end_matches(['\0', ' ']).trim_start();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+impl S7Client {
+    /// Read the IP parameters of the Ethernet interface of the PLC
+    ///
+    /// The parameters are read from the system status list (SZL) of the module.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let ip_config = client.read_ip_config()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not expose its IP parameters or `Error`
+    /// if any errors occurred during reading.
+    pub async fn read_ip_config(&mut self) -> Result<IpConfig, Error> {
+        self.validate_connection_info().await?;
+        match read_szl(self, SZL_ID_ETHERNET_DETAILS, 0x0000).await {
+            Ok(list) => list
+                .records()
+                .next()
+                .ok_or(Error::FunctionNotSupported)
+                .and_then(IpConfig::from_szl_record),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read the order number, serial number and firmware version of the CPU, e.g. for an asset inventory
+    ///
+    /// The identification is read from the system status list (SZL). Fields the CPU does not
+    /// report are `None`, e.g. the serial number of older CPUs.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let info = client.cpu_info()
+    ///     .await?;
+    /// println!("{:?} ({:?})", info.order_code, info.firmware_version);
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not identify itself at all or `Error`
+    /// if any errors occurred during reading.
+    pub async fn cpu_info(&mut self) -> Result<CpuInfo, Error> {
+        self.validate_connection_info().await?;
+        let modules = self.read_optional_szl(SZL_ID_MODULE_IDENTIFICATION).await?;
+        let components = self
+            .read_optional_szl(SZL_ID_COMPONENT_IDENTIFICATION)
+            .await?;
+        if modules.is_none() && components.is_none() {
+            return Err(Error::FunctionNotSupported);
+        }
+
+        Ok(CpuInfo::from_szl_records(
+            modules.iter().flat_map(SzlList::records),
+            components.iter().flat_map(SzlList::records),
+        ))
+    }
+
+    /// Read all records of a partial list of the SZL, `None` if the PLC does not provide it
+    async fn read_optional_szl(&mut self, szl_id: u16) -> Result<Option<SzlList>, Error> {
+        match read_szl(self, szl_id, 0x0000).await {
+            Ok(list) => Ok(Some(list)),
+            Err(Error::FunctionNotSupported) => Ok(None),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read the current operating mode of the CPU, e.g. to alarm on unexpected STOP transitions
+    ///
+    /// The mode is read from the system status list (SZL). Modes other than RUN, STOP and
+    /// STARTUP as well as missing records are reported as `CpuStatus::Unknown`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{CpuStatus, S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// if client.cpu_status().await? != CpuStatus::Run {
+    ///     println!("CPU is not in RUN");
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not report its operating mode or `Error`
+    /// if any errors occurred during reading.
+    pub async fn cpu_status(&mut self) -> Result<CpuStatus, Error> {
+        self.validate_connection_info().await?;
+        match read_szl(self, SZL_ID_CPU_STATUS, 0x0000).await {
+            Ok(list) => Ok(list
+                .records()
+                .next()
+                .map_or(CpuStatus::Unknown, CpuStatus::from_szl_record)),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read the maximum number and size of data blocks the CPU supports
+    ///
+    /// The limits are read from the block types of the system status list (SZL), e.g. to validate
+    /// address maps against the capabilities of the hardware.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300).await?;
+    /// let limits = client.read_db_limits()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not report its block limits or `Error`
+    /// if any errors occurred during reading.
+    pub async fn read_db_limits(&mut self) -> Result<DbLimits, Error> {
+        self.validate_connection_info().await?;
+        match read_szl(self, SZL_ID_BLOCK_TYPES, 0x0000).await {
+            Ok(list) => list
+                .records()
+                .find(|record| record.starts_with(&BLOCK_TYPE_DB.to_be_bytes()))
+                .ok_or(Error::FunctionNotSupported)
+                .and_then(DbLimits::from_szl_record),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read the number of established connections of the CPU per type of connection
+    ///
+    /// The numbers are read from the communication status data of the system status list (SZL),
+    /// e.g. to find out why the CPU rejects further connections. The CPU only reports the number
+    /// of connections per type, not their endpoints.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300).await?;
+    /// for session in client.list_sessions().await? {
+    ///     println!("{:?}: {} of {}", session.kind, session.active, session.reserved);
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not report its communication status or `Error`
+    /// if any errors occurred during reading.
+    pub async fn list_sessions(&mut self) -> Result<Vec<SessionInfo>, Error> {
+        self.validate_connection_info().await?;
+        match read_szl(
+            self,
+            SZL_ID_COMMUNICATION_STATUS,
+            INDEX_COMMUNICATION_GENERAL,
+        )
+        .await
+        {
+            Ok(list) => list
+                .records()
+                .next()
+                .ok_or(Error::FunctionNotSupported)
+                .and_then(SessionInfo::from_szl_record),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read the state of the real-time clock of the CPU
+    ///
+    /// The state is read from the time system status of the system status list (SZL), e.g. to
+    /// check whether the clock can be trusted before using [`get_plc_clock`](S7Client::get_plc_clock).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300).await?;
+    /// let status = client.read_clock_status()
+    ///     .await?;
+    /// if status.running && status.synchronized {
+    ///     let clock = client.get_plc_clock().await?;
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not report the state of its clock or `Error`
+    /// if any errors occurred during reading.
+    pub async fn read_clock_status(&mut self) -> Result<ClockStatus, Error> {
+        self.validate_connection_info().await?;
+        match read_szl(self, SZL_ID_COMMUNICATION_STATUS, INDEX_TIME_SYSTEM).await {
+            Ok(list) => list
+                .records()
+                .next()
+                .ok_or(Error::FunctionNotSupported)
+                .and_then(ClockStatus::from_szl_record),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Read the configured scan cycle monitoring time (watchdog) of the CPU
+    ///
+    /// The CPU goes to STOP or calls the time error OB if a cycle takes longer, so it limits the
+    /// cycle time a program may reach.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300).await?;
+    /// let watchdog = client.read_watchdog_time()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not report its startup parameters or `Error`
+    /// if any errors occurred during reading.
+    pub async fn read_watchdog_time(&mut self) -> Result<Duration, Error> {
+        self.validate_connection_info().await?;
+        match read_szl(self, SZL_ID_STARTUP_PARAMETERS, INDEX_STARTUP_PARAMETERS).await {
+            Ok(list) => list
+                .records()
+                .next()
+                .ok_or(Error::FunctionNotSupported)
+                .and_then(watchdog_time_from_szl_record),
+            Err(error) => {
+                if error.is_connection_error() {
+                    self.set_closed();
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+impl S7Pool {
+    /// Read the IP parameters of the Ethernet interface of the PLC
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let ip_config = pool.read_ip_config()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not expose its IP parameters or `Error`
+    /// if any errors occurred during reading.
+    pub async fn read_ip_config(&self) -> Result<IpConfig, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.read_ip_config().await
+    }
+
+    /// Read the order number, serial number and firmware version of the CPU
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let info = pool.cpu_info()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not identify itself at all or `Error`
+    /// if any errors occurred during reading.
+    pub async fn cpu_info(&self) -> Result<CpuInfo, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.cpu_info().await
+    }
+
+    /// Read the current operating mode of the CPU
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let status = pool.cpu_status()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not report its operating mode or `Error`
+    /// if any errors occurred during reading.
+    pub async fn cpu_status(&self) -> Result<CpuStatus, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.cpu_status().await
+    }
+
+    /// Read the maximum number and size of data blocks the CPU supports
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// let limits = pool.read_db_limits()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not report its block limits or `Error`
+    /// if any errors occurred during reading.
+    pub async fn read_db_limits(&self) -> Result<DbLimits, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.read_db_limits().await
+    }
+
+    /// Read the number of established connections of the CPU per type of connection
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// let sessions = pool.list_sessions()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not report its communication status or `Error`
+    /// if any errors occurred during reading.
+    pub async fn list_sessions(&self) -> Result<Vec<SessionInfo>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.list_sessions().await
+    }
+
+    /// Read the state of the real-time clock of the CPU
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// let status = pool.read_clock_status()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not report the state of its clock or `Error`
+    /// if any errors occurred during reading.
+    pub async fn read_clock_status(&self) -> Result<ClockStatus, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.read_clock_status().await
+    }
+
+    /// Read the configured scan cycle monitoring time (watchdog) of the CPU
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// let watchdog = pool.read_watchdog_time()
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::FunctionNotSupported` if the PLC does not report its startup parameters or `Error`
+    /// if any errors occurred during reading.
+    pub async fn read_watchdog_time(&self) -> Result<Duration, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection.read_watchdog_time().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    // record of the SZL partial list 0x0037 as returned by a CPU 1214C
+    const RECORDED_RECORD: [u8; 48] = [
+        0x00, 0x40, 0xC0, 0xA8, 0x0A, 0x48, 0xFF, 0xFF, 0xFF, 0x00, 0xC0, 0xA8, 0x0A, 0x01, 0x28,
+        0x63, 0x36, 0x8A, 0x1B, 0x2C, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn parse_recorded_ip_config() {
+        let config = IpConfig::from_szl_record(&RECORDED_RECORD).unwrap();
+
+        assert_eq!(config.ip, Ipv4Addr::new(192, 168, 10, 72));
+        assert_eq!(config.subnet, Ipv4Addr::new(255, 255, 255, 0));
+        assert_eq!(config.gateway, Some(Ipv4Addr::new(192, 168, 10, 1)));
+
+        let mut record = RECORDED_RECORD;
+        record[10..14].fill(0);
+        assert_eq!(IpConfig::from_szl_record(&record).unwrap().gateway, None);
+    }
+
+    #[tokio::test]
+    async fn read_ip_config_from_szl() {
+        let plc = MockPlc::start().await;
+        plc.set_szl(SZL_ID_ETHERNET_DETAILS, 0x0000, 48, &RECORDED_RECORD);
+        let mut client = plc.client().await;
+
+        let config = client.read_ip_config().await.unwrap();
+
+        assert_eq!(config.ip, Ipv4Addr::new(192, 168, 10, 72));
+    }
+
+    // records of the SZL partial list 0x0015 for OBs, DBs, SDBs, FCs and FBs
+    const RECORDED_BLOCK_TYPES: [u8; 50] = [
+        0x08, 0x00, 0x00, 0x20, 0x00, 0x40, 0x00, 0x00, 0x40, 0x00, 0x0A, 0x00, 0x04, 0x00, 0x00,
+        0x40, 0x00, 0x00, 0x40, 0x00, 0x0B, 0x00, 0x04, 0x00, 0x00, 0x40, 0x00, 0x00, 0x40, 0x00,
+        0x0C, 0x00, 0x08, 0x00, 0x00, 0x40, 0x00, 0x00, 0x40, 0x00, 0x0E, 0x00, 0x08, 0x00, 0x00,
+        0x40, 0x00, 0x00, 0x40, 0x00,
+    ];
+
+    #[tokio::test]
+    async fn read_db_limits_from_block_types() {
+        let plc = MockPlc::start().await;
+        plc.set_szl(SZL_ID_BLOCK_TYPES, 0x0000, 10, &RECORDED_BLOCK_TYPES);
+        let mut client = plc.client().await;
+
+        let limits = client.read_db_limits().await.unwrap();
+
+        assert_eq!(limits.max_db_number, 1024);
+        assert_eq!(limits.max_db_size, 16384);
+    }
+
+    #[tokio::test]
+    async fn read_db_limits_not_supported() {
+        let plc = MockPlc::start().await;
+        plc.set_szl(SZL_ID_BLOCK_TYPES, 0x0000, 10, &RECORDED_BLOCK_TYPES[..10]);
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.read_db_limits().await,
+            Err(Error::FunctionNotSupported)
+        );
+    }
+
+    fn module_record(index: u16, order_code: &[u8; 20], version: [u8; 4]) -> Vec<u8> {
+        let mut record = index.to_be_bytes().to_vec();
+        record.extend(order_code);
+        record.extend([0x00, 0x00]);
+        record.extend(version);
+        record
+    }
+
+    #[tokio::test]
+    async fn read_cpu_info_from_szl() {
+        let plc = MockPlc::start().await;
+        let mut modules = module_record(0x0001, b"6ES7 214-1AG40-0XB0 ", [0x00, 0x00, 0x00, 0x04]);
+        modules.extend(module_record(
+            0x0006,
+            b"6ES7 214-1AG40-0XB0 ",
+            [0x00, 0x00, 0x00, 0x04],
+        ));
+        modules.extend(module_record(0x0007, &[b' '; 20], [b'V', 4, 5, 2]));
+        plc.set_szl(SZL_ID_MODULE_IDENTIFICATION, 0x0000, 28, &modules);
+        let mut component = 0x0005_u16.to_be_bytes().to_vec();
+        component.extend(b"S C-X4U421302016");
+        component.extend([0x00; 16]);
+        plc.set_szl(SZL_ID_COMPONENT_IDENTIFICATION, 0x0000, 34, &component);
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.cpu_info().await,
+            Ok(CpuInfo {
+                order_code: Some("6ES7 214-1AG40-0XB0".to_string()),
+                serial_number: Some("S C-X4U421302016".to_string()),
+                firmware_version: Some("V4.5.2".to_string()),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn cpu_info_skips_absent_and_short_records() {
+        let plc = MockPlc::start().await;
+        let mut modules = module_record(0x0001, b"6ES7 315-2EH14-0AB0 ", [0x00, 0x00, 0x00, 0x01]);
+        // truncated firmware record
+        modules.extend(&module_record(0x0007, &[b' '; 20], [b'V', 3, 2, 6])[..26]);
+        plc.set_szl(SZL_ID_MODULE_IDENTIFICATION, 0x0000, 28, &modules[..54]);
+        let mut client = plc.client().await;
+
+        let info = client.cpu_info().await.unwrap();
+
+        assert_eq!(info.order_code.as_deref(), Some("6ES7 315-2EH14-0AB0"));
+        assert_eq!(info.firmware_version, None);
+        assert_eq!(info.serial_number, None);
+
+        let plc = MockPlc::start().await;
+        let mut client = plc.client().await;
+        assert_eq!(client.cpu_info().await, Err(Error::FunctionNotSupported));
+    }
+
+    #[test]
+    fn parse_cpu_status() {
+        let record = |mode: u8| [0x43, 0x02, 0xFF, mode, 0x00, 0x00];
+
+        assert_eq!(CpuStatus::from_szl_record(&record(0x38)), CpuStatus::Run);
+        assert_eq!(CpuStatus::from_szl_record(&record(0x84)), CpuStatus::Stop);
+        assert_eq!(
+            CpuStatus::from_szl_record(&record(0x45)),
+            CpuStatus::StartUp
+        );
+        assert_eq!(
+            CpuStatus::from_szl_record(&record(0x8A)),
+            CpuStatus::Unknown
+        );
+        assert_eq!(
+            CpuStatus::from_szl_record(&record(0x00)),
+            CpuStatus::Unknown
+        );
+        assert_eq!(CpuStatus::from_szl_record(&[0x43]), CpuStatus::Unknown);
+    }
+
+    #[tokio::test]
+    async fn read_cpu_status_from_szl() {
+        let plc = MockPlc::start().await;
+        let mut record = [0x00; 20];
+        record[..4].copy_from_slice(&[0x43, 0x02, 0xFF, 0x84]);
+        plc.set_szl(SZL_ID_CPU_STATUS, 0x0000, 20, &record);
+        let mut client = plc.client().await;
+
+        assert_eq!(client.cpu_status().await, Ok(CpuStatus::Stop));
+
+        plc.set_szl(SZL_ID_CPU_STATUS, 0x0000, 20, &[]);
+        assert_eq!(client.cpu_status().await, Ok(CpuStatus::Unknown));
+    }
+
+    // general record of the SZL partial list 0x0132 with 2 programming device, 3 operator panel
+    // and 1 of 4 configured connections established
+    const RECORDED_COMMUNICATION_STATUS: [u8; 40] = [
+        0x00, 0x01, 0x00, 0x01, 0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04, 0x00, 0x01, 0x00,
+        0x0A, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    #[tokio::test]
+    async fn list_sessions_from_communication_status() {
+        let plc = MockPlc::start().await;
+        plc.set_szl(
+            SZL_ID_COMMUNICATION_STATUS,
+            INDEX_COMMUNICATION_GENERAL,
+            40,
+            &RECORDED_COMMUNICATION_STATUS,
+        );
+        let mut client = plc.client().await;
+
+        let sessions = client.list_sessions().await.unwrap();
+
+        assert_eq!(sessions.len(), 3);
+        assert_eq!(
+            sessions[1],
+            SessionInfo {
+                kind: SessionKind::OperatorPanel,
+                active: 3,
+                reserved: 1,
+            }
+        );
+        assert_eq!(
+            sessions.iter().map(|session| session.active).sum::<u16>(),
+            6
+        );
+
+        plc.set_szl(
+            SZL_ID_COMMUNICATION_STATUS,
+            INDEX_COMMUNICATION_GENERAL,
+            40,
+            &[],
+        );
+        assert_eq!(
+            client.list_sessions().await,
+            Err(Error::FunctionNotSupported)
+        );
+    }
+
+    fn time_system_record(correction_factor: i16, status: u16) -> Vec<u8> {
+        let mut record = vec![0x00; 48];
+        record[..2].copy_from_slice(&INDEX_TIME_SYSTEM.to_be_bytes());
+        record[4..6].copy_from_slice(&correction_factor.to_be_bytes());
+        record[TIME_STATUS_OFFSET..TIME_STATUS_OFFSET + 2].copy_from_slice(&status.to_be_bytes());
+        record
+    }
+
+    #[tokio::test]
+    async fn clock_status_from_time_system_record() {
+        let plc = MockPlc::start().await;
+        plc.set_szl(
+            SZL_ID_COMMUNICATION_STATUS,
+            INDEX_TIME_SYSTEM,
+            48,
+            &time_system_record(-3, 0x0000),
+        );
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.read_clock_status().await,
+            Ok(ClockStatus {
+                running: true,
+                synchronized: true,
+                correction_factor: -3,
+                status: 0x0000,
+            })
+        );
+
+        plc.set_szl(
+            SZL_ID_COMMUNICATION_STATUS,
+            INDEX_TIME_SYSTEM,
+            48,
+            &time_system_record(0, TIME_STATUS_SYNC_FAILURE),
+        );
+        let status = client.read_clock_status().await.unwrap();
+        assert!(!status.synchronized);
+        assert!(status.running);
+    }
+
+    // startup parameter record with a scan cycle monitoring time of 150 ms
+    const RECORDED_STARTUP_PARAMETERS: [u8; 20] = [
+        0x00, 0x09, 0x00, 0x96, 0x00, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    #[tokio::test]
+    async fn watchdog_time_from_startup_parameters() {
+        let plc = MockPlc::start().await;
+        let mut client = plc.client().await;
+        assert_eq!(
+            client.read_watchdog_time().await,
+            Err(Error::FunctionNotSupported)
+        );
+
+        plc.set_szl(
+            SZL_ID_STARTUP_PARAMETERS,
+            INDEX_STARTUP_PARAMETERS,
+            20,
+            &RECORDED_STARTUP_PARAMETERS,
+        );
+        assert_eq!(
+            client.read_watchdog_time().await,
+            Ok(Duration::from_millis(150))
+        );
+        assert!(matches!(
+            watchdog_time_from_szl_record(&RECORDED_STARTUP_PARAMETERS[..3]),
+            Err(Error::TryFrom(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_ip_config_not_supported() {
+        let plc = MockPlc::start().await;
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.read_ip_config().await,
+            Err(Error::FunctionNotSupported)
+        );
+        // the connection is still usable afterwards
+        plc.set_db(1, &[0x2A]);
+        assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0x2A]));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::errors::Error;
+
+/// Character encodings used to interpret the bytes of an S7 `ARRAY of CHAR`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// 7-bit ASCII, bytes above `0x7F` are rejected
+    Ascii,
+    /// ISO-8859-1 (Latin-1), every byte maps to exactly one character
+    Latin1,
+    /// UTF-8, the bytes must form a valid UTF-8 sequence
+    Utf8,
+}
+
+pub(crate) fn decode(bytes: &[u8], encoding: Encoding) -> Result<String, Error> {
+    match encoding {
+        Encoding::Ascii => match bytes.iter().position(|byte| !byte.is_ascii()) {
+            Some(position) => Err(Error::Conversion(format!(
+                "Byte {:#04x} at position {position} is not valid ASCII",
+                bytes[position]
+            ))),
+            None => Ok(bytes.iter().map(|byte| char::from(*byte)).collect()),
+        },
+        Encoding::Latin1 => Ok(bytes.iter().map(|byte| char::from(*byte)).collect()),
+        Encoding::Utf8 => String::from_utf8(bytes.to_vec())
+            .map_err(|e| Error::Conversion(format!("Bytes are not valid UTF-8: {e}"))),
+    }
+}
+
+pub(crate) fn encode(text: &str, encoding: Encoding) -> Result<Vec<u8>, Error> {
+    match encoding {
+        Encoding::Ascii => text
+            .chars()
+            .map(|character| {
+                u8::try_from(character)
+                    .ok()
+                    .filter(u8::is_ascii)
+                    .ok_or_else(|| {
+                        Error::Conversion(format!(
+                            "Character '{character}' can not be encoded as ASCII"
+                        ))
+                    })
+            })
+            .collect(),
+        Encoding::Latin1 => text
+            .chars()
+            .map(|character| {
+                u8::try_from(character).map_err(|_| {
+                    Error::Conversion(format!(
+                        "Character '{character}' can not be encoded as Latin-1"
+                    ))
+                })
+            })
+            .collect(),
+        Encoding::Utf8 => Ok(text.as_bytes().to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_same_bytes_with_different_encodings() {
+        let plain = b"Pump 1";
+        assert_eq!(decode(plain, Encoding::Ascii).unwrap(), "Pump 1");
+        assert_eq!(decode(plain, Encoding::Latin1).unwrap(), "Pump 1");
+        assert_eq!(decode(plain, Encoding::Utf8).unwrap(), "Pump 1");
+
+        // "Größe" in Latin-1
+        let latin1 = [0x47, 0x72, 0xF6, 0xDF, 0x65];
+        assert!(matches!(
+            decode(&latin1, Encoding::Ascii),
+            Err(Error::Conversion(_))
+        ));
+        assert_eq!(decode(&latin1, Encoding::Latin1).unwrap(), "Größe");
+        assert!(matches!(
+            decode(&latin1, Encoding::Utf8),
+            Err(Error::Conversion(_))
+        ));
+
+        // "Größe" in UTF-8 decodes to mojibake with Latin-1
+        let utf8 = "Größe".as_bytes();
+        assert_eq!(decode(utf8, Encoding::Utf8).unwrap(), "Größe");
+        assert_eq!(decode(utf8, Encoding::Latin1).unwrap(), "GrÃ¶Ã\u{9f}e");
+    }
+
+    #[test]
+    fn encode_with_different_encodings() {
+        assert_eq!(
+            encode("Größe", Encoding::Latin1).unwrap(),
+            [0x47, 0x72, 0xF6, 0xDF, 0x65]
+        );
+        assert_eq!(encode("Größe", Encoding::Utf8).unwrap(), "Größe".as_bytes());
+        assert!(matches!(
+            encode("Größe", Encoding::Ascii),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            encode("€", Encoding::Latin1),
+            Err(Error::Conversion(_))
+        ));
+    }
+}
@@ -0,0 +1,171 @@
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+
+use super::create::S7Client;
+use super::pooled::S7Pool;
+use crate::errors::Error;
+
+/// Access to an [`S7Client`] with a different exchange timeout, created by [`S7Client::with_timeout`]
+///
+/// All requests sent through the guard use its timeout, the configured exchange timeout of the
+/// client applies again once the guard is dropped.
+#[derive(Debug)]
+pub struct TimeoutGuard<'a> {
+    client: &'a mut S7Client,
+    previous: Option<Duration>,
+}
+
+impl Deref for TimeoutGuard<'_> {
+    type Target = S7Client;
+
+    fn deref(&self) -> &Self::Target {
+        self.client
+    }
+}
+
+impl DerefMut for TimeoutGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.client
+    }
+}
+
+impl Drop for TimeoutGuard<'_> {
+    fn drop(&mut self) {
+        self.client.timeout_override = self.previous;
+    }
+}
+
+impl S7Client {
+    /// Override the exchange timeout for the operations sent through the returned guard
+    ///
+    /// Useful for single operations that need more time than usual, e.g. a large read split into
+    /// many requests, or less, e.g. a fast health check. The timeout applies to every request of
+    /// the operation, not to the operation as a whole.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let data = client.with_timeout(Duration::from_secs(10))
+    ///     .db_read(100, 0, 16384)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    pub fn with_timeout(&mut self, exchange_timeout: Duration) -> TimeoutGuard<'_> {
+        let previous = self.timeout_override.replace(exchange_timeout);
+        TimeoutGuard {
+            client: self,
+            previous,
+        }
+    }
+
+    /// Read a defined number of bytes from a specified data block, overriding the exchange timeout for this read
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, offset, length) = (100, 0, 4);
+    /// let data = client.db_read_with_timeout(data_block, offset, length, Duration::from_millis(500))
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataExchangeTimedOut` if the PLC did not respond in time or `Error` if any other errors
+    /// occurred during reading.
+    pub async fn db_read_with_timeout(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+        exchange_timeout: Duration,
+    ) -> Result<Vec<u8>, Error> {
+        self.with_timeout(exchange_timeout)
+            .db_read(db_number, start, length)
+            .await
+    }
+}
+
+impl S7Pool {
+    /// Read a defined number of bytes from a specified data block, overriding the exchange timeout for this read
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, length) = (100, 0, 4);
+    /// let data = pool.db_read_with_timeout(data_block, offset, length, Duration::from_millis(500))
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataExchangeTimedOut` if the PLC did not respond in time or `Error` if any other errors
+    /// occurred during reading.
+    pub async fn db_read_with_timeout(
+        &self,
+        db_number: u16,
+        start: u32,
+        length: u16,
+        exchange_timeout: Duration,
+    ) -> Result<Vec<u8>, Error> {
+        let mut connection = self.0.get().await?;
+
+        connection
+            .db_read_with_timeout(db_number, start, length, exchange_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    #[tokio::test]
+    async fn timeout_is_overridden_for_a_single_operation() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x01, 0x02, 0x03, 0x04]);
+        plc.state()
+            .read_delays
+            .insert(1, Duration::from_millis(100));
+        let mut client = plc
+            .builder()
+            .exchange_timeout(Duration::from_millis(30))
+            .connect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            client
+                .db_read_with_timeout(1, 0, 4, Duration::from_secs(2))
+                .await,
+            Ok(vec![0x01, 0x02, 0x03, 0x04])
+        );
+        assert_eq!(
+            client
+                .with_timeout(Duration::from_secs(2))
+                .db_read(1, 0, 2)
+                .await,
+            Ok(vec![0x01, 0x02])
+        );
+        assert_eq!(client.exchange_timeout(), Duration::from_millis(30));
+        assert_eq!(
+            client.db_read(1, 0, 4).await,
+            Err(Error::DataExchangeTimedOut)
+        );
+    }
+}
@@ -1,10 +1,50 @@
 use std::borrow::Borrow;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt::Debug;
+use std::future::Future;
 use std::hash::Hash;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_core::Stream;
+use tokio::time::{interval, Interval, MissedTickBehavior};
 
 use crate::errors::Error;
-use crate::{S7Pool, S7ReadAccess};
+use crate::{S7Client, S7Pool, S7ReadAccess};
+
+/// Backend reading the observed variables of a [`TriggerCollection`]
+///
+/// Implemented for [`S7Pool`] and for an exclusively borrowed [`S7Client`].
+#[async_trait]
+pub trait TriggerSource: Send {
+    /// Read the observed variables in one go, see [`S7Client::db_read_multi`]
+    async fn read_multi(
+        &mut self,
+        info: &[S7ReadAccess],
+    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error>;
+}
+
+#[async_trait]
+impl TriggerSource for S7Pool {
+    async fn read_multi(
+        &mut self,
+        info: &[S7ReadAccess],
+    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
+        self.db_read_multi(info).await
+    }
+}
+
+#[async_trait]
+impl TriggerSource for &mut S7Client {
+    async fn read_multi(
+        &mut self,
+        info: &[S7ReadAccess],
+    ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
+        self.db_read_multi(info).await
+    }
+}
 
 #[derive(Debug)]
 struct PLCBool {
@@ -35,32 +75,35 @@ impl PLCBool {
 }
 
 /// Collection of observed `Bool` variables of the PLC
-pub struct TriggerCollection<T>
+///
+/// The variables are read through the [`TriggerSource`] `S`, a [`S7Pool`] by default.
+pub struct TriggerCollection<T, S = S7Pool>
 where
     T: Hash + Eq,
 {
     stored_values: HashMap<T, PLCBool>,
     plc_values: Vec<S7ReadAccess>,
     value_ids: Vec<T>,
-    pool: S7Pool,
+    source: S,
 }
 
-impl<T> Debug for TriggerCollection<T>
+impl<T, S> Debug for TriggerCollection<T, S>
 where
     T: Hash + Eq,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TriggerCollection")
             .field("observed plc values", &self.plc_values)
-            .finish()
+            .finish_non_exhaustive()
     }
 }
 
-impl<T> TriggerCollection<T>
+impl<T, S> TriggerCollection<T, S>
 where
     T: Hash + Eq + Clone,
+    S: TriggerSource,
 {
-    pub(crate) fn new(pool: &S7Pool, triggers: &[(T, S7ReadAccess)]) -> Result<Self, Error> {
+    pub(crate) fn new(source: S, triggers: &[(T, S7ReadAccess)]) -> Result<Self, Error> {
         let value_ids: Vec<T> = triggers
             .iter()
             .map(|trigger| trigger.0.to_owned())
@@ -75,7 +118,7 @@ where
         }) {
             // throw error because Bytes are tried to be read
             return Err(Error::InvalidTriggerCollection);
-        };
+        }
 
         let mut stored_values = HashMap::new();
 
@@ -87,7 +130,7 @@ where
             stored_values,
             plc_values,
             value_ids,
-            pool: pool.clone(),
+            source,
         })
     }
 
@@ -96,7 +139,7 @@ where
     ///
     /// Will return `Error` if the `TriggerCollection` could not be updated.
     pub async fn update(&mut self) -> Result<(), Error> {
-        let values = self.pool.db_read_multi(&self.plc_values).await?;
+        let values = self.source.read_multi(&self.plc_values).await?;
 
         for (index, value) in values.into_iter().enumerate() {
             let bool = value?[0] > 0;
@@ -140,4 +183,411 @@ where
     {
         self.stored_values.get(trigger).map(PLCBool::negative_flank)
     }
+
+    fn flanks(&self) -> impl Iterator<Item = TriggerEvent<T>> + '_ {
+        self.value_ids.iter().filter_map(|id| {
+            let trigger = self.stored_values.get(id)?;
+            let kind = if trigger.positive_flank() {
+                TriggerKind::Positive
+            } else if trigger.negative_flank() {
+                TriggerKind::Negative
+            } else {
+                return None;
+            };
+            Some(TriggerEvent {
+                id: id.to_owned(),
+                kind,
+            })
+        })
+    }
+}
+
+impl<T, S> TriggerCollection<T, S>
+where
+    T: Hash + Eq + Clone + Send + 'static,
+    S: TriggerSource + 'static,
+{
+    /// Observe the triggers by updating the collection periodically
+    ///
+    /// The returned [`TriggerWatch`] is a [`Stream`] yielding an event for every detected flank,
+    /// in the order the triggers were given to the collection. Errors of an update are yielded as
+    /// item and the observation continues with the next interval. Dropping the stream stops the observation.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use std::net::Ipv4Addr;
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Pool, S7Types, S7ReadAccess};
+    /// use tokio_stream::StreamExt;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # let pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let collection = pool.new_trigger_collection(&[("START", S7ReadAccess::bit(100, 0, 0))])?;
+    /// let mut events = collection.watch(Duration::from_millis(100));
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     let event = event?;
+    ///     println!("{} changed: {:?}", event.id, event.kind);
+    /// }
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    /// # Panics
+    ///
+    /// Will panic if called outside of a Tokio runtime.
+    pub fn watch(self, interval_duration: Duration) -> TriggerWatch<T, S> {
+        let mut interval = interval(interval_duration);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        TriggerWatch {
+            collection: Some(self),
+            pending: VecDeque::new(),
+            interval,
+            update: None,
+        }
+    }
+}
+
+/// Kind of a flank detected by [`TriggerWatch`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerKind {
+    /// Observed value changed from `false` to `true`
+    Positive,
+    /// Observed value changed from `true` to `false`
+    Negative,
+}
+
+/// Flank of an observed trigger, yielded by [`TriggerWatch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggerEvent<T> {
+    /// Id of the trigger as given to the collection
+    pub id: T,
+    /// Kind of the detected flank
+    pub kind: TriggerKind,
+}
+
+type PendingUpdate<T, S> =
+    Pin<Box<dyn Future<Output = (TriggerCollection<T, S>, Result<(), Error>)> + Send>>;
+
+/// Periodic observation of a [`TriggerCollection`], created by [`TriggerCollection::watch`]
+#[must_use = "streams do nothing unless polled"]
+pub struct TriggerWatch<T, S = S7Pool>
+where
+    T: Hash + Eq,
+{
+    // taken by the update in progress
+    collection: Option<TriggerCollection<T, S>>,
+    pending: VecDeque<TriggerEvent<T>>,
+    interval: Interval,
+    update: Option<PendingUpdate<T, S>>,
+}
+
+impl<T, S> Debug for TriggerWatch<T, S>
+where
+    T: Hash + Eq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TriggerWatch")
+            .field("period", &self.interval.period())
+            .field("updating", &self.update.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+// the fields are never pinned, the pending update is boxed
+impl<T, S> Unpin for TriggerWatch<T, S> where T: Hash + Eq {}
+
+impl<T, S> Stream for TriggerWatch<T, S>
+where
+    T: Hash + Eq + Clone + Send + 'static,
+    S: TriggerSource + 'static,
+{
+    type Item = Result<TriggerEvent<T>, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(event) = this.pending.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+
+            if let Some(update) = this.update.as_mut() {
+                let (collection, result) = ready!(update.as_mut().poll(cx));
+                this.update = None;
+                this.pending.extend(collection.flanks());
+                this.collection = Some(collection);
+                if let Err(error) = result {
+                    return Poll::Ready(Some(Err(error)));
+                }
+                continue;
+            }
+
+            ready!(this.interval.poll_tick(cx));
+            // the collection is only missing while an update is in progress
+            if let Some(mut collection) = this.collection.take() {
+                this.update = Some(Box::pin(async move {
+                    let result = collection.update().await;
+                    (collection, result)
+                }));
+            }
+        }
+    }
+}
+
+impl S7Client {
+    /// Create new collection of observed `Bool` variables of S7 PLC, read through this client
+    ///
+    /// The collection borrows the client exclusively until it is dropped.
+    ///```rust
+    /// # tokio_test::block_on(async {
+    /// use std::net::Ipv4Addr;
+    /// use s7client::{S7Client, S7Types, S7ReadAccess};
+    ///
+    /// let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let mut trigger_collection = client.new_trigger_collection(&[
+    ///         ("TRIGGER_ONE", S7ReadAccess::bit(100, 0, 1)),
+    ///         ("TRIGGER_TWO", S7ReadAccess::bit(100, 0, 2)),
+    ///     ])?;
+    /// trigger_collection.update().await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// # });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if the `TriggerCollection` could not be created.
+    pub fn new_trigger_collection<T>(
+        &mut self,
+        triggers: &[(T, S7ReadAccess)],
+    ) -> Result<TriggerCollection<T, &mut Self>, Error>
+    where
+        T: Hash + Eq + Clone,
+    {
+        TriggerCollection::new(self, triggers)
+    }
+}
+
+#[derive(Debug, Default)]
+struct PLCValue {
+    value: Option<Vec<u8>>,
+    last_known_value: Option<Vec<u8>>,
+}
+
+impl PLCValue {
+    fn update(&mut self, new_value: Vec<u8>) {
+        self.last_known_value = self.value.replace(new_value);
+    }
+
+    fn changed(&self) -> bool {
+        matches!((&self.value, &self.last_known_value), (Some(value), Some(last)) if value != last)
+    }
+}
+
+/// Collection of observed variables of the PLC, e.g. status words, that are compared byte by byte
+///
+/// Unlike [`TriggerCollection`] every kind of read access is allowed, bytes are observed as a whole.
+pub struct ValueTriggerCollection<T>
+where
+    T: Hash + Eq,
+{
+    stored_values: HashMap<T, PLCValue>,
+    plc_values: Vec<S7ReadAccess>,
+    value_ids: Vec<T>,
+    pool: S7Pool,
+}
+
+impl<T> Debug for ValueTriggerCollection<T>
+where
+    T: Hash + Eq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValueTriggerCollection")
+            .field("observed plc values", &self.plc_values)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> ValueTriggerCollection<T>
+where
+    T: Hash + Eq + Clone,
+{
+    pub(crate) fn new(pool: &S7Pool, triggers: &[(T, S7ReadAccess)]) -> Self {
+        let value_ids: Vec<T> = triggers
+            .iter()
+            .map(|trigger| trigger.0.to_owned())
+            .collect();
+
+        let plc_values: Vec<S7ReadAccess> = triggers.iter().map(|trigger| trigger.1).collect();
+
+        let stored_values = value_ids
+            .iter()
+            .map(|id| (id.to_owned(), PLCValue::default()))
+            .collect();
+
+        Self {
+            stored_values,
+            plc_values,
+            value_ids,
+            pool: pool.clone(),
+        }
+    }
+
+    /// Read current values from PLC and update collection of observed variables
+    /// # Errors
+    ///
+    /// Will return `Error` if the `ValueTriggerCollection` could not be updated.
+    pub async fn update(&mut self) -> Result<(), Error> {
+        let values = self.pool.db_read_multi(&self.plc_values).await?;
+
+        for (index, value) in values.into_iter().enumerate() {
+            let value = value?;
+            let trigger_id = &self.value_ids[index];
+
+            // Should always be true!
+            if let Some(trigger) = self.stored_values.get_mut(trigger_id) {
+                trigger.update(value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check whether one of the observed variables changed compared to before the last update of the collection.
+    ///
+    /// Returns `Some(true)` if any bit changed.
+    ///
+    /// Returns `Some(false)` if no change is detected or the collection was updated less than twice.
+    ///
+    /// Returns `None` if given trigger is not part of the collection.
+    pub fn changed<Q>(&self, trigger: &Q) -> Option<bool>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.stored_values.get(trigger).map(PLCValue::changed)
+    }
+
+    /// Value of one of the observed variables read by the last update of the collection.
+    ///
+    /// Returns `None` if given trigger is not part of the collection or the collection was not updated yet.
+    pub fn current<Q>(&self, trigger: &Q) -> Option<&[u8]>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.stored_values.get(trigger)?.value.as_deref()
+    }
+
+    /// Value of one of the observed variables before the last update of the collection.
+    ///
+    /// Returns `None` if given trigger is not part of the collection or the collection was updated less than twice.
+    pub fn previous<Q>(&self, trigger: &Q) -> Option<&[u8]>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.stored_values.get(trigger)?.last_known_value.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio_stream::StreamExt;
+
+    use super::{TriggerEvent, TriggerKind};
+    use crate::errors::Error;
+    use crate::mock::MockPlc;
+    use crate::S7ReadAccess;
+
+    #[tokio::test]
+    async fn watch_yields_flanks_and_errors() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0b0000_0001]);
+        let pool = plc.pool(1);
+        let collection = pool
+            .new_trigger_collection(&[
+                ("START", S7ReadAccess::bit(1, 0, 0)),
+                ("STOP", S7ReadAccess::bit(1, 0, 1)),
+            ])
+            .unwrap();
+        let mut events = collection.watch(Duration::from_millis(10));
+
+        assert_eq!(
+            events.next().await.unwrap(),
+            Ok(TriggerEvent {
+                id: "START",
+                kind: TriggerKind::Positive
+            })
+        );
+
+        plc.set_db(1, &[0b0000_0010]);
+        assert_eq!(
+            events.next().await.unwrap(),
+            Ok(TriggerEvent {
+                id: "START",
+                kind: TriggerKind::Negative
+            })
+        );
+        assert_eq!(
+            events.next().await.unwrap(),
+            Ok(TriggerEvent {
+                id: "STOP",
+                kind: TriggerKind::Positive
+            })
+        );
+
+        plc.state().read_protected = true;
+        assert!(matches!(
+            events.next().await,
+            Some(Err(Error::DataItemError(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn triggers_are_read_through_client() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0b0000_0001]);
+        let mut client = plc.client().await;
+        let mut triggers = client
+            .new_trigger_collection(&[("START", S7ReadAccess::bit(1, 0, 0))])
+            .unwrap();
+
+        triggers.update().await.unwrap();
+        assert_eq!(triggers.positive_flank("START"), Some(true));
+
+        plc.set_db(1, &[0b0000_0000]);
+        triggers.update().await.unwrap();
+        assert_eq!(triggers.negative_flank("START"), Some(true));
+        assert_eq!(triggers.positive_flank("START"), Some(false));
+        drop(triggers);
+
+        assert_eq!(client.requests_issued(), 2);
+    }
+
+    #[tokio::test]
+    async fn value_triggers_detect_changed_words() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00, 0x01, 0x00, 0x02]);
+        let pool = plc.pool(1);
+        let mut triggers = pool.new_value_trigger_collection(&[
+            ("STATUS", S7ReadAccess::bytes(1, 0, 2)),
+            ("MODE", S7ReadAccess::bytes(1, 2, 2)),
+        ]);
+
+        triggers.update().await.unwrap();
+        assert_eq!(triggers.changed("STATUS"), Some(false));
+        assert_eq!(triggers.current("STATUS"), Some([0x00, 0x01].as_slice()));
+        assert_eq!(triggers.previous("STATUS"), None);
+
+        plc.set_db(1, &[0x80, 0x01, 0x00, 0x02]);
+        triggers.update().await.unwrap();
+        assert_eq!(triggers.changed("STATUS"), Some(true));
+        assert_eq!(triggers.changed("MODE"), Some(false));
+        assert_eq!(triggers.current("STATUS"), Some([0x80, 0x01].as_slice()));
+        assert_eq!(triggers.previous("STATUS"), Some([0x00, 0x01].as_slice()));
+        assert_eq!(triggers.changed("UNKNOWN"), None);
+
+        triggers.update().await.unwrap();
+        assert_eq!(triggers.changed("STATUS"), Some(false));
+    }
 }
@@ -1,9 +1,16 @@
+use tokio::time::{timeout_at, Instant};
+
+use super::bits;
 use super::create::S7Client;
-use super::verify_max_bit;
-use crate::s7_protocol::types::Area;
-use crate::s7_protocol::write_area::write_area_multi;
+use super::text::{self, Encoding};
+use super::{verify_max_bit, IoMode};
+use crate::s7_protocol::types::{Area, TransportSize};
+use crate::s7_protocol::write_area::{
+    batch_write_accesses, fits_into_write_request, write_area_multi, write_area_single_with,
+};
+use crate::time::{self, S7DateTime};
 use crate::{errors::Error, s7_protocol::write_area::write_area_single};
-use crate::{S7Pool, S7WriteAccess};
+use crate::{IntType, S7Pool, S7WriteAccess};
 
 /// *Methods for writing data into the PLC device*
 impl S7Client {
@@ -25,7 +32,7 @@ impl S7Client {
     ///
     /// Will return `Error` if any errors occurred during writing.
     pub async fn db_write(&mut self, db_number: u16, start: u32, data: &[u8]) -> Result<(), Error> {
-        self.validate_connection_info()?;
+        self.validate_connection_info().await?;
         write_area_single(
             self,
             Area::DataBlock,
@@ -38,6 +45,42 @@ impl S7Client {
         .await
     }
 
+    /// Write a defined number bytes into a specified data block with an offset before a deadline
+    ///
+    /// The deadline bounds the whole operation. If the deadline passes before the write is
+    /// acknowledged, the connection is closed because it was interrupted mid exchange.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let deadline = tokio::time::Instant::now() + Duration::from_millis(500);
+    /// client.db_write_until(100, 0, &[0, 1, 2, 3], deadline)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataExchangeTimedOut` if the deadline passed or `Error` if any other errors occurred during writing.
+    pub async fn db_write_until(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        data: &[u8],
+        deadline: Instant,
+    ) -> Result<(), Error> {
+        if let Ok(result) = timeout_at(deadline, self.db_write(db_number, start, data)).await {
+            result
+        } else {
+            self.set_closed();
+            Err(Error::DataExchangeTimedOut)
+        }
+    }
+
     /// Write a specific bit to a specified data block
     ///
     /// The bit number must be within the range 0..7
@@ -63,7 +106,7 @@ impl S7Client {
         bit: u8,
         value: bool,
     ) -> Result<(), Error> {
-        self.validate_connection_info()?;
+        self.validate_connection_info().await?;
 
         verify_max_bit(bit)?;
 
@@ -80,8 +123,171 @@ impl S7Client {
         .await
     }
 
+    /// Write consecutive bits into a specified data block, e.g. an `ARRAY of BOOL`
+    ///
+    /// The bits are written in the order of the PLC: starting with bit `start_bit` of byte
+    /// `start_byte`, continuing with bit 0 of the next byte after bit 7. The bytes covering the
+    /// bits are read, modified and written back, so other bits in these bytes keep their value.
+    /// Changes of the PLC to these other bits between reading and writing are overwritten.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// // DB100.DBX4.6 to DB100.DBX5.1
+    /// client.db_write_bits(100, 4, 6, &[true, false, true, true])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::RequestedBitOutOfRange` if `start_bit` is larger than 7 or `Error` if any errors occurred during reading or writing.
+    pub async fn db_write_bits(
+        &mut self,
+        db_number: u16,
+        start_byte: u32,
+        start_bit: u8,
+        values: &[bool],
+    ) -> Result<(), Error> {
+        verify_max_bit(start_bit)?;
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let length = u16::try_from(bits::byte_span(start_bit, values.len()))
+            .map_err(|_| Error::DataItemTooLarge)?;
+        let mut data = self.db_read(db_number, start_byte, length).await?;
+        bits::pack_bits(&mut data, start_bit, values)?;
+        self.db_write(db_number, start_byte, &data).await
+    }
+
+    /// Write an unsigned value packed into `bit_width` bits of a specified data block
+    ///
+    /// The bitfield starts at bit `start_bit` of byte `byte` (least significant bit first) and may
+    /// continue into the next byte. The covered bytes are read, modified and written back, so other
+    /// bits in these bytes keep their value. Changes of the PLC to these other bits between reading
+    /// and writing are overwritten.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// // DB100.DBX4.2 to DB100.DBX4.4
+    /// client.db_write_bitfield(100, 4, 2, 3, 5)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::RequestedBitOutOfRange` if the bitfield is empty or covers more than two bytes,
+    /// `Error::Conversion` if `value` does not fit into `bit_width` bits or `Error` if any errors occurred during reading or writing.
+    pub async fn db_write_bitfield(
+        &mut self,
+        db_number: u16,
+        byte: u32,
+        start_bit: u8,
+        bit_width: u8,
+        value: u32,
+    ) -> Result<(), Error> {
+        let length = bits::bitfield_span(start_bit, bit_width)?;
+        let mut data = self.db_read(db_number, byte, length).await?;
+        bits::insert_bitfield(&mut data, start_bit, bit_width, value)?;
+        self.db_write(db_number, byte, &data).await
+    }
+
+    /// Write several bits of the same byte of a specified data block in one go
+    ///
+    /// The byte is read once, all `(bit, value)` updates are applied in the given order and the
+    /// byte is written back, keeping the read-modify-write window as short as possible compared to
+    /// separate calls of [`db_write_bit`](S7Client::db_write_bit). Changes of the PLC to the other
+    /// bits of the byte between reading and writing are overwritten.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// // set DB100.DBX4.0 and DB100.DBX4.5, reset DB100.DBX4.2
+    /// client.db_write_bits_in_byte(100, 4, &[(0, true), (2, false), (5, true)])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::RequestedBitOutOfRange` if any bit is larger than 7 or `Error` if any errors occurred during reading or writing.
+    pub async fn db_write_bits_in_byte(
+        &mut self,
+        db_number: u16,
+        byte: u32,
+        updates: &[(u8, bool)],
+    ) -> Result<(), Error> {
+        for (bit, _) in updates {
+            verify_max_bit(*bit)?;
+        }
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        let mut data = self.db_read(db_number, byte, 1).await?;
+        if data.len() != 1 {
+            return Err(Error::Conversion(format!(
+                "Expected 1 byte but got {}",
+                data.len()
+            )));
+        }
+        for (bit, value) in updates {
+            bits::pack_bits(&mut data, *bit, &[*value])?;
+        }
+        self.db_write(db_number, byte, &data).await
+    }
+
+    /// Pack 16 bits into a WORD and write it into a specified data block, e.g. a control register
+    ///
+    /// Index `n` of `flags` provides bit `n` of the WORD, matching
+    /// [`db_read_word_flags`](S7Client::db_read_word_flags).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let mut flags = [false; 16];
+    /// flags[0] = true;
+    /// // DB100.DBW4
+    /// client.db_write_word_flags(100, 4, &flags)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn db_write_word_flags(
+        &mut self,
+        db_number: u16,
+        byte: u32,
+        flags: &[bool; 16],
+    ) -> Result<(), Error> {
+        let word = bits::flags_to_word(flags);
+        self.db_write(db_number, byte, &word.to_be_bytes()).await
+    }
+
     /// Write multiple bytes or bits to different locations of the PLC
     ///
+    /// The items are packed into as few requests as the PDU size allows, limited by both the number
+    /// of items and the amount of data per request. The requests are sent one after another and
+    /// the results are returned in the order of the items. An item too large for a request on its
+    /// own is written in chunks.
+    ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
@@ -103,47 +309,62 @@ impl S7Client {
         &mut self,
         info: &[S7WriteAccess<'_>],
     ) -> Result<Vec<Result<(), Error>>, Error> {
-        self.validate_connection_info()?;
+        self.validate_connection_info().await?;
 
         for access in info {
             verify_max_bit(access.max_bit())?;
         }
 
-        write_area_multi(self, Area::DataBlock, info).await
+        let mut results = Vec::with_capacity(info.len());
+        for batch in batch_write_accesses(info, self.pdu_length.into()) {
+            let batch = &info[batch];
+            if let [access] = batch {
+                if !fits_into_write_request(batch, self.pdu_length.into()) {
+                    // a single item might be too large for one request and has to be split
+                    match write_area_single(self, Area::DataBlock, *access).await {
+                        Err(error) if error.is_connection_error() => return Err(error),
+                        result => results.push(result),
+                    }
+                    continue;
+                }
+            }
+            results.extend(write_area_multi(self, Area::DataBlock, batch).await?);
+        }
+
+        Ok(results)
     }
 
-    /// Write a defined number of bytes to the 'Merker area' of the PLC with a certain offset
+    /// Encode a text with the given encoding and write it as `ARRAY of CHAR` into a specified data block
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Client, S7Types};
+    /// # use s7client::{Encoding, S7Client, S7Types};
     /// # tokio_test::block_on(async {
     /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
-    /// let (start, data) = (10, &[0, 1]);
-    /// let bit = client.mb_write(start, data)
+    /// let (data_block, offset, text) = (100, 0, "Pump 1");
+    /// client.db_write_char_array(data_block, offset, text, Encoding::Ascii)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
     /// # Errors
     ///
-    /// Will return `Error` if any errors occurred during writing.
-    pub async fn mb_write(&mut self, start: u32, data: &[u8]) -> Result<(), Error> {
-        self.validate_connection_info()?;
-        write_area_single(
-            self,
-            Area::Merker,
-            S7WriteAccess::Bytes {
-                db_number: 0,
-                start,
-                data,
-            },
-        )
-        .await
+    /// Will return `Error` if the text can not be represented in the chosen encoding or if any errors occurred during writing.
+    pub async fn db_write_char_array(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        text: &str,
+        encoding: Encoding,
+    ) -> Result<(), Error> {
+        let data = text::encode(text, encoding)?;
+        self.db_write(db_number, start, &data).await
     }
 
-    /// Write a defined number of bytes into the 'input value area' of the PLC with a certain offset
+    /// Write a text as fixed Latin-1 `ARRAY of CHAR` into a specified data block
+    ///
+    /// Every character is written as one byte, no `STRING` header is written.
     ///
     /// # Example
     /// ```rust
@@ -151,30 +372,26 @@ impl S7Client {
     /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
     /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
-    /// let (start, data) = (10, &[0, 1]);
-    /// let bit = client.i_write(start, data)
+    /// let (data_block, offset, text) = (100, 0, "Größe 1");
+    /// client.db_write_chars(data_block, offset, text)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
     /// # Errors
     ///
-    /// Will return `Error` if any errors occurred during writing.
-    pub async fn i_write(&mut self, start: u32, data: &[u8]) -> Result<(), Error> {
-        self.validate_connection_info()?;
-        write_area_single(
-            self,
-            Area::ProcessInput,
-            S7WriteAccess::Bytes {
-                db_number: 0,
-                start,
-                data,
-            },
-        )
-        .await
+    /// Will return `Error::Conversion` if the text contains characters outside of Latin-1 or `Error` if any errors occurred during writing.
+    pub async fn db_write_chars(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        text: &str,
+    ) -> Result<(), Error> {
+        self.db_write_char_array(db_number, start, text, Encoding::Latin1)
+            .await
     }
 
-    /// Write a defined number of bytes into the 'output value area' of the PLC with a certain offset
+    /// Write an `ARRAY of INT` into a specified data block
     ///
     /// # Example
     /// ```rust
@@ -182,8 +399,8 @@ impl S7Client {
     /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
     /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
-    /// let (start, data) = (10, &[0, 1]);
-    /// let bit = client.o_write(start, data)
+    /// let (data_block, offset) = (100, 0);
+    /// client.db_write_int_array(data_block, offset, &[-5, 0, 120])
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
@@ -191,33 +408,29 @@ impl S7Client {
     /// # Errors
     ///
     /// Will return `Error` if any errors occurred during writing.
-    pub async fn o_write(&mut self, start: u32, data: &[u8]) -> Result<(), Error> {
-        self.validate_connection_info()?;
-        write_area_single(
-            self,
-            Area::ProcessOutput,
-            S7WriteAccess::Bytes {
-                db_number: 0,
-                start,
-                data,
-            },
-        )
-        .await
+    pub async fn db_write_int_array(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        values: &[i16],
+    ) -> Result<(), Error> {
+        let data = values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect::<Vec<u8>>();
+        self.db_write(db_number, start, &data).await
     }
-}
 
-/// *Methods for writing data into the PLC device*
-impl S7Pool {
-    /// Write a defined number bytes into a specified data block with an offset
+    /// Write an `ARRAY of DINT` into a specified data block
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Pool, S7Types};
+    /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
-    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
-    /// let (data_block, offset, data) = (100, 0, &[0, 1, 2, 3]);
-    /// let data = pool.db_write(data_block, offset, data)
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, offset) = (100, 0);
+    /// client.db_write_dint_array(data_block, offset, &[-100_000, 0, 250_000])
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
@@ -225,22 +438,29 @@ impl S7Pool {
     /// # Errors
     ///
     /// Will return `Error` if any errors occurred during writing.
-    pub async fn db_write(&self, db_number: u16, start: u32, data: &[u8]) -> Result<(), Error> {
-        let mut connection = self.0.get().await?;
-        connection.db_write(db_number, start, data).await
+    pub async fn db_write_dint_array(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        values: &[i32],
+    ) -> Result<(), Error> {
+        let data = values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect::<Vec<u8>>();
+        self.db_write(db_number, start, &data).await
     }
 
-    /// Write a specific bit to a specified data block
+    /// Write an `ARRAY of REAL` into a specified data block
     ///
-    /// The bit number must be within the range 0..7
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Pool, S7Types};
+    /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
-    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
-    /// let (data_block, byte, bit, value) = (100, 0, 0, false);
-    /// let bit = pool.db_write_bit(data_block, byte, bit, value)
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, offset) = (100, 0);
+    /// client.db_write_real_array(data_block, offset, &[1.5, -0.25, 100.0])
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
@@ -248,76 +468,95 @@ impl S7Pool {
     /// # Errors
     ///
     /// Will return `Error` if any errors occurred during writing.
-    pub async fn db_write_bit(
-        &self,
+    pub async fn db_write_real_array(
+        &mut self,
         db_number: u16,
-        byte: u32,
-        bit: u8,
-        value: bool,
+        start: u32,
+        values: &[f32],
     ) -> Result<(), Error> {
-        let mut connection = self.0.get().await?;
-        connection.db_write_bit(db_number, byte, bit, value).await
+        let data = values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect::<Vec<u8>>();
+        self.db_write(db_number, start, &data).await
     }
 
-    /// Write multiple bytes or bits to different locations of the PLC
+    /// Write a date and time as `DTL` (12 bytes) into a specified data block
+    ///
+    /// The year is written as plain number and the fraction of the second with nanoseconds.
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Pool, S7Types, S7WriteAccess};
+    /// # use s7client::{S7Client, S7Types};
+    /// # use s7client::time::S7DateTime;
     /// # tokio_test::block_on(async {
-    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
-    /// let data = pool.db_write_multi(&[
-    ///        S7WriteAccess::bytes(100, 0, &[0, 0, 0, 1]),
-    ///        S7WriteAccess::bit(101, 0, 1, true),
-    ///    ])
-    ///    .await?;
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71500).await?;
+    /// let timestamp = S7DateTime::new(2024, 5, 17, 8, 30, 0, 250_000_000)?;
+    /// client.db_write_dtl(100, 0, &timestamp)
+    ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
     /// # Errors
     ///
-    /// Will return `Error` if any errors occurred during writing.
-    pub async fn db_write_multi(
-        &self,
-        info: &[S7WriteAccess<'_>],
-    ) -> Result<Vec<Result<(), Error>>, Error> {
-        let mut connection = self.0.get().await?;
-        connection.db_write_multi(info).await
+    /// Will return `Error::Conversion` if the date and time is invalid or outside of the years 1970 to 2262
+    /// or `Error` if any errors occurred during writing.
+    pub async fn db_write_dtl(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        date_time: &S7DateTime,
+    ) -> Result<(), Error> {
+        let data = time::encode_dtl(date_time)?;
+        self.db_write(db_number, start, &data).await
     }
 
-    /// Write a defined number of bytes to the 'Merker area' of the PLC with a certain offset
+    /// Divide `value` by `scale` and write it as integer into a specified data block
+    ///
+    /// The quotient is rounded to the nearest integer, halves are rounded away from zero
+    /// (`2.5` becomes `3` and `-2.5` becomes `-3`).
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Pool, S7Types};
+    /// # use s7client::{IntType, S7Client, S7Types};
     /// # tokio_test::block_on(async {
-    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
-    /// let (start, data) = (10, &[0, 1]);
-    /// let bit = pool.mb_write(start, data)
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300).await?;
+    /// // DB100.DBW4 in 0.1 °C, writes 235
+    /// client.db_write_fixed(100, 4, IntType::Int, 0.1, 23.5)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
     /// ```
     /// # Errors
     ///
-    /// Will return `Error` if any errors occurred during writing.
-    pub async fn mb_write(&self, start: u32, data: &[u8]) -> Result<(), Error> {
-        let mut connection = self.0.get().await?;
-        connection.mb_write(start, data).await
+    /// Will return `Error::Conversion` if the scale is zero or not finite or the rounded value does not fit
+    /// into the integer type or `Error` if any errors occurred during writing.
+    pub async fn db_write_fixed(
+        &mut self,
+        db_number: u16,
+        byte: u32,
+        int_type: IntType,
+        scale: f64,
+        value: f64,
+    ) -> Result<(), Error> {
+        let data = int_type.encode_scaled(value, scale)?;
+        self.db_write(db_number, byte, &data).await
     }
 
-    /// Write a defined number of bytes into the 'input value area' of the PLC with a certain offset
+    /// Fill a region of a specified data block with copies of a single byte
+    ///
+    /// The fill is split into multiple requests if it does not fit into one PDU.
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Pool, S7Types};
+    /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
-    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
-    /// let (start, data) = (10, &[0, 1]);
-    /// let bit = pool.i_write(start, data)
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (data_block, offset, length) = (100, 0, 500);
+    /// client.db_fill(data_block, offset, length, 0x00)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
@@ -325,21 +564,27 @@ impl S7Pool {
     /// # Errors
     ///
     /// Will return `Error` if any errors occurred during writing.
-    pub async fn i_write(&self, start: u32, data: &[u8]) -> Result<(), Error> {
-        let mut connection = self.0.get().await?;
-        connection.i_write(start, data).await
+    pub async fn db_fill(
+        &mut self,
+        db_number: u16,
+        start: u32,
+        length: u32,
+        value: u8,
+    ) -> Result<(), Error> {
+        let data = vec![value; length as usize];
+        self.db_write(db_number, start, &data).await
     }
 
-    /// Write a defined number of bytes into the 'output value area' of the PLC with a certain offset
+    /// Write a defined number of bytes to the 'Merker area' of the PLC with a certain offset
     ///
     /// # Example
     /// ```rust
     /// # use std::net::Ipv4Addr;
-    /// # use s7client::{S7Pool, S7Types};
+    /// # use s7client::{S7Client, S7Types};
     /// # tokio_test::block_on(async {
-    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
     /// let (start, data) = (10, &[0, 1]);
-    /// let bit = pool.o_write(start, data)
+    /// let bit = client.mb_write(start, data)
     ///     .await?;
     /// # Ok::<(), s7client::errors::Error>(())
     /// });
@@ -347,8 +592,1292 @@ impl S7Pool {
     /// # Errors
     ///
     /// Will return `Error` if any errors occurred during writing.
-    pub async fn o_write(&self, start: u32, data: &[u8]) -> Result<(), Error> {
+    pub async fn mb_write(&mut self, start: u32, data: &[u8]) -> Result<(), Error> {
+        self.validate_connection_info().await?;
+        write_area_single(
+            self,
+            Area::Merker,
+            S7WriteAccess::Bytes {
+                db_number: 0,
+                start,
+                data,
+            },
+        )
+        .await
+    }
+
+    /// Write a defined number of bytes into the 'input value area' of the PLC with a certain offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (start, data) = (10, &[0, 1]);
+    /// let bit = client.i_write(start, data)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn i_write(&mut self, start: u32, data: &[u8]) -> Result<(), Error> {
+        self.validate_connection_info().await?;
+        write_area_single(
+            self,
+            Area::ProcessInput,
+            S7WriteAccess::Bytes {
+                db_number: 0,
+                start,
+                data,
+            },
+        )
+        .await
+    }
+
+    /// Write a defined number of bytes into the 'output value area' of the PLC with a certain offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (start, data) = (10, &[0, 1]);
+    /// let bit = client.o_write(start, data)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn o_write(&mut self, start: u32, data: &[u8]) -> Result<(), Error> {
+        self.validate_connection_info().await?;
+        write_area_single(
+            self,
+            Area::ProcessOutput,
+            S7WriteAccess::Bytes {
+                db_number: 0,
+                start,
+                data,
+            },
+        )
+        .await
+    }
+
+    /// Write a defined number of bytes directly to the peripheral outputs (PA) of the PLC with a certain offset
+    ///
+    /// Unlike [`o_write`](Self::o_write), which writes into the process image of the outputs that the
+    /// PLC transfers to the modules at the end of its cycle (and might overwrite in the next cycle),
+    /// this writes to the output modules directly and takes effect immediately.
+    /// The connection has to be created with [`S7ClientBuilder::allow_direct_output_writes`](crate::S7ClientBuilder::allow_direct_output_writes).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// let mut client = S7Client::builder(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)
+    ///     .allow_direct_output_writes()
+    ///     .connect()
+    ///     .await?;
+    /// let (start, data) = (10, &[0, 1]);
+    /// client.pa_write(start, data)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DirectOutputWriteNotAllowed` if direct output writes were not enabled or `Error` if any errors occurred during writing.
+    pub async fn pa_write(&mut self, start: u32, data: &[u8]) -> Result<(), Error> {
+        if !self.config.allow_direct_output_writes {
+            return Err(Error::DirectOutputWriteNotAllowed);
+        }
+        self.validate_connection_info().await?;
+        write_area_single(
+            self,
+            Area::PeripheralIO,
+            S7WriteAccess::Bytes {
+                db_number: 0,
+                start,
+                data,
+            },
+        )
+        .await
+    }
+
+    /// Write a defined number of bytes to the outputs of the PLC with a certain offset, either into the process image or directly to the peripheral modules
+    ///
+    /// With [`IoMode::ProcessImage`] this is the same as [`o_write`](Self::o_write), with
+    /// [`IoMode::Peripheral`] the same as [`pa_write`](Self::pa_write), which has to be enabled
+    /// with [`S7ClientBuilder::allow_direct_output_writes`](crate::S7ClientBuilder::allow_direct_output_writes).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{IoMode, S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let (start, data) = (10, &[0, 1]);
+    /// client.io_write(IoMode::ProcessImage, start, data)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DirectOutputWriteNotAllowed` if direct output writes were not enabled for [`IoMode::Peripheral`] or `Error` if any errors occurred during writing.
+    pub async fn io_write(&mut self, mode: IoMode, start: u32, data: &[u8]) -> Result<(), Error> {
+        match mode {
+            IoMode::ProcessImage => self.o_write(start, data).await,
+            IoMode::Peripheral => self.pa_write(start, data).await,
+        }
+    }
+
+    /// Write bytes or a bit into any area of the PLC
+    ///
+    /// Generic variant of the area specific writing functions, e.g. for areas chosen at runtime.
+    /// The data block number of the access is ignored for areas other than `Area::DataBlock`.
+    /// Writing to `Area::PeripheralIO` has to be enabled like for [`pa_write`](Self::pa_write).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Area, S7Client, S7Types, S7WriteAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// client.write(Area::Merker, S7WriteAccess::bytes(0, 10, &[0, 1]))
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DirectOutputWriteNotAllowed` if direct output writes were not enabled for `Area::PeripheralIO` or `Error` if any errors occurred during writing.
+    pub async fn write(&mut self, area: Area, access: S7WriteAccess<'_>) -> Result<(), Error> {
+        if area == Area::PeripheralIO && !self.config.allow_direct_output_writes {
+            return Err(Error::DirectOutputWriteNotAllowed);
+        }
+        verify_max_bit(access.max_bit())?;
+        self.validate_connection_info().await?;
+        write_area_single(self, area, access).await
+    }
+
+    /// Write bytes or a bit into any area of the PLC, announcing the data with the given transport size
+    ///
+    /// Like [`write`](Self::write), but overrides the transport size of the data item that is
+    /// otherwise derived from the access, e.g. for servers that insist on a specific size for a field.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Area, S7Client, S7Types, S7WriteAccess, TransportSize};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let access = S7WriteAccess::bytes(100, 10, &[0x01, 0x02]);
+    /// client.write_with_transport_size(Area::DataBlock, access, TransportSize::Integer)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DirectOutputWriteNotAllowed` if direct output writes were not enabled for `Area::PeripheralIO` or `Error` if any errors occurred during writing.
+    pub async fn write_with_transport_size(
+        &mut self,
+        area: Area,
+        access: S7WriteAccess<'_>,
+        transport_size: TransportSize,
+    ) -> Result<(), Error> {
+        if area == Area::PeripheralIO && !self.config.allow_direct_output_writes {
+            return Err(Error::DirectOutputWriteNotAllowed);
+        }
+        verify_max_bit(access.max_bit())?;
+        self.validate_connection_info().await?;
+        write_area_single_with(self, area, access, Some(transport_size)).await
+    }
+
+    /// Read the first `input_len` bytes of the 'input value area' and write the given regions of the 'output value area'
+    ///
+    /// This is the classic I/O exchange of a gateway synchronized to the PLC cycle: the inputs are
+    /// read with one request and all `outputs` (start byte and data) are written with a second one.
+    /// These are two separate S7 operations, so the exchange is not atomic: the PLC may complete a
+    /// cycle between reading the inputs and writing the outputs.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Client, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut client = S7Client::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200).await?;
+    /// let inputs = client.exchange_io(8, &[(0, vec![0x01]), (4, vec![0x00, 0xFF])])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::TooManyItemsInOneRequest` or `Error::TooMuchDataToWrite` if the outputs do not fit into one request
+    /// or `Error` if any errors occurred during reading or writing.
+    pub async fn exchange_io(
+        &mut self,
+        input_len: u16,
+        outputs: &[(u32, Vec<u8>)],
+    ) -> Result<Vec<u8>, Error> {
+        let inputs = self.i_read(0, input_len).await?;
+        if outputs.is_empty() {
+            return Ok(inputs);
+        }
+
+        let accesses = outputs
+            .iter()
+            .map(|(start, data)| S7WriteAccess::bytes(0, *start, data))
+            .collect::<Vec<_>>();
+        self.validate_connection_info().await?;
+        for result in write_area_multi(self, Area::ProcessOutput, &accesses).await? {
+            result?;
+        }
+        Ok(inputs)
+    }
+}
+
+/// *Methods for writing data into the PLC device*
+impl S7Pool {
+    /// Write a defined number bytes into a specified data block with an offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, data) = (100, 0, &[0, 1, 2, 3]);
+    /// let data = pool.db_write(data_block, offset, data)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn db_write(&self, db_number: u16, start: u32, data: &[u8]) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_write(db_number, start, data).await
+    }
+
+    /// Write a defined number bytes into a specified data block with an offset before a deadline
+    ///
+    /// The deadline bounds the whole operation, including waiting for a free pooled connection.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use std::time::Duration;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let deadline = tokio::time::Instant::now() + Duration::from_millis(500);
+    /// pool.db_write_until(100, 0, &[0, 1, 2, 3], deadline)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DataExchangeTimedOut` if the deadline passed or `Error` if any other errors occurred during writing.
+    pub async fn db_write_until(
+        &self,
+        db_number: u16,
+        start: u32,
+        data: &[u8],
+        deadline: Instant,
+    ) -> Result<(), Error> {
+        let mut connection = timeout_at(deadline, self.0.get())
+            .await
+            .map_err(|_| Error::DataExchangeTimedOut)??;
+        connection
+            .db_write_until(db_number, start, data, deadline)
+            .await
+    }
+
+    /// Write a specific bit to a specified data block
+    ///
+    /// The bit number must be within the range 0..7
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, byte, bit, value) = (100, 0, 0, false);
+    /// let bit = pool.db_write_bit(data_block, byte, bit, value)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn db_write_bit(
+        &self,
+        db_number: u16,
+        byte: u32,
+        bit: u8,
+        value: bool,
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_write_bit(db_number, byte, bit, value).await
+    }
+
+    /// Write consecutive bits into a specified data block, e.g. an `ARRAY of BOOL`
+    ///
+    /// The bytes covering the bits are read, modified and written back on the same connection.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// // DB100.DBX4.6 to DB100.DBX5.1
+    /// pool.db_write_bits(100, 4, 6, &[true, false, true, true])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::RequestedBitOutOfRange` if `start_bit` is larger than 7 or `Error` if any errors occurred during reading or writing.
+    pub async fn db_write_bits(
+        &self,
+        db_number: u16,
+        start_byte: u32,
+        start_bit: u8,
+        values: &[bool],
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection
+            .db_write_bits(db_number, start_byte, start_bit, values)
+            .await
+    }
+
+    /// Write an unsigned value packed into `bit_width` bits of a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// // DB100.DBX4.2 to DB100.DBX4.4
+    /// pool.db_write_bitfield(100, 4, 2, 3, 5)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::RequestedBitOutOfRange` if the bitfield is empty or covers more than two bytes,
+    /// `Error::Conversion` if `value` does not fit into `bit_width` bits or `Error` if any errors occurred during reading or writing.
+    pub async fn db_write_bitfield(
+        &self,
+        db_number: u16,
+        byte: u32,
+        start_bit: u8,
+        bit_width: u8,
+        value: u32,
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+
+        connection
+            .db_write_bitfield(db_number, byte, start_bit, bit_width, value)
+            .await
+    }
+
+    /// Write several bits of the same byte of a specified data block in one go
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// // set DB100.DBX4.0 and DB100.DBX4.5, reset DB100.DBX4.2
+    /// pool.db_write_bits_in_byte(100, 4, &[(0, true), (2, false), (5, true)])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::RequestedBitOutOfRange` if any bit is larger than 7 or `Error` if any errors occurred during reading or writing.
+    pub async fn db_write_bits_in_byte(
+        &self,
+        db_number: u16,
+        byte: u32,
+        updates: &[(u8, bool)],
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+
+        connection
+            .db_write_bits_in_byte(db_number, byte, updates)
+            .await
+    }
+
+    /// Pack 16 bits into a WORD and write it into a specified data block, e.g. a control register
+    ///
+    /// Index `n` of `flags` provides bit `n` of the WORD.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let mut flags = [false; 16];
+    /// flags[0] = true;
+    /// // DB100.DBW4
+    /// pool.db_write_word_flags(100, 4, &flags)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn db_write_word_flags(
+        &self,
+        db_number: u16,
+        byte: u32,
+        flags: &[bool; 16],
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_write_word_flags(db_number, byte, flags).await
+    }
+
+    /// Write multiple bytes or bits to different locations of the PLC
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types, S7WriteAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let data = pool.db_write_multi(&[
+    ///        S7WriteAccess::bytes(100, 0, &[0, 0, 0, 1]),
+    ///        S7WriteAccess::bit(101, 0, 1, true),
+    ///    ])
+    ///    .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn db_write_multi(
+        &self,
+        info: &[S7WriteAccess<'_>],
+    ) -> Result<Vec<Result<(), Error>>, Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_write_multi(info).await
+    }
+
+    /// Encode a text with the given encoding and write it as `ARRAY of CHAR` into a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Encoding, S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, text) = (100, 0, "Pump 1");
+    /// pool.db_write_char_array(data_block, offset, text, Encoding::Ascii)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if the text can not be represented in the chosen encoding or if any errors occurred during writing.
+    pub async fn db_write_char_array(
+        &self,
+        db_number: u16,
+        start: u32,
+        text: &str,
+        encoding: Encoding,
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection
+            .db_write_char_array(db_number, start, text, encoding)
+            .await
+    }
+
+    /// Write a text as fixed Latin-1 `ARRAY of CHAR` into a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, text) = (100, 0, "Größe 1");
+    /// pool.db_write_chars(data_block, offset, text)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the text contains characters outside of Latin-1 or `Error` if any errors occurred during writing.
+    pub async fn db_write_chars(
+        &self,
+        db_number: u16,
+        start: u32,
+        text: &str,
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_write_chars(db_number, start, text).await
+    }
+
+    /// Write an `ARRAY of INT` into a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset) = (100, 0);
+    /// pool.db_write_int_array(data_block, offset, &[-5, 0, 120])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn db_write_int_array(
+        &self,
+        db_number: u16,
+        start: u32,
+        values: &[i16],
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection
+            .db_write_int_array(db_number, start, values)
+            .await
+    }
+
+    /// Write an `ARRAY of DINT` into a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset) = (100, 0);
+    /// pool.db_write_dint_array(data_block, offset, &[-100_000, 0, 250_000])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn db_write_dint_array(
+        &self,
+        db_number: u16,
+        start: u32,
+        values: &[i32],
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection
+            .db_write_dint_array(db_number, start, values)
+            .await
+    }
+
+    /// Write an `ARRAY of REAL` into a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset) = (100, 0);
+    /// pool.db_write_real_array(data_block, offset, &[1.5, -0.25, 100.0])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn db_write_real_array(
+        &self,
+        db_number: u16,
+        start: u32,
+        values: &[f32],
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection
+            .db_write_real_array(db_number, start, values)
+            .await
+    }
+
+    /// Write a date and time as `DTL` (12 bytes) into a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # use s7client::time::S7DateTime;
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71500)?;
+    /// let timestamp = S7DateTime::new(2024, 5, 17, 8, 30, 0, 250_000_000)?;
+    /// pool.db_write_dtl(100, 0, &timestamp)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the date and time is invalid or outside of the years 1970 to 2262
+    /// or `Error` if any errors occurred during writing.
+    pub async fn db_write_dtl(
+        &self,
+        db_number: u16,
+        start: u32,
+        date_time: &S7DateTime,
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_write_dtl(db_number, start, date_time).await
+    }
+
+    /// Divide `value` by `scale` and write it as integer into a specified data block
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{IntType, S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S7300)?;
+    /// pool.db_write_fixed(100, 4, IntType::Int, 0.1, 23.5)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the scale is zero or not finite or the rounded value does not fit
+    /// into the integer type or `Error` if any errors occurred during writing.
+    pub async fn db_write_fixed(
+        &self,
+        db_number: u16,
+        byte: u32,
+        int_type: IntType,
+        scale: f64,
+        value: f64,
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection
+            .db_write_fixed(db_number, byte, int_type, scale, value)
+            .await
+    }
+
+    /// Fill a region of a specified data block with copies of a single byte
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (data_block, offset, length) = (100, 0, 500);
+    /// pool.db_fill(data_block, offset, length, 0x00)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn db_fill(
+        &self,
+        db_number: u16,
+        start: u32,
+        length: u32,
+        value: u8,
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.db_fill(db_number, start, length, value).await
+    }
+
+    /// Write a defined number of bytes to the 'Merker area' of the PLC with a certain offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (start, data) = (10, &[0, 1]);
+    /// let bit = pool.mb_write(start, data)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn mb_write(&self, start: u32, data: &[u8]) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.mb_write(start, data).await
+    }
+
+    /// Write a defined number of bytes into the 'input value area' of the PLC with a certain offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (start, data) = (10, &[0, 1]);
+    /// let bit = pool.i_write(start, data)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn i_write(&self, start: u32, data: &[u8]) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.i_write(start, data).await
+    }
+
+    /// Write a defined number of bytes into the 'output value area' of the PLC with a certain offset
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (start, data) = (10, &[0, 1]);
+    /// let bit = pool.o_write(start, data)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error` if any errors occurred during writing.
+    pub async fn o_write(&self, start: u32, data: &[u8]) -> Result<(), Error> {
         let mut connection = self.0.get().await?;
         connection.o_write(start, data).await
     }
+
+    /// Write a defined number of bytes to the outputs of the PLC with a certain offset, either into the process image or directly to the peripheral modules
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{IoMode, S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let (start, data) = (10, &[0, 1]);
+    /// pool.io_write(IoMode::ProcessImage, start, data)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DirectOutputWriteNotAllowed` if direct output writes were not enabled for [`IoMode::Peripheral`] or `Error` if any errors occurred during writing.
+    pub async fn io_write(&self, mode: IoMode, start: u32, data: &[u8]) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.io_write(mode, start, data).await
+    }
+
+    /// Write bytes or a bit into any area of the PLC
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Area, S7Pool, S7Types, S7WriteAccess};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// pool.write(Area::Merker, S7WriteAccess::bytes(0, 10, &[0, 1]))
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DirectOutputWriteNotAllowed` if direct output writes were not enabled for `Area::PeripheralIO` or `Error` if any errors occurred during writing.
+    pub async fn write(&self, area: Area, access: S7WriteAccess<'_>) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection.write(area, access).await
+    }
+
+    /// Write bytes or a bit into any area of the PLC, announcing the data with the given transport size
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{Area, S7Pool, S7Types, S7WriteAccess, TransportSize};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let access = S7WriteAccess::bytes(100, 10, &[0x01, 0x02]);
+    /// pool.write_with_transport_size(Area::DataBlock, access, TransportSize::Integer)
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::DirectOutputWriteNotAllowed` if direct output writes were not enabled for `Area::PeripheralIO` or `Error` if any errors occurred during writing.
+    pub async fn write_with_transport_size(
+        &self,
+        area: Area,
+        access: S7WriteAccess<'_>,
+        transport_size: TransportSize,
+    ) -> Result<(), Error> {
+        let mut connection = self.0.get().await?;
+        connection
+            .write_with_transport_size(area, access, transport_size)
+            .await
+    }
+
+    /// Read the first `input_len` bytes of the 'input value area' and write the given regions of the 'output value area'
+    ///
+    /// These are two separate S7 operations, so the exchange is not atomic.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use std::net::Ipv4Addr;
+    /// # use s7client::{S7Pool, S7Types};
+    /// # tokio_test::block_on(async {
+    /// # let mut pool = S7Pool::new(Ipv4Addr::new(192, 168, 10, 72), S7Types::S71200)?;
+    /// let inputs = pool.exchange_io(8, &[(0, vec![0x01]), (4, vec![0x00, 0xFF])])
+    ///     .await?;
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// });
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::TooManyItemsInOneRequest` or `Error::TooMuchDataToWrite` if the outputs do not fit into one request
+    /// or `Error` if any errors occurred during reading or writing.
+    pub async fn exchange_io(
+        &self,
+        input_len: u16,
+        outputs: &[(u32, Vec<u8>)],
+    ) -> Result<Vec<u8>, Error> {
+        let mut connection = self.0.get().await?;
+        connection.exchange_io(input_len, outputs).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::{Error, IsoError};
+    use crate::mock::MockPlc;
+    use crate::time::S7DateTime;
+    use crate::{Area, IntType, S7WriteAccess, TransportSize};
+
+    const AREA_PERIPHERAL: u8 = 0x80;
+    const AREA_INPUTS: u8 = 0x81;
+    const AREA_OUTPUTS: u8 = 0x82;
+
+    #[tokio::test]
+    async fn pa_write_requires_guard() {
+        let plc = MockPlc::start().await;
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.pa_write(0, &[0xFF]).await,
+            Err(Error::DirectOutputWriteNotAllowed)
+        );
+        // nothing but the negotiation was sent
+        assert_eq!(plc.state().requests.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn pa_write_targets_peripheral_area() {
+        let plc = MockPlc::start().await;
+        plc.state().areas.insert((AREA_PERIPHERAL, 0), vec![0; 4]);
+        let mut client = plc
+            .builder()
+            .allow_direct_output_writes()
+            .connect()
+            .await
+            .unwrap();
+
+        client.pa_write(2, &[0x01, 0x80]).await.unwrap();
+
+        let request = plc.state().requests[1].clone();
+        assert_eq!(request[10], 0x05);
+        assert_eq!(request[20], AREA_PERIPHERAL);
+        assert_eq!(
+            plc.state().areas[&(AREA_PERIPHERAL, 0)],
+            [0x00, 0x00, 0x01, 0x80]
+        );
+    }
+
+    #[tokio::test]
+    async fn dry_run_records_write_without_sending() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0; 4]);
+        let mut client = plc.client().await;
+        client.set_dry_run(true);
+
+        client.db_write(1, 2, &[0xAA, 0xBB]).await.unwrap();
+        assert_eq!(client.db_read(1, 0, 3).await, Ok(vec![0x00; 3]));
+
+        let requests = client.take_dry_run_requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0],
+            [
+                0x03, 0x00, 0x00, 0x25, // TPKT header
+                0x02, 0xF0, 0x80, // COTP header
+                0x32, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x0E, 0x00, 0x06, // S7 header
+                0x05, 0x01, 0x12, 0x0A, 0x10, 0x02, 0x00, 0x02, 0x00, 0x01, 0x84, 0x00, 0x00,
+                0x10, // write parameters
+                0x00, 0x04, 0x00, 0x10, 0xAA, 0xBB, // data item
+            ]
+        );
+        // nothing but the negotiation was sent
+        assert_eq!(plc.state().requests.len(), 1);
+        assert_eq!(plc.db(1), [0; 4]);
+
+        client.set_dry_run(false);
+        client.db_write(1, 2, &[0xAA, 0xBB]).await.unwrap();
+        assert_eq!(plc.db(1), [0x00, 0x00, 0xAA, 0xBB]);
+    }
+
+    #[tokio::test]
+    async fn int_array_round_trip() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0; 20]);
+        let mut client = plc.client().await;
+        let values: Vec<i16> = (-5..5).map(|i| i * 1000).collect();
+
+        client.db_write_int_array(1, 0, &values).await.unwrap();
+
+        assert_eq!(&plc.db(1)[..2], (-5000_i16).to_be_bytes());
+        assert_eq!(client.db_read_int_array(1, 0, 10).await, Ok(values));
+    }
+
+    #[tokio::test]
+    async fn chars_are_latin1() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00; 6]);
+        let mut client = plc.client().await;
+
+        client.db_write_chars(1, 1, "Größe").await.unwrap();
+
+        assert_eq!(plc.db(1), [0x00, 0x47, 0x72, 0xF6, 0xDF, 0x65]);
+        assert_eq!(client.db_read_chars(1, 1, 5).await.unwrap(), "Größe");
+        assert!(matches!(
+            client.db_write_chars(1, 1, "5 €").await,
+            Err(Error::Conversion(_))
+        ));
+        assert_eq!(plc.db(1)[1], 0x47);
+    }
+
+    #[tokio::test]
+    async fn real_array_round_trip() {
+        let plc = MockPlc::start().await;
+        plc.state().pdu_length = 240;
+        plc.set_db(1, &[0; 200]);
+        let mut client = plc.client().await;
+        #[allow(clippy::cast_precision_loss)]
+        let values: Vec<f32> = (-25..25).map(|i| i as f32 * 0.5).collect();
+
+        client.db_write_real_array(1, 0, &values).await.unwrap();
+
+        assert_eq!(&plc.db(1)[..4], (-12.5_f32).to_be_bytes());
+        assert_eq!(client.db_read_real_array(1, 0, 50).await, Ok(values));
+    }
+
+    #[tokio::test]
+    async fn dtl_round_trip() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0; 14]);
+        let mut client = plc.client().await;
+        let date_time = S7DateTime::new(2024, 5, 17, 8, 30, 59, 123_456_789).unwrap();
+
+        client.db_write_dtl(1, 2, &date_time).await.unwrap();
+
+        assert_eq!(
+            plc.db(1)[2..],
+            [0x07, 0xE8, 5, 17, 6, 8, 30, 59, 0x07, 0x5B, 0xCD, 0x15]
+        );
+        assert_eq!(client.db_read_dtl(1, 2).await, Ok(date_time));
+
+        let too_early = S7DateTime::new(1969, 12, 31, 23, 59, 59, 0).unwrap();
+        assert!(matches!(
+            client.db_write_dtl(1, 2, &too_early).await,
+            Err(Error::Conversion(_))
+        ));
+        assert_eq!(client.db_read_dtl(1, 2).await, Ok(date_time));
+    }
+
+    #[tokio::test]
+    async fn fixed_point_round_trip() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00, 0xEB, 0x00, 0x00]);
+        let mut client = plc.client().await;
+
+        assert_eq!(
+            client.db_read_fixed(1, 0, IntType::Int, 0.1).await,
+            Ok(23.5)
+        );
+
+        client
+            .db_write_fixed(1, 2, IntType::Int, 0.1, -1.25)
+            .await
+            .unwrap();
+        assert_eq!(plc.db(1)[2..], (-13_i16).to_be_bytes());
+        assert!(matches!(
+            client.db_write_fixed(1, 2, IntType::Byte, 1.0, 256.0).await,
+            Err(Error::Conversion(_))
+        ));
+        assert_eq!(plc.db(1)[2..], (-13_i16).to_be_bytes());
+    }
+
+    #[tokio::test]
+    async fn dint_array_round_trip() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0; 40]);
+        let mut client = plc.client().await;
+        let values: Vec<i32> = (-5..5).map(|i| i * 1_000_000).collect();
+
+        client.db_write_dint_array(1, 0, &values).await.unwrap();
+
+        assert_eq!(&plc.db(1)[..4], (-5_000_000_i32).to_be_bytes());
+        assert_eq!(client.db_read_dint_array(1, 0, 10).await, Ok(values));
+        assert_eq!(
+            client.db_read_dint_array(1, 0, 20_000).await,
+            Err(Error::DataItemTooLarge)
+        );
+    }
+
+    #[tokio::test]
+    async fn write_bits_keeps_other_bits() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00, 0b0011_1111, 0b1111_0000, 0x00]);
+        let mut client = plc.client().await;
+
+        client
+            .db_write_bits(1, 1, 6, &[true, false, true, true, false, true])
+            .await
+            .unwrap();
+
+        assert_eq!(plc.db(1), [0x00, 0b0111_1111, 0b1111_1011, 0x00]);
+        assert_eq!(
+            client.db_write_bits(1, 0, 8, &[true]).await,
+            Err(Error::RequestedBitOutOfRange)
+        );
+    }
+
+    #[tokio::test]
+    async fn bits_in_byte_are_written_at_once() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00, 0b1100_0110, 0xFF]);
+        let mut client = plc.client().await;
+
+        client
+            .db_write_bits_in_byte(1, 1, &[(0, true), (2, false), (5, true)])
+            .await
+            .unwrap();
+
+        assert_eq!(plc.db(1), [0x00, 0b1110_0011, 0xFF]);
+        // one read and one write
+        assert_eq!(client.requests_issued(), 2);
+        assert_eq!(
+            client
+                .db_write_bits_in_byte(1, 1, &[(0, false), (8, true)])
+                .await,
+            Err(Error::RequestedBitOutOfRange)
+        );
+        assert_eq!(plc.db(1), [0x00, 0b1110_0011, 0xFF]);
+
+        plc.state().read_length_limit = Some(0);
+        assert!(matches!(
+            client.db_write_bits_in_byte(1, 1, &[(0, false)]).await,
+            Err(Error::Conversion(_))
+        ));
+        assert_eq!(plc.db(1), [0x00, 0b1110_0011, 0xFF]);
+    }
+
+    #[tokio::test]
+    async fn bitfield_round_trip() {
+        let plc = MockPlc::start().await;
+        // 3-bit mode at bits 2 to 4 between set bits
+        plc.set_db(1, &[0x00, 0b1110_0011]);
+        let mut client = plc.client().await;
+        assert_eq!(client.db_read_bitfield(1, 1, 2, 3).await, Ok(0));
+
+        client.db_write_bitfield(1, 1, 2, 3, 0b101).await.unwrap();
+
+        assert_eq!(plc.db(1), [0x00, 0b1111_0111]);
+        assert_eq!(client.db_read_bitfield(1, 1, 2, 3).await, Ok(0b101));
+        assert!(matches!(
+            client.db_write_bitfield(1, 1, 2, 3, 8).await,
+            Err(Error::Conversion(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn write_with_overridden_transport_size() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00; 4]);
+        let mut client = plc.client().await;
+
+        let access = S7WriteAccess::bytes(1, 2, &[0x12, 0x34]);
+        client.write(Area::DataBlock, access).await.unwrap();
+        client
+            .write_with_transport_size(Area::DataBlock, access, TransportSize::Integer)
+            .await
+            .unwrap();
+
+        // data item follows the header and the parameters of the single request item
+        let requests = plc.state().requests.clone();
+        let data_items = requests[requests.len() - 2..]
+            .iter()
+            .map(|pdu| pdu[24..28].to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(data_items[0], [0x00, 0x04, 0x00, 0x10]);
+        assert_eq!(data_items[1], [0x00, 0x05, 0x00, 0x10]);
+        assert_eq!(plc.db(1), [0x00, 0x00, 0x12, 0x34]);
+    }
+
+    #[tokio::test]
+    async fn write_multi_is_split_into_requests() {
+        let plc = MockPlc::start().await;
+        plc.state().pdu_length = 240;
+        plc.set_db(1, &[0x00; 200]);
+        let mut client = plc.client().await;
+
+        // 10 items of 20 bytes exceed the data limit of one request
+        let data = (0..10_u8).map(|i| [i + 1; 20]).collect::<Vec<_>>();
+        let accesses = (0..10_u32)
+            .zip(&data)
+            .map(|(i, data)| S7WriteAccess::bytes(1, 20 * i, data))
+            .collect::<Vec<_>>();
+        let results = client.db_write_multi(&accesses).await.unwrap();
+
+        assert_eq!(results.len(), 10);
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(client.requests_issued(), 2);
+        assert_eq!(plc.db(1), data.concat());
+    }
+
+    #[tokio::test]
+    async fn write_multi_rejects_missing_results() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00; 4]);
+        let mut client = plc.client().await;
+        let accesses = [
+            S7WriteAccess::bytes(1, 0, &[0x01, 0x02]),
+            S7WriteAccess::bytes(1, 2, &[0x03, 0x04]),
+        ];
+
+        // fewer results than written items
+        plc.state().write_item_count = Some(1);
+        assert!(matches!(
+            client.db_write_multi(&accesses).await,
+            Err(Error::TryFrom(..))
+        ));
+
+        // response cut off after the first result
+        plc.state().write_item_count = None;
+        plc.state().write_result_limit = Some(1);
+        assert_eq!(
+            client.db_write_multi(&accesses).await,
+            Err(Error::ISOResponse(IsoError::ShortPacket))
+        );
+    }
+
+    #[tokio::test]
+    async fn fill_is_split_into_requests() {
+        let plc = MockPlc::start().await;
+        plc.state().pdu_length = 240;
+        plc.set_db(1, &[0x00; 504]);
+        let mut client = plc.client().await;
+
+        client.db_fill(1, 2, 500, 0xAA).await.unwrap();
+
+        assert!(client.requests_issued() > 1);
+        assert_eq!(client.db_read(1, 2, 500).await, Ok(vec![0xAA; 500]));
+        let db = plc.db(1);
+        assert_eq!(db[..2], [0x00; 2]);
+        assert_eq!(db[502..], [0x00; 2]);
+    }
+
+    #[tokio::test]
+    async fn word_flags_round_trip() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0x00, 0xA5, 0xA5, 0x00]);
+        let mut client = plc.client().await;
+
+        let flags = client.db_read_word_flags(1, 1).await.unwrap();
+        // 0xA5A5 = 1010_0101 1010_0101, bit 0 first
+        let expected = [
+            true, false, true, false, false, true, false, true, true, false, true, false, false,
+            true, false, true,
+        ];
+        assert_eq!(flags, expected);
+
+        client.db_write_word_flags(1, 2, &flags).await.unwrap();
+        assert_eq!(plc.db(1), [0x00, 0xA5, 0xA5, 0xA5]);
+    }
+
+    #[tokio::test]
+    async fn short_read_is_an_error() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0xA5; 8]);
+        plc.state().read_length_limit = Some(1);
+        let mut client = plc.client().await;
+
+        assert!(matches!(
+            client.db_read_word_flags(1, 0).await,
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            client.db_read(1, 0, 4).await,
+            Err(Error::Conversion(_))
+        ));
+        assert_eq!(client.db_read(1, 0, 1).await, Ok(vec![0xA5]));
+    }
+
+    #[tokio::test]
+    async fn exchange_io_reads_inputs_and_writes_outputs() {
+        let plc = MockPlc::start().await;
+        let inputs = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        plc.state().areas.insert((AREA_INPUTS, 0), inputs.to_vec());
+        plc.state().areas.insert((AREA_OUTPUTS, 0), vec![0; 8]);
+        let mut client = plc.client().await;
+
+        let result = client
+            .exchange_io(8, &[(0, vec![0xAA]), (4, vec![0xBB, 0xCC])])
+            .await;
+
+        assert_eq!(result, Ok(inputs.to_vec()));
+        // one read and one write request
+        assert_eq!(client.requests_issued(), 2);
+        assert_eq!(
+            plc.state().areas[&(AREA_OUTPUTS, 0)],
+            [0xAA, 0x00, 0x00, 0x00, 0xBB, 0xCC, 0x00, 0x00]
+        );
+    }
 }
@@ -39,13 +39,9 @@ pub(crate) enum ConnectionType {
     Basic = 3,
 }
 
-struct TSAPInfo {
-    rack: u8,
-    slot: u8,
-}
-
 /// Supported PLC devices from the S7 family
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum S7Types {
     /// Choose this if you want to connect to a S7 200
     S7200,
@@ -64,10 +60,10 @@ pub enum S7Types {
 }
 
 impl S7Types {
-    fn to_tsap_info(self) -> TSAPInfo {
+    pub(crate) fn default_rack_slot(self) -> (u8, u8) {
         match self {
-            Self::S7200 | Self::S7300 | Self::S7400 => TSAPInfo { rack: 0, slot: 2 },
-            Self::S71200 | Self::S71500 => TSAPInfo { rack: 0, slot: 0 },
+            Self::S7200 | Self::S7300 | Self::S7400 => (0, 2),
+            Self::S71200 | Self::S71500 => (0, 0),
         }
     }
 }
@@ -75,11 +71,9 @@ impl S7Types {
 struct Tsap {}
 impl Tsap {
     #[allow(clippy::cast_possible_truncation)]
-    fn build(s7_type: S7Types) -> Vec<u8> {
-        let tsap_info = s7_type.to_tsap_info();
-        let dst_tsap = ((ConnectionType::Basic as u16) << 8)
-            + (u16::from(tsap_info.rack) * 0x20)
-            + u16::from(tsap_info.slot);
+    fn build(rack: u8, slot: u8) -> Vec<u8> {
+        let dst_tsap =
+            ((ConnectionType::Basic as u16) << 8) + (u16::from(rack) * 0x20) + u16::from(slot);
         vec![
             0xC1,                  // code that identifies source TSAP
             2,                     // source TSAP Len
@@ -452,7 +446,7 @@ pub(super) struct IsoControlPDU {
 }
 
 impl IsoControlPDU {
-    pub(crate) fn build(pdu_size: u32, s7_type: S7Types) -> Self {
+    pub(crate) fn build(pdu_size: u32, rack: u8, slot: u8) -> Self {
         // Params length
         let par_len = 11_u8; // 2 Src TSAP (Code+field Len)      +
                              // 2 Src TSAP len                   +
@@ -478,7 +472,7 @@ impl IsoControlPDU {
                     // 2048 => 0x0B,
                     _ => 0x0B, // Our Default
                 },
-                tsap: Tsap::build(s7_type),
+                tsap: Tsap::build(rack, slot),
             },
             header_length: par_len + 6, // <-- 6 = 7 - 1 (COTP Header size - 1)
             pdu_type: PDU_TYPE_CR,      // Connection Request
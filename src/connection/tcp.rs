@@ -1,9 +1,12 @@
 use bytes::{BufMut, BytesMut};
 use std::convert::TryFrom;
+use std::future::poll_fn;
+use std::io::ErrorKind;
 use std::mem;
+use std::task::Poll;
 use std::time::Duration;
 // use std::net::TcpStream;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, ReadBuf};
 use tokio::net::TcpStream;
 use tokio::time::timeout;
 
@@ -12,16 +15,23 @@ use crate::connection::iso::{COTPDisconnect, IsoDisconnect};
 use crate::errors::{Error, IsoError};
 use crate::s7_protocol::negotiate::{NegotiatePDUParameters, S7Negotiation};
 use crate::s7_protocol::segments::header::S7ProtocolHeader;
-use crate::S7Types;
+use crate::trace::event;
 
-const DATA_SEND_AND_RECEIVE_TIMEOUT: Duration = Duration::from_secs(4);
+pub(crate) const DATA_SEND_AND_RECEIVE_TIMEOUT: Duration = Duration::from_secs(4);
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(tcp_client, exchange_timeout), err(Display))
+)]
 pub(crate) async fn connect(
     tcp_client: &mut TcpStream,
-    s7_type: S7Types,
+    rack: u8,
+    slot: u8,
+    requested_pdu_size: u16,
+    exchange_timeout: Duration,
 ) -> Result<NegotiatePDUParameters, Error> {
     // send connection request
-    let iso: Vec<u8> = IsoControlPDU::build(1024, s7_type).into();
+    let iso: Vec<u8> = IsoControlPDU::build(tpdu_size(requested_pdu_size), rack, slot).into();
     tcp_client.write_all(&iso).await?;
 
     // Get response TTPKT Header
@@ -31,7 +41,16 @@ pub(crate) async fn connect(
     let cotp_connection = COTPConnection::try_from(&mut tpkt_data)?;
     cotp_connection.req_ok()?;
 
-    negotiate_connection_params(tcp_client).await
+    negotiate_connection_params(tcp_client, requested_pdu_size, exchange_timeout).await
+}
+
+/// Smallest ISO TPDU size that carries a PDU of the requested size in a single TPDU, at least 1024 bytes
+fn tpdu_size(requested_pdu_size: u16) -> u32 {
+    let required = u32::from(requested_pdu_size) + u32::from(COTPData::len());
+    [1024, 2048, 4096, 8192]
+        .into_iter()
+        .find(|size| *size >= required)
+        .unwrap_or(8192)
 }
 
 pub(crate) async fn disconnect(tcp_client: &mut TcpStream) -> Result<(), Error> {
@@ -47,18 +66,79 @@ pub(crate) async fn disconnect(tcp_client: &mut TcpStream) -> Result<(), Error>
     Ok(())
 }
 
+/// Send a disconnect request without waiting for the socket or the response of the PLC
+///
+/// This is a best effort for situations where nothing can be awaited, e.g. when dropping a client.
+pub(crate) fn disconnect_now(tcp_client: &TcpStream) {
+    let iso: Vec<u8> = IsoDisconnect::build().into();
+    let _ = tcp_client.try_write(&iso);
+}
+
+/// Check without waiting whether the PLC closed the connection
+///
+/// Pending data of an idle connection is left in the socket, only a closed or reset connection
+/// is reported.
+pub(crate) async fn is_closed_by_peer(tcp_client: &TcpStream) -> bool {
+    let mut buffer = [0_u8; 1];
+    poll_fn(|cx| {
+        let mut buffer = ReadBuf::new(&mut buffer);
+        Poll::Ready(match tcp_client.poll_peek(cx, &mut buffer) {
+            Poll::Ready(Ok(0) | Err(_)) => true,
+            Poll::Ready(Ok(_)) | Poll::Pending => false,
+        })
+    })
+    .await
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(conn, exchange_timeout), err(Display))
+)]
 pub(crate) async fn negotiate_connection_params(
     conn: &mut TcpStream,
+    requested_pdu_size: u16,
+    exchange_timeout: Duration,
 ) -> Result<NegotiatePDUParameters, Error> {
-    let negotiation_params = BytesMut::from(S7Negotiation::build()?);
-    let mut exchanged_data = exchange_buffer(conn, negotiation_params).await?;
+    let negotiation_params = BytesMut::from(S7Negotiation::build(requested_pdu_size)?);
+    let mut exchanged_data = exchange_buffer(conn, negotiation_params, exchange_timeout).await?;
 
     S7ProtocolHeader::try_from(&mut exchanged_data)?.is_ack_with_data()?;
-    let params = NegotiatePDUParameters::try_from(&mut exchanged_data)?;
+    let mut params = NegotiatePDUParameters::try_from(&mut exchanged_data)?;
+    if !params.has_negotiate_function_code() {
+        return Err(Error::Connection(
+            "unexpected negotiation function code".to_string(),
+        ));
+    }
+    // a PLC accepting no unacknowledged request at all would stall every exchange, so
+    // fall back to sending one request at a time
+    if params.max_amq_caller == 0 {
+        event!(
+            warn,
+            "PLC negotiated a max AMQ (caller) of 0, sending requests one at a time"
+        );
+        params.max_amq_caller = 1;
+    }
+    event!(
+        debug,
+        pdu_length = params.pdu_length,
+        max_amq_caller = params.max_amq_caller,
+        max_amq_calle = params.max_amq_calle,
+        "negotiated connection parameters"
+    );
     Ok(params)
 }
 
 pub(crate) async fn send_buffer(conn: &mut TcpStream, data: BytesMut) -> Result<(), Error> {
+    let bytes = build_telegram(data)?;
+
+    // send data to plc
+    conn.write_all(&bytes).await?;
+
+    Ok(())
+}
+
+/// Wrap S7 data into TPKT and COTP headers
+pub(crate) fn build_telegram(data: BytesMut) -> Result<BytesMut, Error> {
     // Telegram length
     let iso_len = u16::from(TTPKTHeader::len())  // TPKT Header
                 + u16::from(COTPData::len())                       // COTP Header Size
@@ -76,13 +156,21 @@ pub(crate) async fn send_buffer(conn: &mut TcpStream, data: BytesMut) -> Result<
     // add data
     bytes.put(data);
 
-    // send data to plc
-    conn.write_all(&bytes).await?;
-
-    Ok(())
+    Ok(bytes)
 }
 
-pub(crate) async fn recv_buffer(conn: &mut TcpStream) -> Result<BytesMut, Error> {
+/// Read one S7 PDU from the connection
+///
+/// A PDU is carried by one or more TPKT telegrams, each holding a COTP data TPDU. The payloads of
+/// all TPDUs up to the one with the end-of-transmission flag are concatenated. Every telegram is
+/// read with exact reads of its header and its announced length, so the PDU is decoded identically
+/// however the bytes are fragmented by the transport, and no byte of a following telegram is consumed.
+///
+/// Returns `Error::ISOResponse(IsoError::ShortPacket)` if the connection ends within a telegram.
+pub(crate) async fn recv_buffer<R>(conn: &mut R) -> Result<BytesMut, Error>
+where
+    R: AsyncRead + Unpin,
+{
     let mut bytes = BytesMut::new();
     let mut is_last: bool = false;
 
@@ -100,35 +188,280 @@ pub(crate) async fn recv_buffer(conn: &mut TcpStream) -> Result<BytesMut, Error>
     Ok(bytes)
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(pdu_reference = ?S7ProtocolHeader::pdu_reference_of(&data).ok())
+    )
+)]
 pub(crate) async fn exchange_buffer(
     conn: &mut TcpStream,
     data: BytesMut,
+    exchange_timeout: Duration,
 ) -> Result<BytesMut, Error> {
-    // Send data to PLC with timeout
-    match timeout(DATA_SEND_AND_RECEIVE_TIMEOUT, send_buffer(conn, data)).await {
-        Ok(_) => {}
-        Err(_) => return Err(Error::DataExchangeTimedOut),
-    };
+    send_request(conn, data, exchange_timeout).await?;
+    receive_response(conn, exchange_timeout).await
+}
 
-    // Receive data from PLC with timeout
-    match timeout(DATA_SEND_AND_RECEIVE_TIMEOUT, recv_buffer(conn)).await {
-        Ok(data) => Ok(data?),
+/// Send data to PLC with timeout
+pub(crate) async fn send_request(
+    conn: &mut TcpStream,
+    data: BytesMut,
+    exchange_timeout: Duration,
+) -> Result<(), Error> {
+    event!(
+        debug,
+        bytes = data.len(),
+        pdu_reference = ?S7ProtocolHeader::pdu_reference_of(&data).ok(),
+        "sending request"
+    );
+    event!(trace, request = %crate::trace::Hex(&data));
+    match timeout(exchange_timeout, send_buffer(conn, data)).await {
+        Ok(result) => result,
         Err(_) => Err(Error::DataExchangeTimedOut),
     }
 }
 
-async fn read_tpkt_header(conn: &mut TcpStream) -> Result<TTPKTHeader, Error> {
-    // Get response TTPKT Header
-    let mut data = BytesMut::with_capacity(mem::size_of::<TTPKTHeader>());
-    conn.read_buf(&mut data).await?;
-    TTPKTHeader::try_from(&mut data)
+/// Receive data from PLC with timeout
+pub(crate) async fn receive_response(
+    conn: &mut TcpStream,
+    exchange_timeout: Duration,
+) -> Result<BytesMut, Error> {
+    let response = match timeout(exchange_timeout, recv_buffer(conn)).await {
+        Ok(data) => data,
+        Err(_) => Err(Error::DataExchangeTimedOut),
+    };
+    #[cfg(feature = "tracing")]
+    crate::trace::response(&response);
+    response
 }
 
-async fn read_tpkt_data(conn: &mut TcpStream, length: u16) -> Result<BytesMut, Error> {
-    let mut data = BytesMut::with_capacity(length as usize - mem::size_of::<TTPKTHeader>());
+async fn read_tpkt_header<R>(conn: &mut R) -> Result<TTPKTHeader, Error>
+where
+    R: AsyncRead + Unpin,
+{
+    // Get response TTPKT Header, which might arrive in multiple TCP segments
+    let mut data = BytesMut::zeroed(mem::size_of::<TTPKTHeader>());
+    match conn.read_exact(&mut data).await {
+        Ok(_) => TTPKTHeader::try_from(&mut data),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+            Err(Error::ISOResponse(IsoError::ShortPacket))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
 
-    match conn.read_buf(&mut data).await {
+async fn read_tpkt_data<R>(conn: &mut R, length: u16) -> Result<BytesMut, Error>
+where
+    R: AsyncRead + Unpin,
+{
+    let data_length = usize::from(length)
+        .checked_sub(mem::size_of::<TTPKTHeader>())
+        .ok_or(Error::ISOResponse(IsoError::InvalidDataSize))?;
+    let mut data = BytesMut::zeroed(data_length);
+
+    // the payload might arrive in multiple TCP segments
+    match conn.read_exact(&mut data).await {
         Ok(_) => Ok(data),
-        Err(_) => Err(Error::ISOResponse(IsoError::InvalidDataSize)),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+            Err(Error::ISOResponse(IsoError::ShortPacket))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::net::Ipv4Addr;
+    use std::time::Duration;
+
+    use bytes::BytesMut;
+
+    use super::{read_tpkt_data, read_tpkt_header, recv_buffer};
+    use crate::errors::{Error, IsoError};
+    use crate::mock::MockPlc;
+    use crate::{S7ClientBuilder, S7Types};
+
+    #[tokio::test]
+    async fn negotiation_rejects_wrong_function_code() {
+        let plc = MockPlc::start().await;
+        // answer the negotiation like a read job
+        plc.state().negotiation_function_code = 0x04;
+
+        let result = S7ClientBuilder::new(Ipv4Addr::LOCALHOST, S7Types::S71200)
+            .port(plc.addr.port())
+            .connect()
+            .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::Connection("unexpected negotiation function code".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn tpdu_size_covers_requested_pdu_size() {
+        let plc = MockPlc::start().await;
+
+        for (requested_pdu_size, tpdu_size_code) in [(480, 0x0A), (960, 0x0A), (1920, 0x0B)] {
+            plc.builder()
+                .requested_pdu_size(requested_pdu_size)
+                .connect()
+                .await
+                .unwrap();
+
+            let request = plc.state().connection_requests.last().unwrap().clone();
+            let position = request
+                .windows(2)
+                .position(|param| param == [0xC0, 0x01])
+                .unwrap();
+            assert_eq!(request[position + 2], tpdu_size_code);
+        }
+    }
+
+    #[tokio::test]
+    async fn tpkt_data_is_read_across_segments() {
+        let mut conn = tokio_test::io::Builder::new()
+            .read(&[0x02, 0xF0])
+            .wait(Duration::from_millis(10))
+            .read(&[0x80, 0x32, 0x03])
+            .read(&[0x00])
+            .build();
+
+        let data = read_tpkt_data(&mut conn, 10).await.unwrap();
+
+        assert_eq!(data.as_ref(), [0x02, 0xF0, 0x80, 0x32, 0x03, 0x00]);
+    }
+
+    #[tokio::test]
+    async fn tpkt_data_reports_short_packet() {
+        let mut conn = tokio_test::io::Builder::new().read(&[0x02, 0xF0]).build();
+
+        assert_eq!(
+            read_tpkt_data(&mut conn, 10).await,
+            Err(Error::ISOResponse(IsoError::ShortPacket))
+        );
+    }
+
+    #[tokio::test]
+    async fn tpkt_data_reports_connection_reset() {
+        let mut conn = tokio_test::io::Builder::new()
+            .read(&[0x02, 0xF0])
+            .read_error(io::Error::from(io::ErrorKind::ConnectionReset))
+            .build();
+
+        assert_eq!(
+            read_tpkt_data(&mut conn, 10).await,
+            Err(Error::from(io::Error::from(io::ErrorKind::ConnectionReset)))
+        );
+    }
+
+    #[tokio::test]
+    async fn io_error_keeps_message() {
+        let error = Error::from(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "binding port 102",
+        ));
+        assert_eq!(error.to_string(), "binding port 102");
+        assert_eq!(error, Error::IO(io::ErrorKind::PermissionDenied.into()));
+        assert_ne!(error, Error::IO(io::ErrorKind::ConnectionRefused.into()));
+
+        // errors while reading the payload keep their message as well
+        let mut conn = tokio_test::io::Builder::new()
+            .read(&[0x02, 0xF0])
+            .read_error(io::Error::new(
+                io::ErrorKind::ConnectionReset,
+                "reset by peer",
+            ))
+            .build();
+        let error = read_tpkt_data(&mut conn, 10).await.unwrap_err();
+        assert_eq!(error.to_string(), "reset by peer");
+        assert_eq!(error, Error::IO(io::ErrorKind::ConnectionReset.into()));
+    }
+
+    #[tokio::test]
+    async fn tpkt_header_is_read_across_segments() {
+        let mut conn = tokio_test::io::Builder::new()
+            .read(&[0x03, 0x00])
+            .wait(Duration::from_millis(10))
+            .read(&[0x00])
+            .read(&[0x16])
+            .build();
+
+        let header = read_tpkt_header(&mut conn).await.unwrap();
+
+        assert_eq!(header.length, 22);
+    }
+
+    #[tokio::test]
+    async fn tpkt_header_reports_short_packet() {
+        let mut conn = tokio_test::io::Builder::new().read(&[0x03, 0x00]).build();
+
+        assert!(matches!(
+            read_tpkt_header(&mut conn).await,
+            Err(Error::ISOResponse(IsoError::ShortPacket))
+        ));
+    }
+
+    // PDU split into two COTP data TPDUs, only the second one carries the end-of-transmission flag
+    const FRAGMENTED_PDU: [u8; 20] = [
+        0x03, 0x00, 0x00, 0x0A, 0x02, 0xF0, 0x00, 0x32, 0x03, 0x00, 0x03, 0x00, 0x00, 0x0A, 0x02,
+        0xF0, 0x80, 0x00, 0x01, 0x02,
+    ];
+
+    async fn receive_chunked(chunks: &[&[u8]]) -> Result<BytesMut, Error> {
+        let mut builder = tokio_test::io::Builder::new();
+        for chunk in chunks {
+            builder.read(chunk);
+        }
+        recv_buffer(&mut builder.build()).await
+    }
+
+    #[tokio::test]
+    async fn fragmentation_does_not_change_received_pdu() {
+        let expected = [0x32, 0x03, 0x00, 0x00, 0x01, 0x02];
+        let single_bytes = FRAGMENTED_PDU.chunks(1).collect::<Vec<_>>();
+        let patterns: [&[&[u8]]; 4] = [
+            // all at once
+            &[&FRAGMENTED_PDU],
+            // one byte at a time
+            &single_bytes,
+            // split within the header of the second telegram
+            &[&FRAGMENTED_PDU[..12], &FRAGMENTED_PDU[12..]],
+            // split within the COTP header and the payload
+            &[
+                &FRAGMENTED_PDU[..5],
+                &FRAGMENTED_PDU[5..8],
+                &FRAGMENTED_PDU[8..],
+            ],
+        ];
+
+        for chunks in patterns {
+            assert_eq!(receive_chunked(chunks).await.unwrap().as_ref(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn following_pdu_is_not_consumed() {
+        let mut data = FRAGMENTED_PDU.to_vec();
+        data.extend([0x03, 0x00, 0x00, 0x09, 0x02, 0xF0, 0x80, 0x32, 0x07]);
+        let mut conn = tokio_test::io::Builder::new().read(&data).build();
+
+        assert_eq!(
+            recv_buffer(&mut conn).await.unwrap().as_ref(),
+            [0x32, 0x03, 0x00, 0x00, 0x01, 0x02]
+        );
+        assert_eq!(recv_buffer(&mut conn).await.unwrap().as_ref(), [0x32, 0x07]);
+    }
+
+    #[tokio::test]
+    async fn pdu_ending_within_telegram_is_short_packet() {
+        assert_eq!(
+            receive_chunked(&[&FRAGMENTED_PDU[..15]]).await,
+            Err(Error::ISOResponse(IsoError::ShortPacket))
+        );
     }
 }
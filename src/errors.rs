@@ -1,17 +1,26 @@
 //! Types for working with errors produced by s7client.
 
 use std::fmt;
-use std::io::{Error as IOError, ErrorKind};
+use std::io::Error as IOError;
+use std::sync::Arc;
 
 use bb8::RunError;
 
-#[derive(Debug, PartialEq)]
+use crate::Area;
+
+#[derive(Debug)]
 /// Possible errors returned by `S7Client` or `S7Pool`
+///
+/// Two `Error::IO` are equal if their [`ErrorKind`](std::io::ErrorKind) is equal.
 pub enum Error {
     /// IO error during message exchange with PLC
-    IO(ErrorKind),
-    /// An error occurred while getting a connection from the pool
-    Pool(String),
+    IO(IOError),
+    /// An error occurred while getting a connection from the pool, e.g. waiting for a connection timed out
+    ///
+    /// Errors on establishing a new connection for the pool are returned as they are.
+    Pool(Box<dyn std::error::Error + Send + Sync>),
+    /// The pool is shutting down or was shut down, no connection is handed out anymore
+    PoolShuttingDown,
     /// Error on establishing connection to PLC
     Connection(String),
     /// Timeout during data exchange with PLC
@@ -23,6 +32,8 @@ pub enum Error {
     // ISORequest(IsoError),
     /// The requested bit is out of range (0 to 7)
     RequestedBitOutOfRange,
+    /// The request was aborted by the cancellation token of the connection
+    Cancelled,
     /// PLC did not acknowledge the request
     RequestNotAcknowledged,
     /// PLC reported an error
@@ -46,17 +57,70 @@ pub enum Error {
     },
     /// Creation of invalid Trigger Collection: Only Bit accesses are allowed inside a Trigger Collection.
     InvalidTriggerCollection,
+    /// Data could not be converted from or into the requested representation
+    Conversion(String),
+    /// Writing directly to the peripheral outputs was not enabled for the connection
+    DirectOutputWriteNotAllowed,
+    /// Starting or stopping the CPU was not enabled for the connection
+    PlcControlNotAllowed,
+    /// The PLC does not support the requested function
+    FunctionNotSupported,
+    /// The requested symbol is not in the symbol table
+    UnknownSymbol(String),
+    /// The requested address lies beyond the declared size of the data block, detected before sending the request
+    AddressOutOfRange {
+        /// Number of the data block
+        db_number: u16,
+        /// Byte offset of the requested address
+        offset: u32,
+    },
+    /// The schema of a data block has no layout for the version stored in the data block
+    UnknownSchemaVersion {
+        /// Number of the data block
+        db_number: u16,
+        /// Version stored in the data block
+        version: u8,
+    },
+    /// Accessing a specific data block failed
+    DataBlockAccess {
+        /// Number of the data block that could not be accessed
+        db_number: u16,
+        /// Cause of the failure
+        error: Box<Error>,
+    },
+    /// Reading was rejected because the CPU is read protected and no password is set
+    ReadProtected,
+    /// The clock of the PLC was never set and reports no valid date
+    ClockNotSet,
+    /// The PLC rejected setting its clock
+    ClockNotAccepted(S7ProtocolError),
+    /// The PLC rejected writing a specific item
+    WriteItemError {
+        /// Area of the rejected write
+        area: Area,
+        /// Number of the data block of the rejected write (0 for other areas)
+        db_number: u16,
+        /// Byte offset of the rejected write
+        byte: u32,
+        /// Bit of the rejected write, if a single bit was written
+        bit: Option<u8>,
+        /// Error code returned by the PLC
+        error: S7DataItemResponseError,
+    },
 }
 
 impl From<IOError> for Error {
     fn from(e: IOError) -> Self {
-        Error::IO(e.kind())
+        Error::IO(e)
     }
 }
 
 impl From<RunError<Error>> for Error {
     fn from(e: RunError<Error>) -> Self {
-        Error::Pool(e.to_string())
+        match e {
+            RunError::User(e) => e,
+            RunError::TimedOut => Error::Pool(Box::new(e)),
+        }
     }
 }
 
@@ -66,16 +130,18 @@ impl fmt::Display for Error {
             f,
             "{}",
             match self {
-                Error::IO(e) => format!("IO Error: {e}"),
-                Error::Pool(e) => format!("Pool Error: {e}"),
+                Error::IO(e) => e.to_string(),
+                Error::Pool(_) => "Pool Error".to_string(),
+                Error::PoolShuttingDown => "The pool is shutting down".to_string(),
                 Error::Connection(e) => format!("Connection Error: {e}"),
                 Error::DataExchangeTimedOut => "Timeout during data exchange".to_string(),
-                Error::TryFrom(_, e) => e.to_string(),
-                Error::ISOResponse(e) => format!("ISO Response Error: {e}"),
+                Error::TryFrom(_, e) => e.clone(),
+                Error::ISOResponse(_) => "ISO Response Error".to_string(),
                 // Error::ISORequest(e) => format!("ISO Request Error: {e}"),
                 Error::RequestedBitOutOfRange =>
                     "The request bit is out of range [0..7]".to_string(),
                 Error::RequestNotAcknowledged => "The PLC did not respond successfully".to_string(),
+                Error::Cancelled => "The request was cancelled".to_string(),
                 Error::S7ProtocolError(e) => e.to_string(),
                 Error::DataItemError(e) => e.to_string(),
                 Error::ResponseDoesNotBelongToCurrentPDU =>
@@ -85,13 +151,140 @@ impl fmt::Display for Error {
                 Error::TooMuchDataToWrite =>
                     "Too much data supplied for one write request".to_string(),
                     Error::ResponseDataWouldBeTooLarge { req_size, max_pdu } => format!("Too much data requested for one read request. Response size ({req_size}) is larger than the protocol limit ({max_pdu})"),
-                Error::InvalidTriggerCollection => "Error on creating Trigger Collection: Only Bit accesses are allowed".to_string()
+                Error::InvalidTriggerCollection => "Error on creating Trigger Collection: Only Bit accesses are allowed".to_string(),
+                Error::Conversion(e) => format!("Conversion Error: {e}"),
+                Error::DirectOutputWriteNotAllowed => "Writing directly to the peripheral outputs is not allowed for this connection".to_string(),
+                Error::PlcControlNotAllowed => "Starting or stopping the CPU is not allowed for this connection".to_string(),
+                Error::FunctionNotSupported => "The PLC does not support the requested function".to_string(),
+                Error::UnknownSymbol(name) => format!("The symbol \"{name}\" is not in the symbol table"),
+                Error::AddressOutOfRange { db_number, offset } =>
+                    format!("Byte {offset} is beyond the declared size of DB{db_number}"),
+                Error::UnknownSchemaVersion { db_number, version } =>
+                    format!("The schema of DB{db_number} has no layout for version {version}"),
+                Error::DataBlockAccess { db_number, .. } =>
+                    format!("Error on accessing DB{db_number}"),
+                Error::ClockNotSet => "The clock of the PLC is not set".to_string(),
+                Error::ClockNotAccepted(_) => "The PLC rejected setting its clock".to_string(),
+                Error::ReadProtected => "Reading is not allowed because the CPU is read protected (protection level 3), a password is required".to_string(),
+                Error::WriteItemError { area, db_number, byte, bit, .. } => {
+                    let area = if *area == Area::DataBlock {
+                        format!("DB{db_number}")
+                    } else {
+                        format!("{area:?}")
+                    };
+                    match bit {
+                        Some(bit) => format!("Error on writing {area} at {byte}.{bit}"),
+                        None => format!("Error on writing {area} at byte {byte}"),
+                    }
+                }
             }
         )
     }
 }
 
-impl std::error::Error for Error {}
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            // `std::io::Error` is not comparable, its kind is
+            (Error::IO(a), Error::IO(b)) => a.kind() == b.kind(),
+            (Error::Pool(a), Error::Pool(b)) => a.to_string() == b.to_string(),
+            (Error::Connection(a), Error::Connection(b))
+            | (Error::Conversion(a), Error::Conversion(b))
+            | (Error::UnknownSymbol(a), Error::UnknownSymbol(b)) => a == b,
+            (Error::TryFrom(a, a_msg), Error::TryFrom(b, b_msg)) => a == b && a_msg == b_msg,
+            (Error::ISOResponse(a), Error::ISOResponse(b)) => a == b,
+            (Error::S7ProtocolError(a), Error::S7ProtocolError(b))
+            | (Error::ClockNotAccepted(a), Error::ClockNotAccepted(b)) => a == b,
+            (Error::DataItemError(a), Error::DataItemError(b)) => a == b,
+            (
+                Error::ResponseDataWouldBeTooLarge { req_size, max_pdu },
+                Error::ResponseDataWouldBeTooLarge {
+                    req_size: other_req_size,
+                    max_pdu: other_max_pdu,
+                },
+            ) => req_size == other_req_size && max_pdu == other_max_pdu,
+            (
+                Error::AddressOutOfRange { db_number, offset },
+                Error::AddressOutOfRange {
+                    db_number: other_db_number,
+                    offset: other_offset,
+                },
+            ) => db_number == other_db_number && offset == other_offset,
+            (
+                Error::UnknownSchemaVersion { db_number, version },
+                Error::UnknownSchemaVersion {
+                    db_number: other_db_number,
+                    version: other_version,
+                },
+            ) => db_number == other_db_number && version == other_version,
+            (
+                Error::DataBlockAccess { db_number, error },
+                Error::DataBlockAccess {
+                    db_number: other_db_number,
+                    error: other_error,
+                },
+            ) => db_number == other_db_number && error == other_error,
+            (
+                Error::WriteItemError {
+                    area,
+                    db_number,
+                    byte,
+                    bit,
+                    error,
+                },
+                Error::WriteItemError {
+                    area: other_area,
+                    db_number: other_db_number,
+                    byte: other_byte,
+                    bit: other_bit,
+                    error: other_error,
+                },
+            ) => {
+                area == other_area
+                    && db_number == other_db_number
+                    && byte == other_byte
+                    && bit == other_bit
+                    && error == other_error
+            }
+            (Error::PoolShuttingDown, Error::PoolShuttingDown)
+            | (Error::DataExchangeTimedOut, Error::DataExchangeTimedOut)
+            | (Error::RequestedBitOutOfRange, Error::RequestedBitOutOfRange)
+            | (Error::Cancelled, Error::Cancelled)
+            | (Error::RequestNotAcknowledged, Error::RequestNotAcknowledged)
+            | (
+                Error::ResponseDoesNotBelongToCurrentPDU,
+                Error::ResponseDoesNotBelongToCurrentPDU,
+            )
+            | (Error::TooManyItemsInOneRequest, Error::TooManyItemsInOneRequest)
+            | (Error::DataItemTooLarge, Error::DataItemTooLarge)
+            | (Error::TooMuchDataToWrite, Error::TooMuchDataToWrite)
+            | (Error::InvalidTriggerCollection, Error::InvalidTriggerCollection)
+            | (Error::DirectOutputWriteNotAllowed, Error::DirectOutputWriteNotAllowed)
+            | (Error::PlcControlNotAllowed, Error::PlcControlNotAllowed)
+            | (Error::FunctionNotSupported, Error::FunctionNotSupported)
+            | (Error::ReadProtected, Error::ReadProtected)
+            | (Error::ClockNotSet, Error::ClockNotSet) => true,
+            _ => false,
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Pool(e) => Some(e.as_ref()),
+            Error::ISOResponse(e) => Some(e),
+            Error::ClockNotAccepted(e) => Some(e),
+            Error::WriteItemError { error, .. } => Some(error),
+            Error::DataBlockAccess { error, .. } => Some(error.as_ref()),
+            // displayed as the wrapped error itself, so its source is the next cause
+            Error::IO(e) => e.source(),
+            Error::S7ProtocolError(e) => e.source(),
+            Error::DataItemError(e) => e.source(),
+            _ => None,
+        }
+    }
+}
 
 impl Error {
     pub(crate) fn is_connection_error(&self) -> bool {
@@ -151,6 +344,8 @@ impl fmt::Display for IsoError {
     }
 }
 
+impl std::error::Error for IsoError {}
+
 /// S7 protocol error
 #[derive(Debug, PartialEq)]
 pub struct S7ProtocolError {
@@ -170,6 +365,8 @@ impl fmt::Display for S7ProtocolError {
     }
 }
 
+impl std::error::Error for S7ProtocolError {}
+
 impl S7ProtocolError {
     pub(crate) fn from_codes(class: Option<u8>, code: Option<u8>) -> Self {
         Self {
@@ -191,6 +388,25 @@ impl S7ProtocolError {
     }
 }
 
+/// Function describing error codes of data items that are not known to this crate, shared between
+/// all connections of a configuration
+///
+/// Registered with [`S7ClientBuilder::data_item_error_mapper`](crate::S7ClientBuilder::data_item_error_mapper).
+#[derive(Clone)]
+pub(crate) struct DataItemErrorMapper(Arc<dyn Fn(u8) -> Option<String> + Send + Sync>);
+
+impl DataItemErrorMapper {
+    pub(crate) fn new(mapper: impl Fn(u8) -> Option<String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(mapper))
+    }
+}
+
+impl fmt::Debug for DataItemErrorMapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DataItemErrorMapper")
+    }
+}
+
 /// Errors from a data item included inside a S7 PLC response
 #[derive(Debug, PartialEq)]
 pub enum S7DataItemResponseError {
@@ -208,6 +424,14 @@ pub enum S7DataItemResponseError {
     DataTypeInconsistent,
     /// Requested object does not exist
     ObjectDoesNotExist,
+    /// Error code described by the mapper registered with
+    /// [`S7ClientBuilder::data_item_error_mapper`](crate::S7ClientBuilder::data_item_error_mapper)
+    Custom {
+        /// Error code returned by the PLC
+        code: u8,
+        /// Description returned by the mapper
+        description: String,
+    },
     /// Unknown error
     Unknown,
 }
@@ -215,6 +439,12 @@ pub enum S7DataItemResponseError {
 impl fmt::Display for S7DataItemResponseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let msg = match self {
+            Self::Custom { code, description } => {
+                return write!(
+                    f,
+                    "S7 Data Item response error: {description} (code {code:#04x})"
+                )
+            }
             Self::Reserved => "Reserved",
             Self::HardwareFault => "Hardware fault",
             Self::AccessNotAllowed => "Accessing the object not allowed",
@@ -228,6 +458,8 @@ impl fmt::Display for S7DataItemResponseError {
     }
 }
 
+impl std::error::Error for S7DataItemResponseError {}
+
 impl From<u8> for S7DataItemResponseError {
     fn from(code: u8) -> Self {
         match code {
@@ -243,6 +475,19 @@ impl From<u8> for S7DataItemResponseError {
     }
 }
 
+impl S7DataItemResponseError {
+    /// Error for `code`, describing codes that are not known to this crate with `mapper`
+    pub(crate) fn from_code(code: u8, mapper: Option<&DataItemErrorMapper>) -> Self {
+        match Self::from(code) {
+            Self::Unknown => match mapper.and_then(|mapper| (mapper.0)(code)) {
+                Some(description) => Self::Custom { code, description },
+                None => Self::Unknown,
+            },
+            error => error,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use error_stack::{Report, ResultExt};
@@ -261,4 +506,66 @@ mod tests {
     fn create_error() -> Result<(), Error> {
         Err(Error::RequestNotAcknowledged)
     }
+
+    #[test]
+    fn wrapped_causes_are_sources() {
+        use std::error::Error as _;
+
+        let error = Error::DataBlockAccess {
+            db_number: 100,
+            error: Box::new(Error::DataItemError(
+                S7DataItemResponseError::ObjectDoesNotExist,
+            )),
+        };
+        // every message of the chain appears once
+        assert_eq!(error.to_string(), "Error on accessing DB100");
+        let source = error.source().unwrap();
+        assert_eq!(
+            source.to_string(),
+            "S7 Data Item response error: Object does not exist"
+        );
+        assert!(source.source().is_none());
+
+        let error = Error::ISOResponse(IsoError::ShortPacket);
+        assert_eq!(error.to_string(), "ISO Response Error");
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            IsoError::ShortPacket.to_string()
+        );
+        assert!(Error::from(RunError::TimedOut).source().is_some());
+        assert!(Error::RequestNotAcknowledged.source().is_none());
+    }
+
+    #[test]
+    fn mapper_describes_unknown_codes() {
+        let mapper =
+            DataItemErrorMapper::new(|code| (code == 0x42).then(|| "Gateway busy".to_string()));
+
+        let error = Error::DataItemError(S7DataItemResponseError::from_code(0x42, Some(&mapper)));
+        assert_eq!(
+            error.to_string(),
+            "S7 Data Item response error: Gateway busy (code 0x42)"
+        );
+        assert_eq!(
+            S7DataItemResponseError::from_code(0x43, Some(&mapper)),
+            S7DataItemResponseError::Unknown
+        );
+        // known codes are not passed to the mapper
+        assert_eq!(
+            S7DataItemResponseError::from_code(0x0a, Some(&mapper)),
+            S7DataItemResponseError::ObjectDoesNotExist
+        );
+        // without a mapper unknown codes stay unknown
+        assert_eq!(
+            S7DataItemResponseError::from_code(0x42, None),
+            S7DataItemResponseError::Unknown
+        );
+    }
+
+    #[test]
+    fn pool_returns_connection_error() {
+        let error = Error::from(RunError::User(Error::DataExchangeTimedOut));
+        assert_eq!(error, Error::DataExchangeTimedOut);
+        assert!(matches!(Error::from(RunError::TimedOut), Error::Pool(_)));
+    }
 }
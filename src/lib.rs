@@ -56,15 +56,57 @@ let data = pool.db_read(100, 0, 4).await?;
 # Ok::<(), s7client::errors::Error>(())
 # });
 ```
+
+# Features
+* `tracing`: emit spans and events of the protocol exchange (connecting, negotiated parameters,
+  PDU numbers, byte counts and errors) with the [`tracing`](https://docs.rs/tracing) crate.
+  Requests and responses are hex-dumped at `trace` level.
+* `serde`: `Serialize` and `Deserialize` implementations for [`S7ReadAccess`], [`S7WriteValue`]
+  and [`S7Types`] to define tag tables in configuration files.
+* `blocking`: `BlockingS7Client`, a synchronous wrapper around [`S7Client`] running each request
+  on an internal current-thread runtime, for applications without an async runtime.
 */
 
 mod client;
 mod connection;
 pub mod errors;
+#[cfg(test)]
+mod mock;
 mod s7_protocol;
-
+pub mod time;
+mod trace;
+pub mod values;
+
+pub use client::alarms::{AlarmEvent, AlarmKind, AlarmStream};
+pub use client::benchmark::BenchmarkResult;
+#[cfg(feature = "blocking")]
+pub use client::blocking::BlockingS7Client;
+pub use client::builder::S7ClientBuilder;
 pub use client::create::S7Client;
-pub use client::{triggers::TriggerCollection, S7ReadAccess, S7WriteAccess};
+pub use client::events::ConnectionEvent;
+pub use client::operation::{OpInfo, OpKind};
+pub use client::priority::Priority;
+pub use client::probe::Endianness;
+pub use client::protection::ProtectionLevel;
+pub use client::recipe::Recipe;
+pub use client::schema::DbSchema;
+pub use client::snapshot::DbSnapshot;
+pub use client::symbols::{SymbolAddress, SymbolTable};
+pub use client::szl::{
+    ClockStatus, CpuInfo, CpuStatus, DbLimits, IpConfig, SessionInfo, SessionKind,
+};
+pub use client::text::Encoding;
+pub use client::timeout::TimeoutGuard;
+pub use client::{
+    triggers::{
+        TriggerCollection, TriggerEvent, TriggerKind, TriggerSource, TriggerWatch,
+        ValueTriggerCollection,
+    },
+    IoMode, S7ReadAccess, S7WriteAccess, S7WriteValue,
+};
 pub use connection::iso::S7Types;
+pub use s7_protocol::segments::data_item::RawDataItem;
+pub use s7_protocol::types::{Area, S7DataTypes as S7DataType, TransportSize};
+pub use values::{IntType, S7Value};
 
-pub use client::pooled::S7Pool;
+pub use client::pooled::{HealthCheck, S7Pool};
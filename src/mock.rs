@@ -0,0 +1,638 @@
+//! Minimal S7 PLC emulation for unit tests.
+//!
+//! The mock speaks just enough ISO on TCP and S7 protocol to accept connections,
+//! negotiate the PDU parameters and serve read/write jobs from in-memory areas.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use std::net::Ipv4Addr;
+
+use crate::{HealthCheck, S7Client, S7ClientBuilder, S7Pool, S7Types};
+
+pub(crate) const AREA_DB: u8 = 0x84;
+
+#[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct MockState {
+    pub(crate) pdu_length: u16,
+    pub(crate) max_amq_caller: u16,
+    pub(crate) max_amq_calle: u16,
+    pub(crate) negotiation_function_code: u8,
+    /// Delay before answering read and write jobs
+    pub(crate) response_delay: Duration,
+    /// Stop answering read and write jobs after this number of answered jobs
+    pub(crate) stall_after_jobs: Option<usize>,
+    /// Number of jobs answered while the next request was already waiting
+    pub(crate) pipelined_jobs: usize,
+    /// Whether subscriptions of the message service (alarms) are accepted
+    pub(crate) message_service_supported: bool,
+    /// User data PDUs pushed after the next subscription of the message service
+    pub(crate) pushes: Vec<Vec<u8>>,
+    /// Reject every read job with "access not allowed"
+    pub(crate) read_protected: bool,
+    /// Reject every read job with the given return code
+    pub(crate) read_error_code: Option<u8>,
+    /// Answer byte reads with at most the given number of bytes, like a misbehaving server
+    pub(crate) read_length_limit: Option<usize>,
+    /// Answer read and write jobs with a PDU reference not matching the request
+    pub(crate) mismatched_pdu_reference: bool,
+    /// Clock of the CPU as returned by the time functions (reserved byte, BCD century and `DATE_AND_TIME`)
+    pub(crate) clock: Option<Vec<u8>>,
+    /// Error code of the parameters answering requests setting the clock, 0 accepts them
+    pub(crate) set_clock_error_code: u16,
+    /// Error class and code of the header answering start and stop commands, 0 for success
+    pub(crate) plc_control_error: u16,
+    /// Item count of the parameters answering write jobs replacing the number of written items
+    pub(crate) write_item_count: Option<u8>,
+    /// Number of return codes answering write jobs, dropping the remaining ones
+    pub(crate) write_result_limit: Option<usize>,
+    /// Close the connection after answering a read or write job
+    pub(crate) close_after_response: bool,
+    /// Reset the connection instead of closing it, see `close_after_response`
+    pub(crate) reset_on_close: bool,
+    /// Additional delay before answering read jobs keyed by data block number of the first item
+    pub(crate) read_delays: HashMap<u16, Duration>,
+    /// Memory of the PLC keyed by area code and data block number
+    pub(crate) areas: HashMap<(u8, u16), Vec<u8>>,
+    /// Partial lists of the system status list keyed by SZL id and index, with record length and records
+    pub(crate) szl: HashMap<(u16, u16), (u16, Vec<u8>)>,
+    /// Block infos of the data blocks keyed by data block number
+    pub(crate) block_info: HashMap<u16, Vec<u8>>,
+    /// Every S7 PDU (without TPKT and COTP headers) that was received
+    pub(crate) requests: Vec<Vec<u8>>,
+    /// Every COTP connection request (without TPKT header) that was received
+    pub(crate) connection_requests: Vec<Vec<u8>>,
+    /// Number of COTP disconnect requests that were received
+    pub(crate) disconnect_requests: usize,
+}
+
+impl Default for MockState {
+    fn default() -> Self {
+        Self {
+            pdu_length: 480,
+            max_amq_caller: 0x0100,
+            max_amq_calle: 0x0100,
+            negotiation_function_code: 0xf0,
+            response_delay: Duration::ZERO,
+            stall_after_jobs: None,
+            pipelined_jobs: 0,
+            message_service_supported: true,
+            pushes: Vec::new(),
+            read_protected: false,
+            read_error_code: None,
+            read_length_limit: None,
+            mismatched_pdu_reference: false,
+            clock: None,
+            set_clock_error_code: 0,
+            plc_control_error: 0,
+            write_item_count: None,
+            write_result_limit: None,
+            close_after_response: false,
+            reset_on_close: false,
+            read_delays: HashMap::new(),
+            areas: HashMap::new(),
+            szl: HashMap::new(),
+            block_info: HashMap::new(),
+            requests: Vec::new(),
+            connection_requests: Vec::new(),
+            disconnect_requests: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MockPlc {
+    pub(crate) addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockPlc {
+    pub(crate) async fn start() -> Self {
+        Self::start_on(0).await
+    }
+
+    pub(crate) async fn start_on(port: u16) -> Self {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .await
+            .expect("Could not bind mock PLC");
+        let addr = listener.local_addr().expect("Mock PLC has no address");
+        let state = Arc::new(Mutex::new(MockState::default()));
+
+        let server_state = state.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, server_state.clone()));
+            }
+        });
+
+        Self { addr, state }
+    }
+
+    pub(crate) fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().expect("Mock PLC state is poisoned")
+    }
+
+    /// Set the content of a data block
+    pub(crate) fn set_db(&self, db_number: u16, data: &[u8]) {
+        self.state()
+            .areas
+            .insert((AREA_DB, db_number), data.to_vec());
+    }
+
+    pub(crate) fn db(&self, db_number: u16) -> Vec<u8> {
+        self.state()
+            .areas
+            .get(&(AREA_DB, db_number))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Set the records of a partial list of the system status list
+    pub(crate) fn set_szl(&self, szl_id: u16, index: u16, record_len: u16, records: &[u8]) {
+        self.state()
+            .szl
+            .insert((szl_id, index), (record_len, records.to_vec()));
+    }
+
+    /// Provide the block info of a data block
+    pub(crate) fn set_block_info(&self, db_number: u16, block_info: &[u8]) {
+        self.state()
+            .block_info
+            .insert(db_number, block_info.to_vec());
+    }
+
+    /// Queue user data of the CPU functions to be pushed after the next subscription of the message service
+    pub(crate) fn push_user_data(&self, subfunction: u8, data: &[u8]) {
+        let params = [
+            0x00,
+            0x01,
+            0x12,
+            0x08,
+            0x12,
+            0x04,
+            subfunction,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+        ];
+        let mut user_data = vec![0xFF, 0x09];
+        user_data.extend(
+            u16::try_from(data.len())
+                .expect("Mock data too large")
+                .to_be_bytes(),
+        );
+        user_data.extend(data);
+
+        let mut pdu = vec![0x32, 0x07, 0x00, 0x00, 0x00, 0x00];
+        pdu.extend(
+            u16::try_from(params.len())
+                .expect("Mock params too large")
+                .to_be_bytes(),
+        );
+        pdu.extend(
+            u16::try_from(user_data.len())
+                .expect("Mock data too large")
+                .to_be_bytes(),
+        );
+        pdu.extend(params);
+        pdu.extend(user_data);
+        self.state().pushes.push(pdu);
+    }
+
+    /// Destination TSAP of the last connection request
+    pub(crate) fn destination_tsap(&self) -> Option<[u8; 2]> {
+        let state = self.state();
+        let request = state.connection_requests.last()?;
+        let position = request.windows(2).position(|param| param == [0xC2, 0x02])?;
+        Some([request[position + 2], request[position + 3]])
+    }
+
+    pub(crate) fn builder(&self) -> S7ClientBuilder {
+        S7ClientBuilder::new(Ipv4Addr::LOCALHOST, S7Types::S71200).port(self.addr.port())
+    }
+
+    pub(crate) fn pool(&self, max_size: u32) -> S7Pool {
+        S7Pool::from_builder(self.builder(), max_size)
+            .expect("Could not create pool for mock PLC")
+            .health_check(HealthCheck::Disabled)
+    }
+
+    pub(crate) async fn client(&self) -> S7Client {
+        self.builder()
+            .connect()
+            .await
+            .expect("Could not connect to mock PLC")
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+async fn serve(mut stream: TcpStream, state: Arc<Mutex<MockState>>) {
+    loop {
+        let mut header = [0_u8; 4];
+        if stream.read_exact(&mut header).await.is_err() {
+            return;
+        }
+        let length = usize::from(u16::from_be_bytes([header[2], header[3]]));
+        let mut payload = vec![0_u8; length.saturating_sub(4)];
+        if stream.read_exact(&mut payload).await.is_err() {
+            return;
+        }
+
+        let mut close_connection = false;
+        let mut pushes = Vec::new();
+        let response = match payload.get(1) {
+            // connection request
+            Some(0xE0) => {
+                state
+                    .lock()
+                    .expect("Mock PLC state is poisoned")
+                    .connection_requests
+                    .push(payload.clone());
+                cotp_connection_confirm()
+            }
+            // disconnect request
+            Some(0x80) => {
+                state
+                    .lock()
+                    .expect("Mock PLC state is poisoned")
+                    .disconnect_requests += 1;
+                vec![6, 0xC0, 0x00, 0x01, 0x00, 0x01, 0x80]
+            }
+            // data transfer
+            Some(0xF0) => {
+                let pdu = payload[3..].to_vec();
+                let (response, delay, close) = {
+                    let mut state = state.lock().expect("Mock PLC state is poisoned");
+                    state.requests.push(pdu.clone());
+                    if pdu[1] == 0x07
+                        && pdu[15] == 0x44
+                        && pdu[16] == 0x02
+                        && state.message_service_supported
+                    {
+                        pushes = std::mem::take(&mut state.pushes);
+                    }
+                    let delay = match pdu[10] {
+                        0xf0 => Duration::ZERO,
+                        0x04 => {
+                            let db_number = u16::from_be_bytes([pdu[18], pdu[19]]);
+                            state.response_delay
+                                + state
+                                    .read_delays
+                                    .get(&db_number)
+                                    .copied()
+                                    .unwrap_or_default()
+                        }
+                        _ => state.response_delay,
+                    };
+                    let jobs = state
+                        .requests
+                        .iter()
+                        .filter(|pdu| matches!(pdu[10], 0x04 | 0x05))
+                        .count();
+                    let delay = match state.stall_after_jobs {
+                        Some(answered) if jobs > answered => Duration::from_secs(3600),
+                        _ => delay,
+                    };
+                    let close = state.close_after_response && matches!(pdu[10], 0x04 | 0x05);
+                    let mut response = handle_pdu(&mut state, &pdu);
+                    if state.mismatched_pdu_reference && matches!(pdu[10], 0x04 | 0x05) {
+                        response[4] ^= 0xFF;
+                    }
+                    (response, delay, close)
+                };
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                if pdu[10] != 0xf0 && next_request_waiting(&stream).await {
+                    state
+                        .lock()
+                        .expect("Mock PLC state is poisoned")
+                        .pipelined_jobs += 1;
+                }
+                close_connection = close;
+                let mut data = vec![0x02, 0xF0, 0x80];
+                data.extend(response);
+                data
+            }
+            _ => return,
+        };
+
+        if stream.write_all(&telegram(response)).await.is_err() {
+            return;
+        }
+        if close_connection {
+            if state
+                .lock()
+                .expect("Mock PLC state is poisoned")
+                .reset_on_close
+            {
+                let _ = stream.set_zero_linger();
+            }
+            return;
+        }
+        for push in pushes {
+            let mut data = vec![0x02, 0xF0, 0x80];
+            data.extend(push);
+            if stream.write_all(&telegram(data)).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Wrap COTP data into a TPKT telegram
+fn telegram(data: Vec<u8>) -> Vec<u8> {
+    let mut telegram = vec![0x03, 0x00];
+    telegram.extend(
+        u16::try_from(data.len() + 4)
+            .expect("Mock response too large")
+            .to_be_bytes(),
+    );
+    telegram.extend(data);
+    telegram
+}
+
+async fn next_request_waiting(stream: &TcpStream) -> bool {
+    let mut buffer = [0_u8; 1];
+    matches!(
+        tokio::time::timeout(Duration::ZERO, stream.peek(&mut buffer)).await,
+        Ok(Ok(1))
+    )
+}
+
+fn cotp_connection_confirm() -> Vec<u8> {
+    vec![
+        17, 0xD0, 0x00, 0x01, 0x00, 0x01, 0x00, // COTP header
+        0xC0, 0x01, 0x0A, // TPDU size
+        0xC1, 0x02, 0x01, 0x00, // source TSAP
+        0xC2, 0x02, 0x01, 0x02, // destination TSAP
+    ]
+}
+
+fn ack_data(request: &[u8], params: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut response = vec![0x32, 0x03, 0x00, 0x00, request[4], request[5]];
+    response.extend(
+        u16::try_from(params.len())
+            .expect("Mock params too large")
+            .to_be_bytes(),
+    );
+    response.extend(
+        u16::try_from(data.len())
+            .expect("Mock data too large")
+            .to_be_bytes(),
+    );
+    response.extend([0x00, 0x00]);
+    response.extend(params);
+    response.extend(data);
+    response
+}
+
+struct Item {
+    var_type: u8,
+    count: usize,
+    db_number: u16,
+    area: u8,
+    address: usize,
+}
+
+fn parse_items(pdu: &[u8]) -> Vec<Item> {
+    let item_count = usize::from(pdu[11]);
+    (0..item_count)
+        .map(|index| {
+            let item = &pdu[12 + index * 12..24 + index * 12];
+            Item {
+                var_type: item[3],
+                count: usize::from(u16::from_be_bytes([item[4], item[5]])),
+                db_number: u16::from_be_bytes([item[6], item[7]]),
+                area: item[8],
+                address: (usize::from(item[9]) << 16)
+                    | (usize::from(item[10]) << 8)
+                    | usize::from(item[11]),
+            }
+        })
+        .collect()
+}
+
+fn user_data(request: &[u8], error_code: u16, data: &[u8]) -> Vec<u8> {
+    let mut params = vec![
+        0x00,
+        0x01,
+        0x12,
+        0x08,
+        0x12,
+        0x80 | (request[15] & 0x0F),
+        request[16],
+    ];
+    params.extend([0x01, 0x00, 0x00]);
+    params.extend(error_code.to_be_bytes());
+
+    let mut response = vec![0x32, 0x07, 0x00, 0x00, request[4], request[5]];
+    response.extend(
+        u16::try_from(params.len())
+            .expect("Mock params too large")
+            .to_be_bytes(),
+    );
+    response.extend(
+        u16::try_from(data.len())
+            .expect("Mock data too large")
+            .to_be_bytes(),
+    );
+    response.extend(params);
+    response.extend(data);
+    response
+}
+
+fn handle_user_data(state: &mut MockState, pdu: &[u8]) -> Vec<u8> {
+    // only SZL reads, the message service and alarm acknowledgements of the CPU functions,
+    // reading and setting the clock and block infos of data blocks are supported
+    match (pdu[15], pdu[16]) {
+        (0x43, 0x03) => {
+            let block_info = std::str::from_utf8(&pdu[24..29])
+                .ok()
+                .and_then(|digits| digits.parse::<u16>().ok())
+                .and_then(|db_number| state.block_info.get(&db_number));
+            return match block_info {
+                Some(block_info) => {
+                    let mut data = vec![0xFF, 0x09];
+                    data.extend(u16::try_from(block_info.len()).unwrap().to_be_bytes());
+                    data.extend(block_info);
+                    user_data(pdu, 0x0000, &data)
+                }
+                None => user_data(pdu, 0x0000, &[0x0A, 0x00, 0x00, 0x00]),
+            };
+        }
+        (0x47, 0x02) => {
+            if state.set_clock_error_code == 0 {
+                state.clock = Some(pdu[22..32].to_vec());
+            }
+            return user_data(pdu, state.set_clock_error_code, &[0x0A, 0x00, 0x00, 0x00]);
+        }
+        (0x47, 0x01) => {
+            return match &state.clock {
+                Some(clock) => {
+                    let mut data = vec![0xFF, 0x09];
+                    data.extend(u16::try_from(clock.len()).unwrap().to_be_bytes());
+                    data.extend(clock);
+                    user_data(pdu, 0x0000, &data)
+                }
+                None => user_data(pdu, 0x0000, &[0x0A, 0x00, 0x00, 0x00]),
+            };
+        }
+        (0x44, 0x01) => {}
+        (0x44, 0x02) if state.message_service_supported => {
+            return user_data(pdu, 0x0000, &[0xFF, 0x09, 0x00, 0x02, 0x02, 0x00]);
+        }
+        (0x44, 0x0B) => return user_data(pdu, 0x0000, &[0xFF, 0x09, 0x00, 0x00]),
+        (0x44, _) => return user_data(pdu, 0x0000, &[0x0A, 0x00, 0x00, 0x00]),
+        _ => return user_data(pdu, 0x8104, &[0x0A, 0x00, 0x00, 0x00]),
+    }
+    let szl_id = u16::from_be_bytes([pdu[22], pdu[23]]);
+    let index = u16::from_be_bytes([pdu[24], pdu[25]]);
+    match state.szl.get(&(szl_id, index)) {
+        None => user_data(pdu, 0xD401, &[0x0A, 0x00, 0x00, 0x00]),
+        Some((record_len, records)) => {
+            let count = records.len() / usize::from((*record_len).max(1));
+            let mut list = Vec::new();
+            list.extend(szl_id.to_be_bytes());
+            list.extend(index.to_be_bytes());
+            list.extend(record_len.to_be_bytes());
+            list.extend(
+                u16::try_from(count)
+                    .expect("Mock SZL too large")
+                    .to_be_bytes(),
+            );
+            list.extend(records);
+
+            let mut data = vec![0xFF, 0x09];
+            data.extend(
+                u16::try_from(list.len())
+                    .expect("Mock SZL too large")
+                    .to_be_bytes(),
+            );
+            data.extend(list);
+            user_data(pdu, 0x0000, &data)
+        }
+    }
+}
+
+/// Answer a write job, applying the written items to the areas
+fn handle_write(state: &mut MockState, pdu: &[u8]) -> Vec<u8> {
+    let items = parse_items(pdu);
+    let mut offset = 12 + items.len() * 12;
+    let mut results = Vec::new();
+    for item in &items {
+        let transport_size = pdu[offset + 1];
+        let count = usize::from(u16::from_be_bytes([pdu[offset + 2], pdu[offset + 3]]));
+        let length = if transport_size == 0x03 { 1 } else { count / 8 };
+        let value = &pdu[offset + 4..offset + 4 + length];
+        offset += 4 + length;
+
+        results.push(match state.areas.get_mut(&(item.area, item.db_number)) {
+            None => 0x0A,
+            Some(bytes) => {
+                let start = item.address >> 3;
+                if item.var_type == 0x01 {
+                    if start >= bytes.len() {
+                        0x05
+                    } else {
+                        let mask = 1 << (item.address & 0x07);
+                        if value[0] > 0 {
+                            bytes[start] |= mask;
+                        } else {
+                            bytes[start] &= !mask;
+                        }
+                        0xFF
+                    }
+                } else if start + item.count > bytes.len() {
+                    0x05
+                } else {
+                    bytes[start..start + item.count].copy_from_slice(value);
+                    0xFF
+                }
+            }
+        });
+    }
+    results.truncate(state.write_result_limit.unwrap_or(results.len()));
+    ack_data(
+        pdu,
+        &[0x05, state.write_item_count.unwrap_or(pdu[11])],
+        &results,
+    )
+}
+
+fn handle_pdu(state: &mut MockState, pdu: &[u8]) -> Vec<u8> {
+    if pdu[1] == 0x07 {
+        return handle_user_data(state, pdu);
+    }
+    match pdu[10] {
+        0xf0 => {
+            let mut params = vec![state.negotiation_function_code, 0x00];
+            params.extend(state.max_amq_caller.to_be_bytes());
+            params.extend(state.max_amq_calle.to_be_bytes());
+            params.extend(state.pdu_length.to_be_bytes());
+            ack_data(pdu, &params, &[])
+        }
+        0x04 => {
+            let items = parse_items(pdu);
+            let mut data = Vec::new();
+            for (index, item) in items.iter().enumerate() {
+                // every data item except the last one is padded to an even length
+                if index > 0 && data.len() % 2 == 1 {
+                    data.push(0x00);
+                }
+                let area = state.areas.get(&(item.area, item.db_number));
+                let is_bit = item.var_type == 0x01;
+                let (start, length) = if is_bit {
+                    (item.address >> 3, 1)
+                } else {
+                    (item.address >> 3, item.count)
+                };
+                match area {
+                    _ if state.read_protected => data.extend([0x03, 0x00, 0x00, 0x00]),
+                    _ if state.read_error_code.is_some() => {
+                        data.extend(state.read_error_code);
+                        data.extend([0x00, 0x00, 0x00]);
+                    }
+                    None => data.extend([0x0A, 0x00, 0x00, 0x00]),
+                    Some(bytes) if start + length > bytes.len() => {
+                        data.extend([0x05, 0x00, 0x00, 0x00]);
+                    }
+                    Some(bytes) => {
+                        if is_bit {
+                            let value = (bytes[start] >> (item.address & 0x07)) & 0x01;
+                            data.extend([0xFF, 0x03, 0x00, 0x01, value]);
+                        } else {
+                            let length = state
+                                .read_length_limit
+                                .map_or(length, |limit| length.min(limit));
+                            data.extend([0xFF, 0x04]);
+                            data.extend(
+                                u16::try_from(length * 8)
+                                    .expect("Mock read too large")
+                                    .to_be_bytes(),
+                            );
+                            data.extend(&bytes[start..start + length]);
+                        }
+                    }
+                }
+            }
+            ack_data(pdu, &[0x04, pdu[11]], &data)
+        }
+        0x05 => handle_write(state, pdu),
+        // PI service starting or stopping the CPU
+        0x28 | 0x29 => {
+            let mut response = ack_data(pdu, &[pdu[10]], &[]);
+            response[10..12].copy_from_slice(&state.plc_control_error.to_be_bytes());
+            response
+        }
+        _ => ack_data(pdu, &[], &[]),
+    }
+}
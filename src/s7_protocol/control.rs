@@ -0,0 +1,113 @@
+//! Programmer commands of the PI service (`P_PROGRAM`) starting and stopping the CPU.
+
+use std::convert::TryFrom;
+
+use bytes::{BufMut, BytesMut};
+
+use crate::client::create::S7Client;
+use crate::errors::{Error, S7ProtocolError};
+
+use super::segments::header::S7ProtocolHeader;
+
+const FUNCTION_PLC_CONTROL: u8 = 0x28;
+const FUNCTION_PLC_STOP: u8 = 0x29;
+// PI service controlling the program execution of the CPU
+const PI_SERVICE_PROGRAM: &[u8] = b"P_PROGRAM";
+// argument of the PI service requesting a cold start
+const ARGUMENT_COLD_START: &[u8] = b"C ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlcCommand {
+    HotStart,
+    ColdStart,
+    Stop,
+}
+
+impl PlcCommand {
+    fn function_code(self) -> u8 {
+        match self {
+            Self::HotStart | Self::ColdStart => FUNCTION_PLC_CONTROL,
+            Self::Stop => FUNCTION_PLC_STOP,
+        }
+    }
+
+    /// Parameters of the request, which address the PI service by name
+    fn params(self) -> BytesMut {
+        let mut params = BytesMut::new();
+        params.put_u8(self.function_code());
+        match self {
+            Self::HotStart | Self::ColdStart => {
+                let argument = if self == Self::ColdStart {
+                    ARGUMENT_COLD_START
+                } else {
+                    &[]
+                };
+                // unknown bytes and the parameter block
+                params.put_slice(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFD]);
+                #[allow(clippy::cast_possible_truncation)]
+                params.put_u16(argument.len() as u16);
+                params.put_slice(argument);
+            }
+            Self::Stop => params.put_slice(&[0x00, 0x00, 0x00, 0x00, 0x00]),
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        params.put_u8(PI_SERVICE_PROGRAM.len() as u8);
+        params.put_slice(PI_SERVICE_PROGRAM);
+        params
+    }
+}
+
+/// Send the programmer command to the CPU and wait for its acknowledgement
+pub(crate) async fn plc_control(client: &mut S7Client, command: PlcCommand) -> Result<(), Error> {
+    let params = command.params();
+    let mut bytes = BytesMut::new();
+    let header = S7ProtocolHeader::build_request(&mut client.pdu_number, params.len(), 0)?;
+    bytes.put(BytesMut::from(header));
+    bytes.put(params);
+
+    let mut response = client.exchange(bytes).await?;
+
+    // check if s7 header is ack with data and check for errors
+    // check if pdu of response matches request pdu
+    let response_header = S7ProtocolHeader::try_from(&mut response)?;
+    response_header
+        .is_ack_with_data()?
+        .is_current_pdu_response(client.pdu_number, client.config.strict_pdu_reference)?;
+
+    // a rejected command still echoes the function code, the header carries the reason
+    if response_header.has_error() {
+        let (class, code) = response_header.get_errors();
+        return Err(Error::S7ProtocolError(S7ProtocolError::from_codes(
+            class, code,
+        )));
+    }
+
+    match response.first() {
+        Some(function_code) if *function_code == command.function_code() => Ok(()),
+        _ => Err(Error::RequestNotAcknowledged),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_address_program_service() {
+        assert_eq!(
+            PlcCommand::HotStart.params()[..],
+            [
+                0x28, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFD, 0x00, 0x00, 0x09, b'P', b'_', b'P',
+                b'R', b'O', b'G', b'R', b'A', b'M'
+            ]
+        );
+        assert_eq!(
+            PlcCommand::ColdStart.params()[8..13],
+            [0x00, 0x02, b'C', b' ', 0x09]
+        );
+        assert_eq!(
+            PlcCommand::Stop.params()[..7],
+            [0x29, 0x00, 0x00, 0x00, 0x00, 0x00, 0x09]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use bytes::{BufMut, BytesMut};
+
+use super::segments::header::{ACK_DATA, USER_DATA};
+use super::types::{READ_OPERATION, WRITE_OPERATION};
+
+/// Synthetic response of the PLC to an S7 request for a client in dry-run mode
+///
+/// Read jobs get zeroed data of the requested length, write jobs get a success for each item and
+/// user data requests get an empty list.
+pub(crate) fn synthetic_response(request: &[u8]) -> BytesMut {
+    let params = request.get(10..).unwrap_or_default();
+    let (message_type, params, data) = match (request.get(1), params.first()) {
+        (Some(&USER_DATA), _) => (USER_DATA, user_data_params(params), user_data_data(request)),
+        (_, Some(&READ_OPERATION)) => (
+            ACK_DATA,
+            vec![READ_OPERATION, params.get(1).copied().unwrap_or_default()],
+            read_data(params),
+        ),
+        (_, Some(&WRITE_OPERATION)) => {
+            let item_count = params.get(1).copied().unwrap_or_default();
+            (
+                ACK_DATA,
+                vec![WRITE_OPERATION, item_count],
+                vec![0xFF; usize::from(item_count)],
+            )
+        }
+        _ => (ACK_DATA, Vec::new(), Vec::new()),
+    };
+
+    let mut bytes = BytesMut::new();
+    bytes.put_slice(&[0x32, message_type, 0x00, 0x00]);
+    bytes.put_slice(request.get(4..6).unwrap_or(&[0x00, 0x00]));
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        bytes.put_u16(params.len() as u16);
+        bytes.put_u16(data.len() as u16);
+    }
+    if message_type == ACK_DATA {
+        // error class and error code
+        bytes.put_u16(0);
+    }
+    bytes.put_slice(&params);
+    bytes.put_slice(&data);
+    bytes
+}
+
+fn read_data(params: &[u8]) -> Vec<u8> {
+    let item_count = usize::from(params.get(1).copied().unwrap_or_default());
+    let mut data = Vec::new();
+    for item in params
+        .get(2..)
+        .unwrap_or_default()
+        .chunks(12)
+        .take(item_count)
+    {
+        // every data item except the last one is padded to an even length
+        if data.len() % 2 == 1 {
+            data.push(0x00);
+        }
+        if item.len() < 6 {
+            break;
+        }
+        let count = u16::from_be_bytes([item[4], item[5]]);
+        if item[3] == 0x01 {
+            data.extend([0xFF, 0x03, 0x00, 0x01, 0x00]);
+        } else {
+            data.extend([0xFF, 0x04]);
+            data.extend(count.wrapping_mul(8).to_be_bytes());
+            data.extend(std::iter::repeat_n(0x00, usize::from(count)));
+        }
+    }
+    data
+}
+
+fn user_data_params(params: &[u8]) -> Vec<u8> {
+    let type_group = params.get(5).copied().unwrap_or_default();
+    let subfunction = params.get(6).copied().unwrap_or_default();
+    vec![
+        0x00,
+        0x01,
+        0x12,
+        0x08,
+        0x12,
+        0x80 | (type_group & 0x0F),
+        subfunction,
+        0x00,
+        0x00,
+        0x00,
+        0x00,
+        0x00,
+    ]
+}
+
+fn user_data_data(request: &[u8]) -> Vec<u8> {
+    // header of an empty list with the requested id and index
+    let mut data = vec![0xFF, 0x09, 0x00, 0x08];
+    data.extend(request.get(22..26).unwrap_or(&[0x00; 4]));
+    data.extend([0x00; 4]);
+    data
+}
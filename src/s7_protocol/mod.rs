@@ -1,5 +1,8 @@
+pub(crate) mod control;
+pub(crate) mod dry_run;
 pub(crate) mod negotiate;
 pub(crate) mod read_area;
 pub(crate) mod segments;
 pub(crate) mod types;
+pub(crate) mod user_data;
 pub(crate) mod write_area;
@@ -8,6 +8,9 @@ use super::segments::header::S7ProtocolHeader;
 
 pub(crate) const NEGOTIATE_FUNCTION_CODE: u8 = 0xf0;
 
+// Number of unacknowledged requests proposed to the PLC in both directions
+pub(crate) const REQUESTED_MAX_AMQ: u16 = 0x0100;
+
 #[derive(Debug)]
 pub(crate) struct S7Negotiation {
     s7_header: S7ProtocolHeader,
@@ -15,10 +18,10 @@ pub(crate) struct S7Negotiation {
 }
 
 impl S7Negotiation {
-    pub(crate) fn build() -> Result<S7Negotiation, Error> {
+    pub(crate) fn build(requested_pdu_size: u16) -> Result<S7Negotiation, Error> {
         Ok(Self {
-            s7_header: S7ProtocolHeader::build_request(&mut 0, NegotiatePDUParameters::len(), 0)?,
-            params: NegotiatePDUParameters::build(),
+            s7_header: S7ProtocolHeader::build_negotiation(NegotiatePDUParameters::len())?,
+            params: NegotiatePDUParameters::build(requested_pdu_size),
         })
     }
 }
@@ -47,15 +50,19 @@ impl NegotiatePDUParameters {
         8
     }
 
-    pub(crate) fn build() -> Self {
+    pub(crate) fn build(pdu_length: u16) -> Self {
         Self {
             function_code: NEGOTIATE_FUNCTION_CODE,
             reserved: 0,
-            max_amq_caller: 0x0100,
-            max_amq_calle: 0x0100,
-            pdu_length: 480,
+            max_amq_caller: REQUESTED_MAX_AMQ,
+            max_amq_calle: REQUESTED_MAX_AMQ,
+            pdu_length,
         }
     }
+
+    pub(crate) fn has_negotiate_function_code(&self) -> bool {
+        self.function_code == NEGOTIATE_FUNCTION_CODE
+    }
 }
 
 impl From<NegotiatePDUParameters> for BytesMut {
@@ -81,7 +88,7 @@ impl TryFrom<&mut BytesMut> for NegotiatePDUParameters {
                 function_code: bytes.get_u8(),
                 reserved: bytes.get_u8(),
                 max_amq_caller: bytes.get_u16(),
-                max_amq_calle: bytes.get_u16_le(),
+                max_amq_calle: bytes.get_u16(),
                 pdu_length: bytes.get_u16(),
             })
         } else {
@@ -91,3 +98,31 @@ impl TryFrom<&mut BytesMut> for NegotiatePDUParameters {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parameters_round_trip() {
+        let params = NegotiatePDUParameters {
+            function_code: NEGOTIATE_FUNCTION_CODE,
+            reserved: 0,
+            max_amq_caller: 0x0001,
+            max_amq_calle: 0x0003,
+            pdu_length: 960,
+        };
+
+        let mut bytes = BytesMut::from(params);
+        assert_eq!(
+            bytes.as_ref(),
+            [0xf0, 0x00, 0x00, 0x01, 0x00, 0x03, 0x03, 0xC0]
+        );
+
+        let parsed = NegotiatePDUParameters::try_from(&mut bytes).unwrap();
+        assert!(parsed.has_negotiate_function_code());
+        assert_eq!(parsed.max_amq_caller, 0x0001);
+        assert_eq!(parsed.max_amq_calle, 0x0003);
+        assert_eq!(parsed.pdu_length, 960);
+    }
+}
@@ -1,14 +1,17 @@
-use bytes::{BufMut, BytesMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use std::convert::TryFrom;
+use std::ops::Range;
 
 use super::segments::{
-    data_item::DataItem, header::S7ProtocolHeader, parameters::ReadWriteParams,
+    data_item::{DataItem, RawDataItem},
+    header::S7ProtocolHeader,
+    parameters::ReadWriteParams,
     request_item::RequestItem,
 };
 use super::types::{Area, READ_OPERATION};
-use crate::connection::tcp::exchange_buffer;
-use crate::errors::{Error, S7ProtocolError};
-use crate::{S7Client, S7ReadAccess};
+use crate::errors::{Error, S7DataItemResponseError, S7ProtocolError};
+use crate::trace::event;
+use crate::{OpInfo, OpKind, S7Client, S7ReadAccess};
 
 impl<'a> ReadWriteParams<'a> {
     pub(super) fn build_read(items: &'a [RequestItem]) -> Result<Self, Error> {
@@ -45,21 +48,255 @@ fn assert_pdu_size_for_read(data_items: &[S7ReadAccess], max_pdu_size: usize) ->
     Ok(())
 }
 
+/// Group consecutive read accesses into batches that each fit into a single request
+///
+/// Returns the index ranges of the batches, an access that does not fit into a request on its own
+/// ends up alone in its batch.
+pub(crate) fn batch_read_accesses(
+    data_items: &[S7ReadAccess],
+    max_pdu_size: usize,
+) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut batch_start = 0;
+    for end in 1..=data_items.len() {
+        if end - batch_start > 1
+            && assert_pdu_size_for_read(&data_items[batch_start..end], max_pdu_size).is_err()
+        {
+            batches.push(batch_start..end - 1);
+            batch_start = end - 1;
+        }
+    }
+    if batch_start < data_items.len() {
+        batches.push(batch_start..data_items.len());
+    }
+    batches
+}
+
+/// Read access covering one or more of the original read accesses
+#[derive(Debug, PartialEq)]
+pub(crate) struct MergedRead {
+    pub(crate) access: S7ReadAccess,
+    /// Indices of the original accesses served by this read
+    pub(crate) members: Vec<usize>,
+}
+
+impl MergedRead {
+    /// Slice the data of an original access out of the data read for the merged access
+    pub(crate) fn extract(&self, member: &S7ReadAccess, data: &[u8]) -> Vec<u8> {
+        if self.members.len() == 1 {
+            return data.to_vec();
+        }
+        let (merged_start, _) = byte_range(&self.access);
+        let (start, end) = byte_range(member);
+        let offset = (start - merged_start) as usize;
+        let data = &data[offset..(end - merged_start) as usize];
+        match member {
+            S7ReadAccess::Bytes { .. } => data.to_vec(),
+            S7ReadAccess::Bit { bit, .. } => vec![(data[0] >> bit) & 1],
+        }
+    }
+}
+
+fn byte_range(access: &S7ReadAccess) -> (u32, u32) {
+    match access {
+        S7ReadAccess::Bytes { start, length, .. } => (*start, start + u32::from(*length)),
+        S7ReadAccess::Bit { byte, .. } => (*byte, byte + 1),
+    }
+}
+
+/// Merge read accesses of the same data block that are at most `max_gap` bytes apart
+///
+/// Merged accesses are read as bytes and never grow beyond `max_len` bytes. Accesses that can not
+/// be merged with any other are kept as they are.
+pub(crate) fn merge_read_accesses(
+    info: &[S7ReadAccess],
+    max_gap: u16,
+    max_len: u16,
+) -> Vec<MergedRead> {
+    let mut order = (0..info.len()).collect::<Vec<_>>();
+    order.sort_by_key(|index| (info[*index].db_number(), byte_range(&info[*index])));
+
+    let mut groups: Vec<(u16, u32, u32, Vec<usize>)> = Vec::new();
+    for index in order {
+        let access = &info[index];
+        let (start, end) = byte_range(access);
+        if let Some((db_number, group_start, group_end, members)) = groups.last_mut() {
+            if *db_number == access.db_number()
+                && start <= group_end.saturating_add(u32::from(max_gap))
+                && end.max(*group_end) - *group_start <= u32::from(max_len)
+            {
+                *group_end = end.max(*group_end);
+                members.push(index);
+                continue;
+            }
+        }
+        groups.push((access.db_number(), start, end, vec![index]));
+    }
+
+    groups
+        .into_iter()
+        .map(|(db_number, start, end, members)| MergedRead {
+            access: if let [index] = members[..] {
+                info[index]
+            } else {
+                #[allow(clippy::cast_possible_truncation)]
+                S7ReadAccess::bytes(db_number, start, (end - start) as u16)
+            },
+            members,
+        })
+        .collect()
+}
+
+/// Maximum number of bytes that can be read with a single item in one request
+pub(crate) fn max_read_item_len(pdu_length: u16) -> u16 {
+    let overhead =
+        S7ProtocolHeader::len_response() + ReadWriteParams::len() + DataItem::header_len();
+    u16::try_from(usize::from(pdu_length).saturating_sub(overhead)).unwrap_or(u16::MAX)
+}
+
 fn calculate_response_size(data_items: &[S7ReadAccess]) -> usize {
+    // every data item except the last one is padded to an even length
+    let fill_bytes = data_items
+        .iter()
+        .rev()
+        .skip(1)
+        .filter(|item| item.len() % 2 == 1)
+        .count();
+
     // check for maximum data item size
     data_items
         .iter()
         .map(|item| usize::from(item.len()))
         .sum::<usize>()
+        + fill_bytes
         + data_items.len() * DataItem::header_len()
         + 14
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(client), err(Display))
+)]
 pub(crate) async fn read_area_single(
     client: &mut S7Client,
     area: Area,
     data_item: S7ReadAccess,
 ) -> Result<Vec<u8>, Error> {
+    read_area_single_bytes(client, area, data_item)
+        .await
+        .map(Vec::from)
+}
+
+/// Read a single item like [`read_area_single`], returning the buffer the responses were collected in
+pub(crate) async fn read_area_single_bytes(
+    client: &mut S7Client,
+    area: Area,
+    data_item: S7ReadAccess,
+) -> Result<Bytes, Error> {
+    let mut data = BytesMut::new();
+    match read_area_requests(client, area, data_item, &mut data).await {
+        Ok(_) => Ok(data.freeze()),
+        Err(error) => Err(access_denied_error(client, error).await),
+    }
+}
+
+/// Read a single item like [`read_area_single`] into the provided buffer, returning the number of bytes read
+pub(crate) async fn read_area_single_into(
+    client: &mut S7Client,
+    area: Area,
+    data_item: S7ReadAccess,
+    mut buf: &mut [u8],
+) -> Result<usize, Error> {
+    match read_area_requests(client, area, data_item, &mut buf).await {
+        Ok(length) => Ok(length),
+        Err(error) => Err(access_denied_error(client, error).await),
+    }
+}
+
+async fn access_denied_error(client: &mut S7Client, error: Error) -> Error {
+    match error {
+        Error::DataItemError(S7DataItemResponseError::AccessNotAllowed) => {
+            client.read_access_denied_error().await
+        }
+        error => error,
+    }
+}
+
+/// Read a single item with as many requests as the PDU size requires, appending the data of the responses to `out`
+async fn read_area_requests(
+    client: &mut S7Client,
+    area: Area,
+    data_item: S7ReadAccess,
+    out: &mut impl BufMut,
+) -> Result<usize, Error> {
+    let (requests, pdu_numbers) = build_read_requests(client, area, data_item)?;
+
+    let mut length = 0;
+
+    event!(debug, requests = requests.len(), "split read into requests");
+    let responses = client.exchange_pipelined(requests).await?;
+    for (response, pdu_number) in responses.into_iter().zip(pdu_numbers) {
+        length += append_read_response(response, pdu_number, client, out)?;
+    }
+    expect_read_len(data_item, length)?;
+
+    Ok(length)
+}
+
+/// Check that the PLC answered with all of the data of the item
+fn expect_read_len(data_item: S7ReadAccess, length: usize) -> Result<(), Error> {
+    if length < usize::from(data_item.len()) {
+        return Err(Error::Conversion(format!(
+            "Expected {} bytes but got {length}",
+            data_item.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Read a single item like [`read_area_single`], keeping the data of the requests answered before one failed
+///
+/// The requests are exchanged one after another, so the data read until a request failed due to
+/// an error of the connection is kept as well. The data read so far is returned together with
+/// the error that stopped the read.
+pub(crate) async fn read_area_single_partial(
+    client: &mut S7Client,
+    area: Area,
+    data_item: S7ReadAccess,
+) -> (Vec<u8>, Option<Error>) {
+    let mut data = Vec::new();
+    let error = match read_area_requests_sequential(client, area, data_item, &mut data).await {
+        Ok(()) => None,
+        Err(error) => Some(access_denied_error(client, error).await),
+    };
+    (data, error)
+}
+
+async fn read_area_requests_sequential(
+    client: &mut S7Client,
+    area: Area,
+    data_item: S7ReadAccess,
+    out: &mut Vec<u8>,
+) -> Result<(), Error> {
+    let (requests, pdu_numbers) = build_read_requests(client, area, data_item)?;
+    for ((request, operation), pdu_number) in requests.into_iter().zip(pdu_numbers) {
+        let response = client.exchange_operation(request, operation).await?;
+        append_read_response(response, pdu_number, client, out)?;
+    }
+    expect_read_len(data_item, out.len())
+}
+
+/// Request together with the description of its operation
+type Request = (BytesMut, Option<OpInfo>);
+
+/// Build the requests reading a single item together with their PDU references
+///
+/// The item is split into multiple requests if its data does not fit into one response.
+fn build_read_requests(
+    client: &mut S7Client,
+    area: Area,
+    data_item: S7ReadAccess,
+) -> Result<(Vec<Request>, Vec<u16>), Error> {
     // Each PDU (TPKT Header + COTP Header + S7Header + S7Parameters + S7Data) must not exceed the maximum PDU length (bytes) negotiated with the
     // PLC during connection.
     // Moreover we must ensure that a "finite" number of items is send per PDU. If the command size does not fit in one PDU
@@ -107,9 +344,10 @@ pub(crate) async fn read_area_single(
         vec![data_item]
     };
 
-    let mut overall_response_data = BytesMut::new();
-
-    for req in items {
+    // build all requests up front, so they can be pipelined
+    let mut requests = Vec::with_capacity(items.len());
+    let mut pdu_numbers = Vec::with_capacity(items.len());
+    for (chunk, req) in items.into_iter().enumerate() {
         let request_item = RequestItem::build(
             area,
             req.db_number(),
@@ -127,50 +365,152 @@ pub(crate) async fn read_area_single(
         bytes.put(BytesMut::from(req_header));
         bytes.put(request_params);
 
-        let mut response = exchange_buffer(&mut client.connection, bytes).await?;
-
-        // check if s7 header is ack with data and check for errors
-        // check if pdu of response matches request pdu
-        let response_header = S7ProtocolHeader::try_from(&mut response)?;
-        response_header
-            .is_ack_with_data()?
-            .is_current_pdu_response(client.pdu_number)?;
-
-        // Check for errors
-        if response_header.has_error() {
-            let (class, code) = response_header.get_errors();
-            return Err(Error::S7ProtocolError(S7ProtocolError::from_codes(
-                class, code,
-            )));
-        }
+        let operation = OpInfo {
+            kind: OpKind::Read,
+            area,
+            db_number: req.db_number(),
+            start: req.start(),
+            chunk,
+        };
+        requests.push((bytes, Some(operation)));
+        pdu_numbers.push(client.pdu_number);
+    }
 
-        // get data
-        let _read_params = ReadWriteParams::from(&mut response);
-        let data_item = DataItem::try_from(&mut response)?;
-        overall_response_data.put(data_item.data.as_ref());
+    Ok((requests, pdu_numbers))
+}
+
+/// Check the response to a read request and append the data it contains to `out`, returning its length
+fn append_read_response(
+    mut response: BytesMut,
+    pdu_number: u16,
+    client: &S7Client,
+    out: &mut impl BufMut,
+) -> Result<usize, Error> {
+    // check if s7 header is ack with data and check for errors
+    // check if pdu of response matches request pdu
+    let response_header = S7ProtocolHeader::try_from(&mut response)?;
+    response_header
+        .is_ack_with_data()?
+        .is_current_pdu_response(pdu_number, client.config.strict_pdu_reference)?;
+
+    // Check for errors
+    if response_header.has_error() {
+        let (class, code) = response_header.get_errors();
+        return Err(Error::S7ProtocolError(S7ProtocolError::from_codes(
+            class, code,
+        )));
     }
 
-    Ok(overall_response_data.to_vec())
+    // get data
+    let _read_params = ReadWriteParams::from(&mut response);
+    let data = RawDataItem::try_from(&mut response)?.into_data(client.config.error_mapper())?;
+    if data.len() > out.remaining_mut() {
+        return Err(Error::TryFrom(
+            data,
+            "Response contains more data than requested".to_string(),
+        ));
+    }
+    out.put_slice(&data);
+    Ok(data.len())
 }
 
+/// Read multiple items of one area with as few requests as the PDU size allows
+///
+/// The items are partitioned into consecutive batches that each fit into one request, an item that
+/// does not fit into a request on its own is read with split requests. The results are returned in
+/// the order of the given items.
 pub(crate) async fn read_area_multi(
     client: &mut S7Client,
     area: Area,
     info: &[S7ReadAccess],
 ) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
+    let max_pdu_size = usize::from(client.pdu_length);
+    let mut results = Vec::with_capacity(info.len());
+    for batch in batch_read_accesses(info, max_pdu_size) {
+        let batch = &info[batch];
+        if let [access] = batch {
+            if assert_pdu_size_for_read(batch, max_pdu_size).is_err() {
+                match read_area_single(client, area, *access).await {
+                    Err(error) if error.is_connection_error() => return Err(error),
+                    result => results.push(result),
+                }
+                continue;
+            }
+        }
+
+        let items = batch
+            .iter()
+            .map(|access| (area, *access))
+            .collect::<Vec<_>>();
+        results.extend(read_items_multi(client, &items).await?);
+    }
+    Ok(results)
+}
+
+/// Read multiple items that may each belong to a different area with a single request
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(items = items.len()), err(Display))
+)]
+pub(crate) async fn read_items_multi(
+    client: &mut S7Client,
+    items: &[(Area, S7ReadAccess)],
+) -> Result<Vec<Result<Vec<u8>, Error>>, Error> {
+    let data = read_items_multi_raw(client, items)
+        .await?
+        .into_iter()
+        .map(|item| item.and_then(|item| item.into_data(client.config.error_mapper())))
+        .collect::<Vec<Result<Vec<u8>, Error>>>();
+
+    event!(
+        debug,
+        failed_items = data.iter().filter(|item| item.is_err()).count(),
+        "read items"
+    );
+
+    let access_denied = Err(Error::DataItemError(
+        S7DataItemResponseError::AccessNotAllowed,
+    ));
+    if data.contains(&access_denied) {
+        let error = client.read_access_denied_error().await;
+        if error == Error::ReadProtected {
+            return Ok(data
+                .into_iter()
+                .map(|item| {
+                    if item == access_denied {
+                        Err(Error::ReadProtected)
+                    } else {
+                        item
+                    }
+                })
+                .collect());
+        }
+    }
+
+    Ok(data)
+}
+
+/// Read multiple items with a single request, returning the data items of the response as they are
+///
+/// Items are only `Err` if they could not be parsed, return codes of the PLC are kept in the items.
+pub(crate) async fn read_items_multi_raw(
+    client: &mut S7Client,
+    items: &[(Area, S7ReadAccess)],
+) -> Result<Vec<Result<RawDataItem, Error>>, Error> {
     // Each PDU (TPKT Header + COTP Header + S7Header + S7Parameters + S7Data) must not exceed the maximum PDU length (bytes) negotiated with the
     // PLC during connection.
     // Moreover we must ensure that a "finite" number of items is send per PDU. If the command size does not fit in one PDU
     // then it must be split across more subsequent PDU.
 
-    assert_pdu_size_for_read(info, client.pdu_length.into())?;
+    let info = items.iter().map(|(_, access)| *access).collect::<Vec<_>>();
+    assert_pdu_size_for_read(&info, client.pdu_length.into())?;
 
     let request_params = BytesMut::from(ReadWriteParams::build_read(
-        &info
+        &items
             .iter()
-            .map(|info| {
+            .map(|(area, info)| {
                 RequestItem::build(
-                    area,
+                    *area,
                     info.db_number(),
                     info.start(),
                     info.data_type(),
@@ -188,14 +528,21 @@ pub(crate) async fn read_area_multi(
     bytes.put(BytesMut::from(req_header));
     bytes.put(request_params);
 
-    let mut response = exchange_buffer(&mut client.connection, bytes).await?;
+    let operation = items.first().map(|(area, first)| OpInfo {
+        kind: OpKind::Read,
+        area: *area,
+        db_number: first.db_number(),
+        start: first.start(),
+        chunk: 0,
+    });
+    let mut response = client.exchange_operation(bytes, operation).await?;
 
     // check if s7 header is ack with data and check for errors
     // check if pdu of response matches request pdu
     let response_header = S7ProtocolHeader::try_from(&mut response)?;
     response_header
         .is_ack_with_data()?
-        .is_current_pdu_response(client.pdu_number)?;
+        .is_current_pdu_response(client.pdu_number, client.config.strict_pdu_reference)?;
 
     // Check for errors
     if response_header.has_error() {
@@ -207,13 +554,17 @@ pub(crate) async fn read_area_multi(
 
     // get response data
     let read_params = ReadWriteParams::from(&mut response);
-    let data = (0..read_params.item_count)
-        .map(|_| DataItem::try_from(&mut response))
-        .map(|item| match item {
-            Ok(item) => Ok(item.data.to_vec()),
-            Err(e) => Err(e),
+    Ok((0..read_params.item_count)
+        .map(|index| {
+            let item = RawDataItem::try_from(&mut response)?;
+            // every data item except the last one is padded to an even length
+            if item.data.len() % 2 == 1
+                && index + 1 < read_params.item_count
+                && response.has_remaining()
+            {
+                response.advance(1);
+            }
+            Ok(item)
         })
-        .collect::<Vec<Result<Vec<u8>, Error>>>();
-
-    Ok(data)
+        .collect())
 }
@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use bytes::{Buf, BufMut, BytesMut};
 
 use crate::{
-    errors::{Error, S7DataItemResponseError},
+    errors::{DataItemErrorMapper, Error, S7DataItemResponseError},
     s7_protocol::types::DataItemTransportSize,
 };
 
@@ -22,41 +22,60 @@ impl DataItem<'_> {
     }
 }
 
-impl TryFrom<&mut BytesMut> for DataItem<'_> {
-    type Error = Error;
+/// Data item of a read response as sent by the PLC, for inspecting the transport-level response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawDataItem {
+    /// Return code of the item, `0xFF` signals success
+    pub error_code: u8,
+    /// Transport size of the data, e.g. `0x03` for bits, `0x04` for bytes, words and double words,
+    /// `0x05` for integers, `0x07` for reals and `0x09` for octet strings
+    pub var_type: u8,
+    /// Length of the data as given in the response, in bits for the transport sizes `0x04`, `0x05` and `0x07`
+    pub count: u16,
+    /// Data of the item
+    pub data: Vec<u8>,
+}
 
-    fn try_from(bytes: &mut BytesMut) -> Result<Self, Self::Error> {
-        // try to convert data item if it is long enough
-        match bytes.len() {
-            x if x >= 4 => {
-                let error_code = bytes.get_u8();
-                let var_type = bytes.get_u8();
-                let count = bytes
-                    .get_u16()
-                    .checked_div(DataItemTransportSize::from(var_type).len())
-                    .unwrap_or(0);
-                let data = bytes.split_to(count as usize);
+impl RawDataItem {
+    /// Data of the item, or the error the PLC returned for it
+    ///
+    /// Error codes that are not known to this crate are described with `mapper`.
+    pub(crate) fn into_data(self, mapper: Option<&DataItemErrorMapper>) -> Result<Vec<u8>, Error> {
+        if self.error_code == 255 {
+            Ok(self.data)
+        } else {
+            Err(Error::DataItemError(S7DataItemResponseError::from_code(
+                self.error_code,
+                mapper,
+            )))
+        }
+    }
+}
 
-                // check for errors
-                // 255 signals everything went alright
-                if error_code != 255 {
-                    return Err(Error::DataItemError(S7DataItemResponseError::from(
-                        error_code,
-                    )));
-                }
+impl TryFrom<&mut BytesMut> for RawDataItem {
+    type Error = Error;
 
-                Ok(Self {
-                    error_code,
-                    var_type,
-                    count,
-                    data: Cow::from(data.to_vec()),
-                })
-            }
-            _ => Err(Error::TryFrom(
+    fn try_from(bytes: &mut BytesMut) -> Result<Self, Self::Error> {
+        if bytes.len() < DataItem::header_len() {
+            return Err(Error::TryFrom(
                 bytes.to_vec(),
                 "Invalid length for data item".to_string(),
-            )),
+            ));
         }
+        let error_code = bytes.get_u8();
+        let var_type = bytes.get_u8();
+        let count = bytes.get_u16();
+        let length = count
+            .checked_div(DataItemTransportSize::from(var_type).len())
+            .unwrap_or(0);
+        let data = bytes.split_to(usize::from(length).min(bytes.len()));
+
+        Ok(Self {
+            error_code,
+            var_type,
+            count,
+            data: data.to_vec(),
+        })
     }
 }
 
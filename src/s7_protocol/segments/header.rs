@@ -3,6 +3,7 @@ use std::convert::TryFrom;
 use bytes::{Buf, BufMut, BytesMut};
 
 use crate::errors::{Error, IsoError, S7ProtocolError};
+use crate::trace::event;
 
 // **** Message Types ****
 // request sent by the master (e.g. read/write memory, read/write blocks, start/stop device, setup communication)
@@ -12,9 +13,14 @@ pub(crate) const ACK: u8 = 0x02;
 // acknowledgement with optional data field, contains the reply to a job request
 pub(crate) const ACK_DATA: u8 = 0x03;
 // an extension of the original protocol, the parameter field contains the request/response id, (used for programming/debugging, SZL reads, security functions, time setup, cyclic read..)
-#[allow(dead_code)]
 pub(crate) const USER_DATA: u8 = 0x07;
 
+// **** PDU references ****
+// reserved for the negotiation of the communication, no other request uses it
+pub(crate) const NEGOTIATION_PDU_REFERENCE: u16 = 0;
+// reference of the first request after the negotiation and after wrapping around
+const FIRST_PDU_REFERENCE: u16 = 1;
+
 #[derive(Debug)]
 pub(crate) struct S7ProtocolHeader {
     protocol_id: u8,    // protocol constant always set to 0x32
@@ -29,8 +35,20 @@ pub(crate) struct S7ProtocolHeader {
     error_code: Option<u8>, // only present in the Ack-Data messages, the possible error constants are listed in the constants.txt
 }
 
+/// Increase the counter for the PDU reference, wrapping around to the first reference after `u16::MAX`
+///
+/// The wrap skips the reference of the negotiation, so only requests of the current connection
+/// ever share a reference and those are 65535 requests apart.
+fn next_pdu_reference(pdu_ref: u16) -> u16 {
+    if pdu_ref == u16::MAX {
+        FIRST_PDU_REFERENCE
+    } else {
+        pdu_ref + 1
+    }
+}
+
 impl S7ProtocolHeader {
-    pub(crate) fn _len_request() -> usize {
+    pub(crate) fn len_request() -> usize {
         10
     }
 
@@ -38,24 +56,48 @@ impl S7ProtocolHeader {
         12
     }
 
+    pub(crate) fn build_negotiation(parameter_length: usize) -> Result<Self, Error> {
+        Self::with_reference(JOB_REQUEST, NEGOTIATION_PDU_REFERENCE, parameter_length, 0)
+    }
+
     pub(crate) fn build_request(
         pdu_ref: &mut u16,
         parameter_length: usize,
         data_length: usize,
     ) -> Result<Self, Error> {
-        // increase or reset counter for pdu reference
-        *pdu_ref = if *pdu_ref == u16::MAX {
-            0
-        } else {
-            *pdu_ref + 1
-        };
+        Self::build(JOB_REQUEST, pdu_ref, parameter_length, data_length)
+    }
+
+    pub(crate) fn build_user_data(
+        pdu_ref: &mut u16,
+        parameter_length: usize,
+        data_length: usize,
+    ) -> Result<Self, Error> {
+        Self::build(USER_DATA, pdu_ref, parameter_length, data_length)
+    }
 
+    fn build(
+        message_type: u8,
+        pdu_ref: &mut u16,
+        parameter_length: usize,
+        data_length: usize,
+    ) -> Result<Self, Error> {
+        *pdu_ref = next_pdu_reference(*pdu_ref);
+        Self::with_reference(message_type, *pdu_ref, parameter_length, data_length)
+    }
+
+    fn with_reference(
+        message_type: u8,
+        pdu_reference: u16,
+        parameter_length: usize,
+        data_length: usize,
+    ) -> Result<Self, Error> {
         // build S7 protocol header
         Ok(Self {
             protocol_id: 0x32,
-            message_type: JOB_REQUEST,
+            message_type,
             reserved: 0x0000,
-            pdu_reference: *pdu_ref,
+            pdu_reference,
             parameter_length: u16::try_from(parameter_length)
                 .map_err(|_| Error::TooManyItemsInOneRequest)?,
             data_length: u16::try_from(data_length).map_err(|_| Error::DataItemTooLarge)?,
@@ -64,6 +106,14 @@ impl S7ProtocolHeader {
         })
     }
 
+    /// PDU reference of an encoded request or response without parsing the whole header
+    pub(crate) fn pdu_reference_of(bytes: &[u8]) -> Result<u16, Error> {
+        match bytes.get(4..6) {
+            Some(reference) => Ok(u16::from_le_bytes([reference[0], reference[1]])),
+            None => Err(Error::ISOResponse(IsoError::ShortPacket)),
+        }
+    }
+
     pub(crate) fn is_ack(&self) -> Result<&Self, Error> {
         if self.message_type == ACK || self.message_type == ACK_DATA {
             Ok(self)
@@ -100,11 +150,34 @@ impl S7ProtocolHeader {
         }
     }
 
-    pub(crate) fn is_current_pdu_response(&self, current_pdu_number: u16) -> Result<&Self, Error> {
-        if self.pdu_reference == current_pdu_number {
+    pub(crate) fn is_user_data(&self) -> Result<&Self, Error> {
+        if self.message_type == USER_DATA {
             Ok(self)
         } else {
+            Err(Error::RequestNotAcknowledged)
+        }
+    }
+
+    /// Check that the response belongs to the request with the given PDU number
+    ///
+    /// Unless `strict`, a mismatch is only logged and the response accepted.
+    pub(crate) fn is_current_pdu_response(
+        &self,
+        current_pdu_number: u16,
+        strict: bool,
+    ) -> Result<&Self, Error> {
+        if self.pdu_reference == current_pdu_number {
+            Ok(self)
+        } else if strict {
             Err(Error::ResponseDoesNotBelongToCurrentPDU)
+        } else {
+            event!(
+                warn,
+                expected = current_pdu_number,
+                received = self.pdu_reference,
+                "accepting response with mismatched PDU reference"
+            );
+            Ok(self)
         }
     }
 
@@ -141,9 +214,9 @@ impl From<S7ProtocolHeader> for BytesMut {
         bytes.put_u16(header.parameter_length);
         bytes.put_u16(header.data_length);
 
-        if header.error_code.is_some() && header.error_class.is_some() {
-            bytes.put_u8(header.error_class.unwrap());
-            bytes.put_u8(header.error_code.unwrap());
+        if let (Some(error_class), Some(error_code)) = (header.error_class, header.error_code) {
+            bytes.put_u8(error_class);
+            bytes.put_u8(error_code);
         }
 
         bytes
@@ -172,10 +245,36 @@ impl TryFrom<&mut BytesMut> for S7ProtocolHeader {
             if header.message_type == ACK_DATA {
                 header.error_class = Some(bytes.get_u8());
                 header.error_code = Some(bytes.get_u8());
-            };
+            }
             Ok(header)
         } else {
             Err(Error::ISOResponse(IsoError::ShortPacket))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pdu_reference_wraps_around_negotiation() {
+        let mut pdu_ref = NEGOTIATION_PDU_REFERENCE;
+        let header = S7ProtocolHeader::build_request(&mut pdu_ref, 0, 0).unwrap();
+        assert_eq!(header.pdu_reference, FIRST_PDU_REFERENCE);
+
+        let mut pdu_ref = u16::MAX - 1;
+        let references = (0..3)
+            .map(|_| {
+                S7ProtocolHeader::build_user_data(&mut pdu_ref, 0, 0)
+                    .unwrap()
+                    .pdu_reference
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(references, [u16::MAX, FIRST_PDU_REFERENCE, 2]);
+        assert_eq!(pdu_ref, 2);
+
+        let header = S7ProtocolHeader::build_negotiation(8).unwrap();
+        assert_eq!(header.pdu_reference, NEGOTIATION_PDU_REFERENCE);
+    }
+}
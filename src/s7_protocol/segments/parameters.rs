@@ -38,7 +38,7 @@ impl<'a> From<ReadWriteParams<'a>> for BytesMut {
             for item in items {
                 bytes.put(BytesMut::from(*item));
             }
-        };
+        }
 
         bytes
     }
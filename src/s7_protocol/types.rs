@@ -4,36 +4,80 @@ pub(super) const WRITE_OPERATION: u8 = 0x05;
 pub(super) const SPEC_TYPE_READ_WRITE: u8 = 0x12;
 pub(super) const SYNTAX_ID_ANY_TYPE: u8 = 0x10;
 
-#[allow(dead_code)]
-#[derive(Copy, Clone)]
-pub(crate) enum Area {
+/// Memory areas of an S7 PLC
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Area {
+    /// Direct access to the peripheral inputs and outputs, bypassing the process image
+    PeripheralIO = 0x80,
+    /// Process image of the inputs
     ProcessInput = 0x81,
+    /// Process image of the outputs
     ProcessOutput = 0x82,
     /// Merker is an address registers within the CPU.
     /// The number of available flag bytes depends on the respective CPU and can be taken from the technical data.
     /// You can use flag bits, flag bytes, flag words or flag double words in a PLC program.
     Merker = 0x83,
     /// German thing, means building blocks
-    /// This is your storage  
+    /// This is your storage
     DataBlock = 0x84,
+    /// Counters of the CPU
     Counter = 0x1C,
+    /// Timers of the CPU
     Timer = 0x1D,
-    Unknown,
 }
 
-#[allow(dead_code)]
-#[derive(Copy, Clone)]
-pub(crate) enum S7DataTypes {
-    S7BIT = 0x01,  // Bit (inside a word)
-    S7BYTE = 0x02, // Byte (8 bit)
+/// Data types of S7 PLC variables
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum S7DataTypes {
+    /// Bit (inside a word)
+    S7BIT = 0x01,
+    /// Byte (8 bit)
+    S7BYTE = 0x02,
+    /// Character (8 bit)
     S7CHAR = 0x03,
-    S7WORD = 0x04, // Word (16 bit)
+    /// Word (16 bit)
+    S7WORD = 0x04,
+    /// Integer (16 bit signed)
     S7INT = 0x05,
-    S7DWORD = 0x06, // Double Word (32 bit)
+    /// Double Word (32 bit)
+    S7DWORD = 0x06,
+    /// Double Integer (32 bit signed)
     S7DINT = 0x07,
-    S7REAL = 0x08,    // Real (32 bit float)
-    S7COUNTER = 0x1C, // Counter (16 bit)
-    S7TIMER = 0x1D,   // Timer (16 bit)
+    /// Real (32 bit float)
+    S7REAL = 0x08,
+    /// Counter (16 bit)
+    S7COUNTER = 0x1C,
+    /// Timer (16 bit)
+    S7TIMER = 0x1D,
+}
+
+impl S7DataTypes {
+    /// Size of a value of the data type in bytes
+    pub(crate) fn size(self) -> u16 {
+        match self {
+            Self::S7BIT | Self::S7BYTE | Self::S7CHAR => 1,
+            Self::S7WORD | Self::S7INT | Self::S7COUNTER | Self::S7TIMER => 2,
+            Self::S7DWORD | Self::S7DINT | Self::S7REAL => 4,
+        }
+    }
+}
+
+/// Transport size announced for the data of a write access
+///
+/// By default it is derived from the data type of the access. Strict servers may expect a
+/// particular transport size for a field though, see [`S7Client::write_with_transport_size`](crate::S7Client::write_with_transport_size).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TransportSize {
+    /// Bit
+    Bit,
+    /// Byte, Word or Double Word
+    Byte,
+    /// Integer
+    Integer,
+    /// Real
+    Real,
+    /// Octet string
+    OctetString,
 }
 
 #[derive(Debug)]
@@ -85,3 +129,15 @@ impl From<S7DataTypes> for DataItemTransportSize {
         }
     }
 }
+
+impl From<TransportSize> for DataItemTransportSize {
+    fn from(transport_size: TransportSize) -> Self {
+        match transport_size {
+            TransportSize::Bit => Self::Bit,
+            TransportSize::Byte => Self::Byte,
+            TransportSize::Integer => Self::Integer,
+            TransportSize::Real => Self::Real,
+            TransportSize::OctetString => Self::OctetString,
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use bytes::{Buf, BufMut, BytesMut};
+use std::convert::TryFrom;
+
+use super::segments::header::{S7ProtocolHeader, USER_DATA as USER_DATA_MESSAGE_TYPE};
+use crate::errors::{DataItemErrorMapper, Error, S7DataItemResponseError, S7ProtocolError};
+use crate::S7Client;
+
+// **** Function groups of user data ****
+pub(crate) const GROUP_BLOCK_FUNCTIONS: u8 = 0x03;
+pub(crate) const GROUP_CPU_FUNCTIONS: u8 = 0x04;
+pub(crate) const GROUP_TIME_FUNCTIONS: u8 = 0x07;
+
+// **** Subfunctions of the block functions ****
+pub(crate) const SUBFUNCTION_BLOCK_INFO: u8 = 0x03;
+
+// **** Subfunctions of the CPU functions ****
+pub(crate) const SUBFUNCTION_READ_SZL: u8 = 0x01;
+pub(crate) const SUBFUNCTION_MESSAGE_SERVICE: u8 = 0x02;
+pub(crate) const SUBFUNCTION_ALARM_ACK: u8 = 0x0B;
+pub(crate) const SUBFUNCTION_ALARM_SQ_INDICATION: u8 = 0x11;
+pub(crate) const SUBFUNCTION_ALARM_S_INDICATION: u8 = 0x12;
+
+// **** Subfunctions of the time functions ****
+pub(crate) const SUBFUNCTION_READ_CLOCK: u8 = 0x01;
+pub(crate) const SUBFUNCTION_SET_CLOCK: u8 = 0x02;
+
+// Method of a request and of a response
+const METHOD_REQUEST: u8 = 0x11;
+const METHOD_RESPONSE: u8 = 0x12;
+// Type of a request and of data pushed by the PLC (upper nibble of the type/group byte)
+const TYPE_REQUEST: u8 = 0x40;
+const TYPE_PUSH: u8 = 0x00;
+
+const TRANSPORT_SIZE_OCTET_STRING: u8 = 0x09;
+pub(crate) const RETURN_CODE_SUCCESS: u8 = 0xFF;
+// Return code of a follow-up request for further parts of a response and of requests without data
+pub(crate) const RETURN_CODE_FOLLOW_UP: u8 = 0x0A;
+
+// Error codes of the parameters that signal that the requested object is not available
+const ERROR_INVALID_SZL_ID: u16 = 0xD401;
+const ERROR_INVALID_SZL_INDEX: u16 = 0xD402;
+
+/// Parameters of a user data response
+#[derive(Debug)]
+struct UserDataResponseParams {
+    method: u8,
+    type_group: u8,
+    subfunction: u8,
+    sequence_number: u8,
+    last_data_unit: bool,
+    error_code: u16,
+}
+
+impl TryFrom<&mut BytesMut> for UserDataResponseParams {
+    type Error = Error;
+
+    fn try_from(bytes: &mut BytesMut) -> Result<Self, Self::Error> {
+        // 3 bytes of parameter head, 1 byte parameter length and the parameters itself
+        if bytes.len() < 4 || bytes.len() < 4 + usize::from(bytes[3]) || bytes[3] < 8 {
+            return Err(Error::TryFrom(
+                bytes.to_vec(),
+                "Invalid length for user data parameters".to_string(),
+            ));
+        }
+        let _head = bytes.get_uint(3);
+        let length = usize::from(bytes.get_u8());
+        let mut params = bytes.split_to(length);
+
+        let method = params.get_u8();
+        let type_group = params.get_u8();
+        let subfunction = params.get_u8();
+        let sequence_number = params.get_u8();
+        let _data_unit_reference = params.get_u8();
+        let last_data_unit = params.get_u8() == 0;
+        let error_code = params.get_u16();
+
+        Ok(Self {
+            method,
+            type_group,
+            subfunction,
+            sequence_number,
+            last_data_unit,
+            error_code,
+        })
+    }
+}
+
+/// Data of a user data response
+#[derive(Debug)]
+pub(crate) struct UserDataResponse {
+    /// Return code of the data
+    pub(crate) return_code: u8,
+    /// Payload of the data (without return code, transport size and length)
+    pub(crate) data: BytesMut,
+    sequence_number: u8,
+    last_data_unit: bool,
+}
+
+/// User data pushed by the PLC without request, e.g. alarm indications
+#[derive(Debug)]
+pub(crate) struct PushedUserData {
+    pub(crate) function_group: u8,
+    pub(crate) subfunction: u8,
+    /// Payload of the data (without return code, transport size and length)
+    pub(crate) data: BytesMut,
+}
+
+/// Send a user data request and receive the response of the PLC
+///
+/// A `sequence_number` of 0 starts a new request, other sequence numbers request further parts of a
+/// response that did not fit into one PDU.
+pub(crate) async fn exchange_user_data(
+    client: &mut S7Client,
+    function_group: u8,
+    subfunction: u8,
+    sequence_number: u8,
+    return_code: u8,
+    payload: &[u8],
+) -> Result<UserDataResponse, Error> {
+    let request = build_user_data_request(
+        client,
+        function_group,
+        subfunction,
+        sequence_number,
+        return_code,
+        payload,
+    )?;
+    let response = client.exchange(request).await?;
+    parse_user_data_response(
+        response,
+        client.pdu_number,
+        client.config.strict_pdu_reference,
+    )
+}
+
+/// Build a user data request with the next PDU number of the client
+pub(crate) fn build_user_data_request(
+    client: &mut S7Client,
+    function_group: u8,
+    subfunction: u8,
+    sequence_number: u8,
+    return_code: u8,
+    payload: &[u8],
+) -> Result<BytesMut, Error> {
+    let mut params = BytesMut::new();
+    params.put_slice(&[0x00, 0x01, 0x12]);
+    if sequence_number == 0 {
+        params.put_u8(4);
+        params.put_slice(&[
+            METHOD_REQUEST,
+            TYPE_REQUEST | function_group,
+            subfunction,
+            sequence_number,
+        ]);
+    } else {
+        params.put_u8(8);
+        params.put_slice(&[
+            METHOD_RESPONSE,
+            TYPE_REQUEST | function_group,
+            subfunction,
+            sequence_number,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+        ]);
+    }
+
+    let mut data = BytesMut::new();
+    data.put_u8(return_code);
+    data.put_u8(if payload.is_empty() {
+        0x00
+    } else {
+        TRANSPORT_SIZE_OCTET_STRING
+    });
+    data.put_u16(u16::try_from(payload.len()).map_err(|_| Error::DataItemTooLarge)?);
+    data.put_slice(payload);
+
+    let mut bytes = BytesMut::new();
+    let req_header =
+        S7ProtocolHeader::build_user_data(&mut client.pdu_number, params.len(), data.len())?;
+    bytes.put(BytesMut::from(req_header));
+    bytes.put(params);
+    bytes.put(data);
+
+    Ok(bytes)
+}
+
+/// Parse the response to the user data request with the given PDU number
+pub(crate) fn parse_user_data_response(
+    mut response: BytesMut,
+    pdu_number: u16,
+    strict_pdu_reference: bool,
+) -> Result<UserDataResponse, Error> {
+    // check if s7 header is user data and the response to the current pdu
+    S7ProtocolHeader::try_from(&mut response)?
+        .is_user_data()?
+        .is_current_pdu_response(pdu_number, strict_pdu_reference)?;
+
+    let params = UserDataResponseParams::try_from(&mut response)?;
+    if params.method != METHOD_RESPONSE {
+        return Err(Error::RequestNotAcknowledged);
+    }
+    if params.error_code == ERROR_INVALID_SZL_ID || params.error_code == ERROR_INVALID_SZL_INDEX {
+        return Err(Error::FunctionNotSupported);
+    }
+    if params.error_code != 0 {
+        let [class, code] = params.error_code.to_be_bytes();
+        return Err(Error::S7ProtocolError(S7ProtocolError::from_codes(
+            Some(class),
+            Some(code),
+        )));
+    }
+
+    let (return_code, data) = parse_user_data(&mut response)?;
+
+    Ok(UserDataResponse {
+        return_code,
+        data,
+        sequence_number: params.sequence_number,
+        last_data_unit: params.last_data_unit,
+    })
+}
+
+/// Whether a PDU received from the PLC is user data pushed without request
+pub(crate) fn is_pushed_user_data(pdu: &[u8]) -> bool {
+    // 10 bytes of header, the type is the upper nibble of the 6th byte of the parameters
+    pdu.get(1) == Some(&USER_DATA_MESSAGE_TYPE)
+        && pdu
+            .get(15)
+            .is_some_and(|type_group| type_group & 0xF0 == TYPE_PUSH)
+}
+
+/// Parse user data pushed by the PLC
+pub(crate) fn parse_pushed_user_data(
+    mut pdu: BytesMut,
+    mapper: Option<&DataItemErrorMapper>,
+) -> Result<PushedUserData, Error> {
+    S7ProtocolHeader::try_from(&mut pdu)?.is_user_data()?;
+    let params = UserDataResponseParams::try_from(&mut pdu)?;
+    let (return_code, data) = parse_user_data(&mut pdu)?;
+    check_return_code(return_code, mapper)?;
+
+    Ok(PushedUserData {
+        function_group: params.type_group & 0x0F,
+        subfunction: params.subfunction,
+        data,
+    })
+}
+
+/// Split off return code and payload of the data part of user data
+fn parse_user_data(bytes: &mut BytesMut) -> Result<(u8, BytesMut), Error> {
+    if bytes.len() < 4 {
+        return Err(Error::TryFrom(
+            bytes.to_vec(),
+            "Invalid length for user data".to_string(),
+        ));
+    }
+    let return_code = bytes.get_u8();
+    let _transport_size = bytes.get_u8();
+    let length = usize::from(bytes.get_u16()).min(bytes.len());
+
+    Ok((return_code, bytes.split_to(length)))
+}
+
+/// Partial list of the system status list (SZL) of the PLC
+#[derive(Debug)]
+pub(crate) struct SzlList {
+    record_len: u16,
+    data: Vec<u8>,
+}
+
+impl SzlList {
+    /// Parse the payload of the first response to an SZL read
+    pub(crate) fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        // SZL id (2 bytes), index (2 bytes), record length (2 bytes) and record count (2 bytes)
+        if bytes.len() < 8 {
+            return Err(Error::TryFrom(
+                bytes.to_vec(),
+                "Invalid length for SZL header".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            record_len: u16::from_be_bytes([bytes[4], bytes[5]]),
+            data: bytes[8..].to_vec(),
+        })
+    }
+
+    /// Complete records of the list, an incomplete trailing record is skipped
+    pub(crate) fn records(&self) -> impl Iterator<Item = &[u8]> {
+        self.data
+            .chunks(usize::from(self.record_len).max(1))
+            .filter(|record| record.len() == usize::from(self.record_len))
+    }
+}
+
+/// Read a partial list of the system status list (SZL)
+///
+/// # Errors
+///
+/// Will return `Error::FunctionNotSupported` if the PLC does not provide the partial list.
+pub(crate) async fn read_szl(
+    client: &mut S7Client,
+    szl_id: u16,
+    index: u16,
+) -> Result<SzlList, Error> {
+    let mut payload = [0_u8; 4];
+    payload[..2].copy_from_slice(&szl_id.to_be_bytes());
+    payload[2..].copy_from_slice(&index.to_be_bytes());
+
+    let mut response = exchange_user_data(
+        client,
+        GROUP_CPU_FUNCTIONS,
+        SUBFUNCTION_READ_SZL,
+        0,
+        RETURN_CODE_SUCCESS,
+        &payload,
+    )
+    .await?;
+    check_return_code(response.return_code, client.config.error_mapper())?;
+    let mut list = SzlList::parse(&response.data)?;
+
+    // request the remaining parts of a list that did not fit into one PDU
+    while !response.last_data_unit {
+        response = exchange_user_data(
+            client,
+            GROUP_CPU_FUNCTIONS,
+            SUBFUNCTION_READ_SZL,
+            response.sequence_number,
+            RETURN_CODE_FOLLOW_UP,
+            &[],
+        )
+        .await?;
+        check_return_code(response.return_code, client.config.error_mapper())?;
+        list.data.extend_from_slice(&response.data);
+    }
+
+    Ok(list)
+}
+
+/// Check the return code of a user data response, describing unknown codes with `mapper`
+pub(crate) fn check_return_code(
+    return_code: u8,
+    mapper: Option<&DataItemErrorMapper>,
+) -> Result<(), Error> {
+    match return_code {
+        RETURN_CODE_SUCCESS => Ok(()),
+        // object does not exist
+        0x0A => Err(Error::FunctionNotSupported),
+        code => Err(Error::DataItemError(S7DataItemResponseError::from_code(
+            code, mapper,
+        ))),
+    }
+}
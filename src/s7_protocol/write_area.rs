@@ -1,15 +1,19 @@
 use bytes::{Buf, BufMut, BytesMut};
 use std::borrow::Cow;
 use std::convert::TryFrom;
+use std::ops::Range;
 
 use super::segments::{
     data_item::DataItem, header::S7ProtocolHeader, parameters::ReadWriteParams,
     request_item::RequestItem,
 };
-use super::types::{Area, DataItemTransportSize, WRITE_OPERATION};
-use crate::connection::{iso::TTPKTHeader, tcp::exchange_buffer};
-use crate::errors::{Error, S7DataItemResponseError, S7ProtocolError};
-use crate::{S7Client, S7WriteAccess};
+use super::types::{Area, DataItemTransportSize, TransportSize, WRITE_OPERATION};
+use crate::connection::iso::TTPKTHeader;
+use crate::errors::{
+    DataItemErrorMapper, Error, IsoError, S7DataItemResponseError, S7ProtocolError,
+};
+use crate::trace::event;
+use crate::{OpInfo, OpKind, S7Client, S7WriteAccess};
 
 impl<'a> ReadWriteParams<'a> {
     fn build_write(items: &'a [RequestItem]) -> Result<Self, Error> {
@@ -71,16 +75,158 @@ fn assert_pdu_size_for_write<'a>(
     Ok(())
 }
 
+/// Group consecutive write accesses into batches that each fit into a single request
+///
+/// Both the number of items and the amount of data per request are limited. Returns the index
+/// ranges of the batches, an access that does not fit into a request on its own ends up alone in
+/// its batch.
+pub(crate) fn batch_write_accesses(
+    data_items: &[S7WriteAccess<'_>],
+    max_pdu_size: usize,
+) -> Vec<Range<usize>> {
+    let mut batches = Vec::new();
+    let mut batch_start = 0;
+    for end in 1..=data_items.len() {
+        if end - batch_start > 1
+            && assert_pdu_size_for_write(&data_items[batch_start..end], max_pdu_size).is_err()
+        {
+            batches.push(batch_start..end - 1);
+            batch_start = end - 1;
+        }
+    }
+    if batch_start < data_items.len() {
+        batches.push(batch_start..data_items.len());
+    }
+    batches
+}
+
+/// Whether the write accesses fit into a single request
+pub(crate) fn fits_into_write_request(
+    data_items: &[S7WriteAccess<'_>],
+    max_pdu_size: usize,
+) -> bool {
+    assert_pdu_size_for_write(data_items, max_pdu_size).is_ok()
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip(client, data_item),
+        fields(
+            db_number = data_item.db_number(),
+            start = data_item.start(),
+            bytes = data_item.len()
+        ),
+        err(Display)
+    )
+)]
 pub(crate) async fn write_area_single(
     client: &mut S7Client,
     area: Area,
     data_item: S7WriteAccess<'_>,
+) -> Result<(), Error> {
+    write_area_single_with(client, area, data_item, None).await
+}
+
+/// Write a single item, announcing its data with `transport_size` instead of the automatic mapping if given
+pub(crate) async fn write_area_single_with(
+    client: &mut S7Client,
+    area: Area,
+    data_item: S7WriteAccess<'_>,
+    transport_size: Option<TransportSize>,
 ) -> Result<(), Error> {
     // Each PDU (TPKT Header + COTP Header + S7Header + S7Parameters + S7Data) must not exceed the maximum PDU length (bytes) negotiated with the
     // PLC during connection.
     // Moreover we must ensure that a "finite" number of items is send per PDU. If the command size does not fit in one PDU
     // then it must be split across more subsequent PDU.
 
+    // max data size per request (1 item per request)
+    // 10 bytes of header data, 2 bytes of param header, 12 bytes for the request item, 4 bytes of data item header and the actual data
+    let max_data_size = usize::from(client.pdu_length).saturating_sub(
+        S7ProtocolHeader::len_request()
+            + ReadWriteParams::len()
+            + RequestItem::len()
+            + DataItem::header_len(),
+    );
+
+    match data_item {
+        S7WriteAccess::Bytes {
+            db_number,
+            start,
+            data,
+        } if data.len() > max_data_size && max_data_size > 0 => {
+            // split request into multiple each smaller than the max PDU size,
+            // keep the chunks word aligned
+            let chunk_size = max_data_size & !1;
+            let accesses = data
+                .chunks(chunk_size)
+                .enumerate()
+                .map(|(i, chunk)| {
+                    #[allow(clippy::cast_possible_truncation)]
+                    let chunk_start = start + (i * chunk_size) as u32;
+                    S7WriteAccess::Bytes {
+                        db_number,
+                        start: chunk_start,
+                        data: chunk,
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            // build all requests up front, so they can be pipelined
+            let mut requests = Vec::with_capacity(accesses.len());
+            let mut pdu_numbers = Vec::with_capacity(accesses.len());
+            for (chunk, access) in accesses.iter().enumerate() {
+                let (bytes, operation) =
+                    build_write_request(client, area, *access, transport_size, chunk)?;
+                requests.push((bytes, Some(operation)));
+                pdu_numbers.push(client.pdu_number);
+            }
+
+            event!(
+                debug,
+                requests = requests.len(),
+                "split write into requests"
+            );
+            let responses = client.exchange_pipelined(requests).await?;
+            for ((response, pdu_number), access) in
+                responses.into_iter().zip(pdu_numbers).zip(accesses)
+            {
+                check_write_response(
+                    response,
+                    pdu_number,
+                    client.config.strict_pdu_reference,
+                    client.config.error_mapper(),
+                    area,
+                    &access,
+                )?;
+            }
+            Ok(())
+        }
+        _ => {
+            let (bytes, operation) =
+                build_write_request(client, area, data_item, transport_size, 0)?;
+            let response = client.exchange_operation(bytes, Some(operation)).await?;
+            check_write_response(
+                response,
+                client.pdu_number,
+                client.config.strict_pdu_reference,
+                client.config.error_mapper(),
+                area,
+                &data_item,
+            )
+        }
+    }
+}
+
+/// Build the request writing a single item together with its description as operation
+fn build_write_request(
+    client: &mut S7Client,
+    area: Area,
+    data_item: S7WriteAccess<'_>,
+    transport_size: Option<TransportSize>,
+    chunk: usize,
+) -> Result<(BytesMut, OpInfo), Error> {
     assert_pdu_size_for_write(&[data_item], client.pdu_length.into())?;
 
     let request_params = BytesMut::from(ReadWriteParams::build_write(&[RequestItem::build(
@@ -90,8 +236,9 @@ pub(crate) async fn write_area_single(
         data_item.data_type(),
         data_item.len(),
     )?])?);
-    let data_items: BytesMut =
-        DataItem::build_write2(data_item.data_type().into(), data_item.data())?.into();
+    let transport_size =
+        transport_size.map_or_else(|| data_item.data_type().into(), DataItemTransportSize::from);
+    let data_items: BytesMut = DataItem::build_write2(transport_size, data_item.data())?.into();
 
     // create data buffer
     let mut bytes = BytesMut::new();
@@ -105,14 +252,31 @@ pub(crate) async fn write_area_single(
     bytes.put(request_params);
     bytes.put(data_items);
 
-    let mut response = exchange_buffer(&mut client.connection, bytes).await?;
+    let operation = OpInfo {
+        kind: OpKind::Write,
+        area,
+        db_number: data_item.db_number(),
+        start: data_item.start(),
+        chunk,
+    };
+    Ok((bytes, operation))
+}
 
+/// Check the response to writing a single item
+fn check_write_response(
+    mut response: BytesMut,
+    pdu_number: u16,
+    strict_pdu_reference: bool,
+    mapper: Option<&DataItemErrorMapper>,
+    area: Area,
+    data_item: &S7WriteAccess<'_>,
+) -> Result<(), Error> {
     // check if s7 header is ack with data and check for errors
     // check if pdu of response matches request pdu
     let response_header = S7ProtocolHeader::try_from(&mut response)?;
     response_header
         .is_ack()?
-        .is_current_pdu_response(client.pdu_number)?;
+        .is_current_pdu_response(pdu_number, strict_pdu_reference)?;
 
     // Check for errors
     if response_header.has_error() {
@@ -129,12 +293,34 @@ pub(crate) async fn write_area_single(
     if error_code == 255 {
         Ok(())
     } else {
-        Err(Error::DataItemError(S7DataItemResponseError::from(
-            error_code,
-        )))
+        Err(write_item_error(area, data_item, error_code, mapper))
+    }
+}
+
+/// Error of a rejected write identifying the write access
+fn write_item_error(
+    area: Area,
+    access: &S7WriteAccess<'_>,
+    error_code: u8,
+    mapper: Option<&DataItemErrorMapper>,
+) -> Error {
+    let (byte, bit) = match access {
+        S7WriteAccess::Bytes { start, .. } => (*start, None),
+        S7WriteAccess::Bit { byte, bit, .. } => (*byte, Some(*bit)),
+    };
+    Error::WriteItemError {
+        area,
+        db_number: access.db_number(),
+        byte,
+        bit,
+        error: S7DataItemResponseError::from_code(error_code, mapper),
     }
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(client, info), fields(items = info.len()), err(Display))
+)]
 pub(crate) async fn write_area_multi(
     client: &mut S7Client,
     area: Area,
@@ -142,8 +328,8 @@ pub(crate) async fn write_area_multi(
 ) -> Result<Vec<Result<(), Error>>, Error> {
     // Each PDU (TPKT Header + COTP Header + S7Header + S7Parameters + S7Data) must not exceed the maximum PDU length (bytes) negotiated with the
     // PLC during connection.
-    // Moreover we must ensure that a "finite" number of items is send per PDU. If the command size does not fit in one PDU
-    // then it must be split across more subsequent PDU.
+    // Moreover we must ensure that a "finite" number of items is send per PDU. Commands that do not fit into one PDU
+    // are rejected, callers split them into batches with `batch_write_accesses` beforehand.
 
     assert_pdu_size_for_write(info, client.pdu_length.into())?;
 
@@ -183,14 +369,21 @@ pub(crate) async fn write_area_multi(
     bytes.put(request_params);
     bytes.put(data_items);
 
-    let mut response = exchange_buffer(&mut client.connection, bytes).await?;
+    let operation = info.first().map(|first| OpInfo {
+        kind: OpKind::Write,
+        area,
+        db_number: first.db_number(),
+        start: first.start(),
+        chunk: 0,
+    });
+    let mut response = client.exchange_operation(bytes, operation).await?;
 
     // check if s7 header is ack with data and check for errors
     // check if pdu of response matches request pdu
     let response_header = S7ProtocolHeader::try_from(&mut response)?;
     response_header
         .is_ack()?
-        .is_current_pdu_response(client.pdu_number)?;
+        .is_current_pdu_response(client.pdu_number, client.config.strict_pdu_reference)?;
 
     // Check for errors
     if response_header.has_error() {
@@ -200,19 +393,84 @@ pub(crate) async fn write_area_multi(
         )));
     }
 
+    // every item must be answered with its own return code
     let read_params = ReadWriteParams::from(&mut response);
+    if usize::from(read_params.item_count) != info.len() {
+        return Err(Error::TryFrom(
+            response.to_vec(),
+            format!(
+                "Response contains {} results for {} written items",
+                read_params.item_count,
+                info.len()
+            ),
+        ));
+    }
+    if response.len() < info.len() {
+        return Err(Error::ISOResponse(IsoError::ShortPacket));
+    }
 
-    Ok((0..read_params.item_count)
-        .map(|_| {
+    Ok(info
+        .iter()
+        .map(|access| {
             let error_code = response.get_u8();
             // 255 signals everything went alright
             if error_code == 255 {
                 Ok(())
             } else {
-                Err(Error::DataItemError(S7DataItemResponseError::from(
+                Err(write_item_error(
+                    area,
+                    access,
                     error_code,
-                )))
+                    client.config.error_mapper(),
+                ))
             }
         })
         .collect::<Vec<Result<(), Error>>>())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockPlc;
+
+    #[tokio::test]
+    async fn large_write_is_split() {
+        let plc = MockPlc::start().await;
+        plc.set_db(1, &[0; 2048]);
+        plc.state().pdu_length = 240;
+        let mut client = plc.client().await;
+
+        let data: Vec<u8> = (0..2000_u16).map(|i| (i % 251) as u8).collect();
+        client.db_write(1, 10, &data).await.unwrap();
+
+        let db = plc.db(1);
+        assert_eq!(&db[10..2010], data.as_slice());
+        assert!(db[..10].iter().chain(&db[2010..]).all(|byte| *byte == 0));
+        // negotiation and 2000 bytes in chunks of 212 bytes
+        assert_eq!(plc.state().requests.len(), 1 + 10);
+    }
+
+    #[tokio::test]
+    async fn write_to_missing_db_identifies_access() {
+        let plc = MockPlc::start().await;
+        let mut client = plc.client().await;
+
+        let error = client.db_write(42, 6, &[0x01]).await.unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::WriteItemError {
+                area: Area::DataBlock,
+                db_number: 42,
+                byte: 6,
+                bit: None,
+                error: S7DataItemResponseError::ObjectDoesNotExist,
+            }
+        );
+        assert_eq!(error.to_string(), "Error on writing DB42 at byte 6");
+        assert_eq!(
+            std::error::Error::source(&error).unwrap().to_string(),
+            "S7 Data Item response error: Object does not exist"
+        );
+    }
+}
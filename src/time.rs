@@ -0,0 +1,566 @@
+//! Codecs for the date and time types of S7 PLCs.
+//!
+//! All functions operate on plain byte slices in PLC (big-endian) byte order, so data that was read
+//! into an own buffer can be decoded without another read call.
+//!
+//! | S7 type         | Size     | Functions                              |
+//! |-----------------|----------|----------------------------------------|
+//! | `DATE_AND_TIME` | 8 bytes  | [`decode_dt`], [`encode_dt`]           |
+//! | `DTL`           | 12 bytes | [`decode_dtl`], [`encode_dtl`]         |
+//! | `S5TIME`        | 2 bytes  | [`decode_s5time`], [`encode_s5time`]   |
+//! | `TIME`          | 4 bytes  | [`decode_time`], [`encode_time`]       |
+//! | `DATE`          | 2 bytes  | [`decode_date`], [`encode_date`]       |
+//! | `TIME_OF_DAY`   | 4 bytes  | [`decode_tod`], [`encode_tod`]         |
+
+use std::time::Duration;
+
+use crate::errors::Error;
+
+const NANOS_PER_MILLI: u32 = 1_000_000;
+const MILLIS_PER_DAY: u128 = 24 * 60 * 60 * 1000;
+// Maximum value of an S5TIME (999 * 10 s)
+const S5TIME_MAX: Duration = Duration::from_secs(9990);
+// Days between 1970-01-01 and 1990-01-01, the epoch of the S7 `DATE` type
+const DATE_EPOCH_DAYS: i64 = 7305;
+
+/// Calendar date and time of day as used by the S7 date and time types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct S7DateTime {
+    /// Year (1990 to 2089 for `DATE_AND_TIME`, 1970 to 2262 for `DTL`)
+    pub year: u16,
+    /// Month (1 to 12)
+    pub month: u8,
+    /// Day of the month (1 to 31)
+    pub day: u8,
+    /// Day of the week (1 = Sunday to 7 = Saturday)
+    pub weekday: u8,
+    /// Hour (0 to 23)
+    pub hour: u8,
+    /// Minute (0 to 59)
+    pub minute: u8,
+    /// Second (0 to 59)
+    pub second: u8,
+    /// Fraction of the second in nanoseconds (`DATE_AND_TIME` only stores milliseconds)
+    pub nanosecond: u32,
+}
+
+impl S7DateTime {
+    /// Create a validated date and time, the day of the week is calculated from the date
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if any of the components is out of range.
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanosecond: u32,
+    ) -> Result<Self, Error> {
+        let date_time = Self {
+            year,
+            month,
+            day,
+            weekday: weekday(year, month, day),
+            hour,
+            minute,
+            second,
+            nanosecond,
+        };
+        date_time.validate()?;
+        Ok(date_time)
+    }
+
+    /// Check that all components describe a valid date and time
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if any of the components is out of range.
+    pub fn validate(&self) -> Result<(), Error> {
+        if !(1..=12).contains(&self.month) {
+            return Err(invalid("month", self.month));
+        }
+        if self.day == 0 || self.day > days_in_month(self.year, self.month) {
+            return Err(invalid("day", self.day));
+        }
+        if !(1..=7).contains(&self.weekday) {
+            return Err(invalid("weekday", self.weekday));
+        }
+        if self.hour > 23 {
+            return Err(invalid("hour", self.hour));
+        }
+        if self.minute > 59 {
+            return Err(invalid("minute", self.minute));
+        }
+        if self.second > 59 {
+            return Err(invalid("second", self.second));
+        }
+        if self.nanosecond >= 1_000_000_000 {
+            return Err(invalid("nanosecond", self.nanosecond));
+        }
+        Ok(())
+    }
+}
+
+fn invalid(component: &str, value: impl std::fmt::Display) -> Error {
+    Error::Conversion(format!("Invalid {component} '{value}' in date and time"))
+}
+
+fn expect_len(bytes: &[u8], len: usize, type_name: &str) -> Result<(), Error> {
+    if bytes.len() == len {
+        Ok(())
+    } else {
+        Err(Error::Conversion(format!(
+            "{type_name} requires {len} bytes but {} were given",
+            bytes.len()
+        )))
+    }
+}
+
+fn from_bcd(byte: u8) -> Result<u8, Error> {
+    let (high, low) = (byte >> 4, byte & 0x0F);
+    if high > 9 || low > 9 {
+        return Err(Error::Conversion(format!(
+            "Byte {byte:#04x} is not a valid BCD value"
+        )));
+    }
+    Ok(high * 10 + low)
+}
+
+pub(crate) fn to_bcd(value: u8) -> u8 {
+    ((value / 10) << 4) | (value % 10)
+}
+
+fn is_leap_year(year: u16) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 of a date of the proleptic Gregorian calendar
+fn days_from_civil(year: u16, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// Date of the proleptic Gregorian calendar from the days since 1970-01-01
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn civil_from_days(days: i64) -> (u16, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as u16, month as u8, day as u8)
+}
+
+// Day of the week with 1 = Sunday to 7 = Saturday
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn weekday(year: u16, month: u8, day: u8) -> u8 {
+    // 1970-01-01 was a Thursday
+    ((days_from_civil(year, month, day) + 4).rem_euclid(7) + 1) as u8
+}
+
+/// Decode an 8 byte `DATE_AND_TIME` (`DT`) value
+///
+/// All components are BCD encoded, years 90 to 99 map to 1990 to 1999 and 00 to 89 to 2000 to 2089.
+/// # Errors
+///
+/// Will return `Error::Conversion` if the bytes are no valid `DATE_AND_TIME`.
+pub fn decode_dt(bytes: &[u8]) -> Result<S7DateTime, Error> {
+    expect_len(bytes, 8, "DATE_AND_TIME")?;
+
+    let year = u16::from(from_bcd(bytes[0])?);
+    let milliseconds = u32::from(from_bcd(bytes[6])?) * 10 + u32::from(bytes[7] >> 4);
+    if bytes[7] >> 4 > 9 {
+        return Err(invalid("millisecond", milliseconds));
+    }
+    let date_time = S7DateTime {
+        year: if year >= 90 { 1900 + year } else { 2000 + year },
+        month: from_bcd(bytes[1])?,
+        day: from_bcd(bytes[2])?,
+        hour: from_bcd(bytes[3])?,
+        minute: from_bcd(bytes[4])?,
+        second: from_bcd(bytes[5])?,
+        nanosecond: milliseconds * NANOS_PER_MILLI,
+        weekday: bytes[7] & 0x0F,
+    };
+    date_time.validate()?;
+    Ok(date_time)
+}
+
+/// Encode a date and time as 8 byte `DATE_AND_TIME` (`DT`) value
+///
+/// The fraction of the second is truncated to milliseconds.
+/// # Errors
+///
+/// Will return `Error::Conversion` if the date and time is invalid or outside of 1990 to 2089.
+#[allow(clippy::cast_possible_truncation)]
+pub fn encode_dt(date_time: &S7DateTime) -> Result<[u8; 8], Error> {
+    date_time.validate()?;
+    if !(1990..=2089).contains(&date_time.year) {
+        return Err(invalid("year", date_time.year));
+    }
+
+    let milliseconds = date_time.nanosecond / NANOS_PER_MILLI;
+    Ok([
+        to_bcd((date_time.year % 100) as u8),
+        to_bcd(date_time.month),
+        to_bcd(date_time.day),
+        to_bcd(date_time.hour),
+        to_bcd(date_time.minute),
+        to_bcd(date_time.second),
+        to_bcd((milliseconds / 10) as u8),
+        (((milliseconds % 10) as u8) << 4) | date_time.weekday,
+    ])
+}
+
+/// Decode a 12 byte `DTL` value
+///
+/// Unlike `DATE_AND_TIME` the year is a plain big-endian `u16` and the nanoseconds a big-endian `u32`.
+/// # Errors
+///
+/// Will return `Error::Conversion` if the bytes are no valid `DTL`.
+pub fn decode_dtl(bytes: &[u8]) -> Result<S7DateTime, Error> {
+    expect_len(bytes, 12, "DTL")?;
+
+    let date_time = S7DateTime {
+        year: u16::from_be_bytes([bytes[0], bytes[1]]),
+        month: bytes[2],
+        day: bytes[3],
+        weekday: bytes[4],
+        hour: bytes[5],
+        minute: bytes[6],
+        second: bytes[7],
+        nanosecond: u32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+    };
+    date_time.validate()?;
+    Ok(date_time)
+}
+
+/// Encode a date and time as 12 byte `DTL` value
+/// # Errors
+///
+/// Will return `Error::Conversion` if the date and time is invalid or outside of 1970 to 2262.
+pub fn encode_dtl(date_time: &S7DateTime) -> Result<[u8; 12], Error> {
+    date_time.validate()?;
+    if !(1970..=2262).contains(&date_time.year) {
+        return Err(invalid("year", date_time.year));
+    }
+
+    let year = date_time.year.to_be_bytes();
+    let nanosecond = date_time.nanosecond.to_be_bytes();
+    Ok([
+        year[0],
+        year[1],
+        date_time.month,
+        date_time.day,
+        date_time.weekday,
+        date_time.hour,
+        date_time.minute,
+        date_time.second,
+        nanosecond[0],
+        nanosecond[1],
+        nanosecond[2],
+        nanosecond[3],
+    ])
+}
+
+/// Decode a 2 byte `S5TIME` value
+///
+/// Bits 12 and 13 select the time base (10 ms, 100 ms, 1 s or 10 s), bits 0 to 11 hold the BCD
+/// encoded factor from 0 to 999.
+/// # Errors
+///
+/// Will return `Error::Conversion` if the bytes are no valid `S5TIME`.
+pub fn decode_s5time(bytes: &[u8]) -> Result<Duration, Error> {
+    expect_len(bytes, 2, "S5TIME")?;
+
+    let base = match (bytes[0] >> 4) & 0x03 {
+        0 => Duration::from_millis(10),
+        1 => Duration::from_millis(100),
+        2 => Duration::from_secs(1),
+        _ => Duration::from_secs(10),
+    };
+    let hundreds = bytes[0] & 0x0F;
+    if hundreds > 9 {
+        return Err(Error::Conversion(format!(
+            "Byte {:#04x} is not a valid BCD value",
+            bytes[0]
+        )));
+    }
+    let factor = u32::from(hundreds) * 100 + u32::from(from_bcd(bytes[1])?);
+    Ok(base * factor)
+}
+
+/// Encode a duration as 2 byte `S5TIME` value
+///
+/// The smallest time base that is able to hold the duration is chosen, parts of the duration
+/// below the resolution of that time base are truncated.
+/// # Errors
+///
+/// Will return `Error::Conversion` if the duration is longer than 9990 seconds.
+#[allow(clippy::cast_possible_truncation)]
+pub fn encode_s5time(duration: Duration) -> Result<[u8; 2], Error> {
+    if duration > S5TIME_MAX {
+        return Err(Error::Conversion(format!(
+            "Duration {duration:?} exceeds the maximum S5TIME of {S5TIME_MAX:?}"
+        )));
+    }
+
+    let milliseconds = duration.as_millis();
+    let (base, factor) = [10_u128, 100, 1000, 10_000]
+        .into_iter()
+        .enumerate()
+        .map(|(base, resolution)| (base as u8, milliseconds / resolution))
+        .find(|(_, factor)| *factor <= 999)
+        .unwrap_or((3, 999));
+    let factor = factor as u16;
+    Ok([
+        (base << 4) | (factor / 100) as u8,
+        to_bcd((factor % 100) as u8),
+    ])
+}
+
+/// Decode a 4 byte IEC `TIME` value (signed milliseconds)
+/// # Errors
+///
+/// Will return `Error::Conversion` if the bytes have the wrong length or the `TIME` is negative.
+pub fn decode_time(bytes: &[u8]) -> Result<Duration, Error> {
+    expect_len(bytes, 4, "TIME")?;
+
+    let milliseconds = i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    u64::try_from(milliseconds)
+        .map(Duration::from_millis)
+        .map_err(|_| {
+            Error::Conversion(format!(
+                "Negative TIME of {milliseconds} ms can not be represented as duration"
+            ))
+        })
+}
+
+/// Encode a duration as 4 byte IEC `TIME` value, truncated to milliseconds
+/// # Errors
+///
+/// Will return `Error::Conversion` if the duration exceeds the maximum `TIME` of about 24.8 days.
+pub fn encode_time(duration: Duration) -> Result<[u8; 4], Error> {
+    i32::try_from(duration.as_millis())
+        .map(i32::to_be_bytes)
+        .map_err(|_| {
+            Error::Conversion(format!(
+                "Duration {duration:?} exceeds the maximum TIME of {} ms",
+                i32::MAX
+            ))
+        })
+}
+
+/// Decode a 2 byte `DATE` value (days since 1990-01-01) into a date at midnight
+/// # Errors
+///
+/// Will return `Error::Conversion` if the bytes have the wrong length.
+pub fn decode_date(bytes: &[u8]) -> Result<S7DateTime, Error> {
+    expect_len(bytes, 2, "DATE")?;
+
+    let days = i64::from(u16::from_be_bytes([bytes[0], bytes[1]]));
+    let (year, month, day) = civil_from_days(DATE_EPOCH_DAYS + days);
+    S7DateTime::new(year, month, day, 0, 0, 0, 0)
+}
+
+/// Encode the date part of a date and time as 2 byte `DATE` value (days since 1990-01-01)
+/// # Errors
+///
+/// Will return `Error::Conversion` if the date is invalid or outside of 1990-01-01 to 2169-06-06.
+pub fn encode_date(date_time: &S7DateTime) -> Result<[u8; 2], Error> {
+    date_time.validate()?;
+
+    let days = days_from_civil(date_time.year, date_time.month, date_time.day) - DATE_EPOCH_DAYS;
+    u16::try_from(days)
+        .map(u16::to_be_bytes)
+        .map_err(|_| invalid("year", date_time.year))
+}
+
+/// Decode a 4 byte `TIME_OF_DAY` value (milliseconds since midnight)
+/// # Errors
+///
+/// Will return `Error::Conversion` if the bytes have the wrong length or exceed one day.
+pub fn decode_tod(bytes: &[u8]) -> Result<Duration, Error> {
+    expect_len(bytes, 4, "TIME_OF_DAY")?;
+
+    let milliseconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    if u128::from(milliseconds) >= MILLIS_PER_DAY {
+        return Err(Error::Conversion(format!(
+            "TIME_OF_DAY of {milliseconds} ms exceeds one day"
+        )));
+    }
+    Ok(Duration::from_millis(u64::from(milliseconds)))
+}
+
+/// Encode the time since midnight as 4 byte `TIME_OF_DAY` value, truncated to milliseconds
+/// # Errors
+///
+/// Will return `Error::Conversion` if the duration is one day or longer.
+#[allow(clippy::cast_possible_truncation)]
+pub fn encode_tod(time_of_day: Duration) -> Result<[u8; 4], Error> {
+    let milliseconds = time_of_day.as_millis();
+    if milliseconds >= MILLIS_PER_DAY {
+        return Err(Error::Conversion(format!(
+            "TIME_OF_DAY of {time_of_day:?} exceeds one day"
+        )));
+    }
+    Ok((milliseconds as u32).to_be_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dt_round_trip() {
+        let date_time = S7DateTime::new(2024, 2, 29, 23, 59, 58, 123_000_000).unwrap();
+        assert_eq!(date_time.weekday, 5);
+
+        let bytes = encode_dt(&date_time).unwrap();
+        assert_eq!(bytes, [0x24, 0x02, 0x29, 0x23, 0x59, 0x58, 0x12, 0x35]);
+        assert_eq!(decode_dt(&bytes).unwrap(), date_time);
+    }
+
+    #[test]
+    fn dt_boundaries() {
+        let first = S7DateTime::new(1990, 1, 1, 0, 0, 0, 0).unwrap();
+        assert_eq!(encode_dt(&first).unwrap()[0], 0x90);
+        assert_eq!(decode_dt(&encode_dt(&first).unwrap()).unwrap(), first);
+
+        let last = S7DateTime::new(2089, 12, 31, 23, 59, 59, 999_000_000).unwrap();
+        assert_eq!(encode_dt(&last).unwrap()[0], 0x89);
+        assert_eq!(decode_dt(&encode_dt(&last).unwrap()).unwrap(), last);
+
+        let too_late = S7DateTime::new(2090, 1, 1, 0, 0, 0, 0).unwrap();
+        assert!(matches!(encode_dt(&too_late), Err(Error::Conversion(_))));
+        // invalid BCD and invalid month
+        assert!(decode_dt(&[0x24, 0x1A, 0x01, 0, 0, 0, 0, 0x01]).is_err());
+        assert!(decode_dt(&[0x24, 0x13, 0x01, 0, 0, 0, 0, 0x01]).is_err());
+        assert!(decode_dt(&[0x24, 0x01]).is_err());
+    }
+
+    #[test]
+    fn dtl_round_trip() {
+        let date_time = S7DateTime::new(2262, 4, 11, 12, 30, 1, 999_999_999).unwrap();
+        let bytes = encode_dtl(&date_time).unwrap();
+        assert_eq!(&bytes[..2], &2262_u16.to_be_bytes());
+        assert_eq!(decode_dtl(&bytes).unwrap(), date_time);
+
+        let first = S7DateTime::new(1970, 1, 1, 0, 0, 0, 0).unwrap();
+        assert_eq!(first.weekday, 5);
+        assert_eq!(decode_dtl(&encode_dtl(&first).unwrap()).unwrap(), first);
+
+        let too_early = S7DateTime::new(1969, 12, 31, 0, 0, 0, 0).unwrap();
+        assert!(encode_dtl(&too_early).is_err());
+        assert!(S7DateTime::new(2023, 2, 29, 0, 0, 0, 0).is_err());
+    }
+
+    #[test]
+    fn s5time_round_trip() {
+        for (duration, bytes) in [
+            (Duration::ZERO, [0x00, 0x00]),
+            (Duration::from_millis(10), [0x00, 0x01]),
+            (Duration::from_millis(9990), [0x09, 0x99]),
+            (Duration::from_secs(10), [0x11, 0x00]),
+            (Duration::from_secs(99), [0x19, 0x90]),
+            (Duration::from_secs(100), [0x21, 0x00]),
+            (Duration::from_secs(999), [0x29, 0x99]),
+            (Duration::from_secs(9990), [0x39, 0x99]),
+        ] {
+            assert_eq!(encode_s5time(duration).unwrap(), bytes);
+            assert_eq!(decode_s5time(&bytes).unwrap(), duration);
+        }
+
+        // truncated to the resolution of the time base
+        assert_eq!(
+            encode_s5time(Duration::from_millis(10_015)).unwrap(),
+            [0x11, 0x00]
+        );
+        assert!(encode_s5time(Duration::from_secs(9991)).is_err());
+        assert!(decode_s5time(&[0x0A, 0x00]).is_err());
+    }
+
+    #[test]
+    fn s5time_round_trip_covers_all_time_bases() {
+        for (base, resolution) in [(0_u8, 10_u64), (1, 100), (2, 1000), (3, 10_000)] {
+            for hundreds in 0..=9_u8 {
+                for rest in 0..=99_u8 {
+                    let bytes = [(base << 4) | hundreds, to_bcd(rest)];
+                    let factor = u64::from(hundreds) * 100 + u64::from(rest);
+                    let duration = decode_s5time(&bytes).unwrap();
+                    assert_eq!(duration, Duration::from_millis(resolution * factor));
+                    // a smaller time base may be chosen for the same duration
+                    assert_eq!(
+                        decode_s5time(&encode_s5time(duration).unwrap()).unwrap(),
+                        duration
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn time_round_trip() {
+        for milliseconds in [0_u64, 1, 86_400_000, 2_147_483_647] {
+            let duration = Duration::from_millis(milliseconds);
+            assert_eq!(
+                decode_time(&encode_time(duration).unwrap()).unwrap(),
+                duration
+            );
+        }
+        assert!(encode_time(Duration::from_millis(2_147_483_648)).is_err());
+        assert!(decode_time(&(-1_i32).to_be_bytes()).is_err());
+    }
+
+    #[test]
+    fn date_round_trip() {
+        let epoch = decode_date(&[0x00, 0x00]).unwrap();
+        assert_eq!((epoch.year, epoch.month, epoch.day), (1990, 1, 1));
+        assert_eq!(epoch.weekday, 2);
+
+        let last = decode_date(&[0xFF, 0xFF]).unwrap();
+        assert_eq!((last.year, last.month, last.day), (2169, 6, 6));
+        assert_eq!(encode_date(&last).unwrap(), [0xFF, 0xFF]);
+
+        let date = S7DateTime::new(2000, 3, 1, 0, 0, 0, 0).unwrap();
+        assert_eq!(decode_date(&encode_date(&date).unwrap()).unwrap(), date);
+        assert!(encode_date(&S7DateTime::new(1989, 12, 31, 0, 0, 0, 0).unwrap()).is_err());
+    }
+
+    #[test]
+    fn tod_round_trip() {
+        for milliseconds in [0_u64, 1, 43_200_000, 86_399_999] {
+            let duration = Duration::from_millis(milliseconds);
+            assert_eq!(
+                decode_tod(&encode_tod(duration).unwrap()).unwrap(),
+                duration
+            );
+        }
+        assert!(encode_tod(Duration::from_secs(24 * 60 * 60)).is_err());
+        assert!(decode_tod(&86_400_000_u32.to_be_bytes()).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+//! Structured logging of the protocol exchange with the `tracing` feature.
+//!
+//! Without the feature the macros expand to nothing, so logging has no overhead.
+
+/// Emit a [`tracing`](https://docs.rs/tracing) event if the `tracing` feature is enabled
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+pub(crate) use event;
+
+/// Hex dump of a buffer for `trace` level events
+#[cfg(feature = "tracing")]
+pub(crate) struct Hex<'a>(pub(crate) &'a [u8]);
+
+#[cfg(feature = "tracing")]
+impl std::fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, byte) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{byte:02X}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Log a received response or the error receiving it
+#[cfg(feature = "tracing")]
+pub(crate) fn response(response: &Result<bytes::BytesMut, crate::errors::Error>) {
+    use crate::s7_protocol::segments::header::S7ProtocolHeader;
+
+    match response {
+        Ok(data) => {
+            tracing::debug!(
+                bytes = data.len(),
+                pdu_reference = ?S7ProtocolHeader::pdu_reference_of(data).ok(),
+                "received response"
+            );
+            tracing::trace!(response = %Hex(data));
+        }
+        Err(error) => tracing::warn!(
+            %error,
+            connection_error = error.is_connection_error(),
+            "receiving response failed"
+        ),
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::Hex;
+
+    #[test]
+    fn hex_dump_separates_bytes() {
+        assert_eq!(Hex(&[0x32, 0x01, 0x0A]).to_string(), "32 01 0A");
+        assert_eq!(Hex(&[]).to_string(), "");
+    }
+}
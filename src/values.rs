@@ -0,0 +1,415 @@
+//! Typed values of S7 PLC variables.
+//!
+//! S7 PLCs store all multi-byte values in big-endian byte order.
+
+use crate::errors::Error;
+use crate::S7DataType;
+
+/// Decoded value of an S7 PLC variable
+#[derive(Debug, Clone, PartialEq)]
+pub enum S7Value {
+    /// `BOOL`
+    Bool(bool),
+    /// `BYTE`
+    Byte(u8),
+    /// `CHAR` (interpreted as Latin-1)
+    Char(char),
+    /// `WORD`
+    Word(u16),
+    /// `INT`
+    Int(i16),
+    /// `DWORD`
+    DWord(u32),
+    /// `DINT`
+    DInt(i32),
+    /// `REAL`
+    Real(f32),
+}
+
+/// Conversion from the big-endian representation of a value in the PLC
+pub(crate) trait FromS7Bytes: Sized {
+    /// Number of bytes of the representation in the PLC
+    const SIZE: usize;
+
+    fn from_be_slice(bytes: &[u8]) -> Self;
+
+    fn from_s7_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() == Self::SIZE {
+            Ok(Self::from_be_slice(bytes))
+        } else {
+            Err(Error::Conversion(format!(
+                "Expected {} bytes but got {}",
+                Self::SIZE,
+                bytes.len()
+            )))
+        }
+    }
+
+    fn from_s7_array(bytes: &[u8]) -> Result<Vec<Self>, Error> {
+        if bytes.len() % Self::SIZE == 0 {
+            Ok(bytes
+                .chunks_exact(Self::SIZE)
+                .map(Self::from_be_slice)
+                .collect())
+        } else {
+            Err(Error::Conversion(format!(
+                "Expected a multiple of {} bytes but got {}",
+                Self::SIZE,
+                bytes.len()
+            )))
+        }
+    }
+}
+
+impl FromS7Bytes for bool {
+    const SIZE: usize = 1;
+
+    fn from_be_slice(bytes: &[u8]) -> Self {
+        bytes[0] > 0
+    }
+}
+
+impl FromS7Bytes for char {
+    const SIZE: usize = 1;
+
+    fn from_be_slice(bytes: &[u8]) -> Self {
+        char::from(bytes[0])
+    }
+}
+
+macro_rules! impl_from_s7_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl FromS7Bytes for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn from_be_slice(bytes: &[u8]) -> Self {
+                    let mut buffer = [0_u8; std::mem::size_of::<$t>()];
+                    buffer.copy_from_slice(bytes);
+                    <$t>::from_be_bytes(buffer)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_s7_bytes!(u8, u16, i16, u32, i32, f32);
+
+impl S7Value {
+    /// Decode the bytes read from the PLC as a value of the given data type
+    ///
+    /// ```rust
+    /// use s7client::{S7DataType, S7Value};
+    ///
+    /// let value = S7Value::decode(&[0xFF, 0xFE], S7DataType::S7INT)?;
+    /// assert_eq!(value, S7Value::Int(-2));
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// ```
+    /// # Errors
+    ///
+    /// Will return `Error::Conversion` if the number of bytes does not match the data type or the
+    /// data type (`COUNTER`, `TIMER`) can not be decoded as value.
+    pub fn decode(data: &[u8], data_type: S7DataType) -> Result<Self, Error> {
+        Ok(match data_type {
+            S7DataType::S7BIT => Self::Bool(bool::from_s7_bytes(data)?),
+            S7DataType::S7BYTE => Self::Byte(u8::from_s7_bytes(data)?),
+            S7DataType::S7CHAR => Self::Char(char::from_s7_bytes(data)?),
+            S7DataType::S7WORD => Self::Word(u16::from_s7_bytes(data)?),
+            S7DataType::S7INT => Self::Int(i16::from_s7_bytes(data)?),
+            S7DataType::S7DWORD => Self::DWord(u32::from_s7_bytes(data)?),
+            S7DataType::S7DINT => Self::DInt(i32::from_s7_bytes(data)?),
+            S7DataType::S7REAL => Self::Real(f32::from_s7_bytes(data)?),
+            S7DataType::S7COUNTER | S7DataType::S7TIMER => {
+                return Err(Error::Conversion(format!(
+                    "{data_type:?} can not be decoded as value"
+                )))
+            }
+        })
+    }
+
+    /// Encode the value in the big-endian representation of the PLC
+    ///
+    /// `Char` values outside of Latin-1 can not be represented in the PLC and are encoded as `?`.
+    ///
+    /// ```rust
+    /// use s7client::{S7DataType, S7Value};
+    ///
+    /// let value = S7Value::DInt(-100_000);
+    /// assert_eq!(S7Value::decode(&value.encode(), S7DataType::S7DINT)?, value);
+    /// # Ok::<(), s7client::errors::Error>(())
+    /// ```
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Bool(value) => vec![u8::from(*value)],
+            Self::Byte(value) => vec![*value],
+            Self::Char(value) => vec![u8::try_from(*value).unwrap_or(b'?')],
+            Self::Word(value) => value.to_be_bytes().to_vec(),
+            Self::Int(value) => value.to_be_bytes().to_vec(),
+            Self::DWord(value) => value.to_be_bytes().to_vec(),
+            Self::DInt(value) => value.to_be_bytes().to_vec(),
+            Self::Real(value) => value.to_be_bytes().to_vec(),
+        }
+    }
+
+    /// Data type of the value in the PLC
+    #[must_use]
+    pub fn data_type(&self) -> S7DataType {
+        match self {
+            Self::Bool(_) => S7DataType::S7BIT,
+            Self::Byte(_) => S7DataType::S7BYTE,
+            Self::Char(_) => S7DataType::S7CHAR,
+            Self::Word(_) => S7DataType::S7WORD,
+            Self::Int(_) => S7DataType::S7INT,
+            Self::DWord(_) => S7DataType::S7DWORD,
+            Self::DInt(_) => S7DataType::S7DINT,
+            Self::Real(_) => S7DataType::S7REAL,
+        }
+    }
+}
+
+/// Integer data types holding scaled fixed-point values, see [`S7Client::db_read_fixed`](crate::S7Client::db_read_fixed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntType {
+    /// `BYTE` (0 to 255)
+    Byte,
+    /// `WORD` (0 to 65535)
+    Word,
+    /// `INT` (-32768 to 32767)
+    Int,
+    /// `DWORD` (0 to 4294967295)
+    DWord,
+    /// `DINT` (-2147483648 to 2147483647)
+    DInt,
+}
+
+impl IntType {
+    /// Number of bytes of the integer in the PLC
+    pub(crate) fn size(self) -> u16 {
+        match self {
+            Self::Byte => 1,
+            Self::Word | Self::Int => 2,
+            Self::DWord | Self::DInt => 4,
+        }
+    }
+
+    /// Decode the big-endian integer multiplied by `scale`
+    pub(crate) fn decode_scaled(self, bytes: &[u8], scale: f64) -> Result<f64, Error> {
+        let raw = match self {
+            Self::Byte => f64::from(u8::from_s7_bytes(bytes)?),
+            Self::Word => f64::from(u16::from_s7_bytes(bytes)?),
+            Self::Int => f64::from(i16::from_s7_bytes(bytes)?),
+            Self::DWord => f64::from(u32::from_s7_bytes(bytes)?),
+            Self::DInt => f64::from(i32::from_s7_bytes(bytes)?),
+        };
+        Ok(raw * scale)
+    }
+
+    /// Encode `value` divided by `scale`, rounded to the nearest integer with halves away from zero
+    pub(crate) fn encode_scaled(self, value: f64, scale: f64) -> Result<Vec<u8>, Error> {
+        if !scale.is_finite() || scale == 0.0 {
+            return Err(Error::Conversion(format!("Invalid scale {scale}")));
+        }
+        let raw = (value / scale).round();
+        let (min, max) = match self {
+            Self::Byte => (f64::from(u8::MIN), f64::from(u8::MAX)),
+            Self::Word => (f64::from(u16::MIN), f64::from(u16::MAX)),
+            Self::Int => (f64::from(i16::MIN), f64::from(i16::MAX)),
+            Self::DWord => (f64::from(u32::MIN), f64::from(u32::MAX)),
+            Self::DInt => (f64::from(i32::MIN), f64::from(i32::MAX)),
+        };
+        // also rejects NaN
+        if !(min..=max).contains(&raw) {
+            return Err(Error::Conversion(format!(
+                "Value {value} with scale {scale} does not fit into {self:?}"
+            )));
+        }
+
+        // the range check above guarantees the raw value fits into the target type
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(match self {
+            Self::Byte => vec![raw as u8],
+            Self::Word => (raw as u16).to_be_bytes().to_vec(),
+            Self::Int => (raw as i16).to_be_bytes().to_vec(),
+            Self::DWord => (raw as u32).to_be_bytes().to_vec(),
+            Self::DInt => (raw as i32).to_be_bytes().to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_big_endian_values() {
+        assert_eq!(
+            S7Value::decode(&[0x01], S7DataType::S7BIT),
+            Ok(S7Value::Bool(true))
+        );
+        assert_eq!(
+            S7Value::decode(&[0xFF, 0xFE], S7DataType::S7INT),
+            Ok(S7Value::Int(-2))
+        );
+        assert_eq!(
+            S7Value::decode(&[0x12, 0x34, 0x56, 0x78], S7DataType::S7DWORD),
+            Ok(S7Value::DWord(0x1234_5678))
+        );
+        assert_eq!(
+            S7Value::decode(&1.5_f32.to_be_bytes(), S7DataType::S7REAL),
+            Ok(S7Value::Real(1.5))
+        );
+        assert!(matches!(
+            S7Value::decode(&[0x00], S7DataType::S7WORD),
+            Err(Error::Conversion(_))
+        ));
+        assert!(S7Value::decode(&[0x00, 0x01], S7DataType::S7TIMER).is_err());
+    }
+
+    #[test]
+    fn decode_arrays_of_whole_elements() {
+        let bytes = [1.5_f32.to_be_bytes(), (-2.0_f32).to_be_bytes()].concat();
+        assert_eq!(f32::from_s7_array(&bytes), Ok(vec![1.5, -2.0]));
+        assert_eq!(f32::from_s7_array(&[]), Ok(vec![]));
+        assert!(matches!(
+            f32::from_s7_array(&bytes[..6]),
+            Err(Error::Conversion(_))
+        ));
+    }
+
+    #[test]
+    fn scaled_integers_round_half_away_from_zero() {
+        let tenth = |int_type: IntType, value: f64| int_type.encode_scaled(value, 0.1);
+
+        assert_eq!(
+            tenth(IntType::Int, 23.5),
+            Ok(235_i16.to_be_bytes().to_vec())
+        );
+        assert_eq!(
+            tenth(IntType::Int, 23.46),
+            Ok(235_i16.to_be_bytes().to_vec())
+        );
+        assert_eq!(
+            tenth(IntType::Int, 23.44),
+            Ok(234_i16.to_be_bytes().to_vec())
+        );
+        assert_eq!(IntType::Int.encode_scaled(2.5, 1.0), Ok(vec![0x00, 0x03]));
+        assert_eq!(IntType::Int.encode_scaled(-2.5, 1.0), Ok(vec![0xFF, 0xFD]));
+        assert_eq!(IntType::Byte.encode_scaled(255.4, 1.0), Ok(vec![0xFF]));
+
+        assert!(matches!(
+            IntType::Byte.encode_scaled(-1.0, 1.0),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            IntType::Byte.encode_scaled(255.5, 1.0),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            tenth(IntType::Int, 3276.8),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            tenth(IntType::Word, f64::NAN),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            IntType::DInt.encode_scaled(1.0, 0.0),
+            Err(Error::Conversion(_))
+        ));
+
+        assert_eq!(IntType::Int.decode_scaled(&[0xFF, 0x15], 0.1), Ok(-23.5));
+        assert_eq!(
+            IntType::DWord.decode_scaled(&[0xFF; 4], 1.0),
+            Ok(f64::from(u32::MAX))
+        );
+        assert!(IntType::DInt.decode_scaled(&[0x00; 2], 1.0).is_err());
+    }
+
+    // xorshift generator, so the property tests cover many values without a test dependency
+    fn random_words(count: usize) -> impl Iterator<Item = u32> {
+        let mut state = 0x2545_F491_u32;
+        std::iter::repeat_with(move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        })
+        .take(count)
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn sample_values() -> Vec<S7Value> {
+        let edges = [
+            0_u32,
+            1,
+            0x7F,
+            0x80,
+            0xFF,
+            0x7FFF,
+            0x8000,
+            0xFFFF,
+            0x7FFF_FFFF,
+            0x8000_0000,
+            u32::MAX,
+        ];
+        edges
+            .into_iter()
+            .chain(random_words(1000))
+            .flat_map(|word| {
+                [
+                    S7Value::Bool(word % 2 == 1),
+                    S7Value::Byte(word as u8),
+                    S7Value::Char(char::from(word as u8)),
+                    S7Value::Word(word as u16),
+                    S7Value::Int(word as i16),
+                    S7Value::DWord(word),
+                    S7Value::DInt(word as i32),
+                    S7Value::Real(f32::from_bits(word)),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        for value in sample_values() {
+            let data = value.encode();
+            assert_eq!(data.len(), usize::from(value.data_type().size()));
+
+            let decoded = S7Value::decode(&data, value.data_type()).unwrap();
+            match (&value, &decoded) {
+                // NaN is not equal to itself, compare the representation instead
+                (S7Value::Real(expected), S7Value::Real(actual)) => {
+                    assert_eq!(expected.to_bits(), actual.to_bits());
+                }
+                _ => assert_eq!(decoded, value),
+            }
+        }
+    }
+
+    #[test]
+    fn decode_encode_round_trip() {
+        let types = [
+            S7DataType::S7BYTE,
+            S7DataType::S7CHAR,
+            S7DataType::S7WORD,
+            S7DataType::S7INT,
+            S7DataType::S7DWORD,
+            S7DataType::S7DINT,
+            S7DataType::S7REAL,
+        ];
+        for word in random_words(1000) {
+            for data_type in types {
+                let data = &word.to_be_bytes()[..usize::from(data_type.size())];
+                let value = S7Value::decode(data, data_type).unwrap();
+                assert_eq!(value.encode(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn encode_non_latin1_char() {
+        assert_eq!(S7Value::Char('€').encode(), [b'?']);
+    }
+}
@@ -95,13 +95,10 @@ async fn test_bit_exchange() {
         .await
         .expect("Could not write bit");
 
-    assert_eq!(
-        client
-            .db_read_bit(TEST_DB, test_byte, test_bit)
-            .await
-            .expect("Could not read bit"),
-        true
-    );
+    assert!(client
+        .db_read_bit(TEST_DB, test_byte, test_bit)
+        .await
+        .expect("Could not read bit"));
 
     // write bit to false
     client
@@ -109,13 +106,10 @@ async fn test_bit_exchange() {
         .await
         .expect("Could not write bit");
 
-    assert_eq!(
-        client
-            .db_read_bit(TEST_DB, test_byte, test_bit)
-            .await
-            .expect("Could not read bit"),
-        false
-    );
+    assert!(!client
+        .db_read_bit(TEST_DB, test_byte, test_bit)
+        .await
+        .expect("Could not read bit"));
 }
 
 #[tokio::test]
@@ -125,7 +119,7 @@ async fn test_multi() {
         .expect("Could not create pool");
 
     let res = client
-        .db_read_multi(&vec![
+        .db_read_multi(&[
             S7ReadAccess::bytes(TEST_DB, 0, 300),
             S7ReadAccess::bit(TEST_DB, 0, 1),
         ])